@@ -0,0 +1,128 @@
+use crate::hyprland::{
+    ConfigBind, ConfigLine, DescriptionStyle, KeyBindEntry, KeyBindings, hyprland_config_path,
+    parse_config_lines, read_config_lines, reload_hyprland, replace_config_line,
+    rewrite_bind_description,
+};
+use std::path::{Path, PathBuf};
+
+/// Inline description edit in progress
+pub struct DescriptionEdit {
+    pub entry: KeyBindEntry,
+    pub text: String,
+}
+
+/// A description change mapped to exactly one config line
+pub struct PendingWrite {
+    /// Config the binds were loaded from; `line` may be in a file it sources
+    pub config: PathBuf,
+    pub entry: KeyBindEntry,
+    pub description: String,
+    pub style: DescriptionStyle,
+    pub line: ConfigLine,
+    pub new_text: String,
+}
+
+/// State of the save dialog
+pub enum DescriptionSave {
+    Preview(Box<PendingWrite>),
+    Failed(String),
+    Done(String),
+}
+
+/// Find the line of `config` defining `entry` and compute its rewritten text
+///
+/// Refuses when the bind is defined on zero or several lines, or when the
+/// rewritten line would no longer parse back to the same bind.
+pub fn prepare_write(
+    entry: &KeyBindEntry,
+    description: &str,
+    style: DescriptionStyle,
+    config: &Path,
+) -> Result<Box<PendingWrite>, String> {
+    let lines = read_config_lines(config)
+        .map_err(|e| format!("Failed to read {}: {e}", config.display()))?;
+    let binds = parse_config_lines(&lines);
+
+    let matches: Vec<&ConfigBind> = binds.iter().filter(|b| b.entry.same_bind(entry)).collect();
+    let bind = match matches.as_slice() {
+        [bind] => *bind,
+        [] => return Err("This bind was not found in the Hyprland config files".to_string()),
+        many => {
+            return Err(format!(
+                "This bind is defined on {} lines; refusing to guess which one to edit",
+                many.len()
+            ));
+        }
+    };
+
+    let line = lines[bind.line].clone();
+    let new_text = rewrite_bind_description(&line.text, description, style)
+        .ok_or_else(|| "This line cannot be rewritten safely".to_string())?;
+
+    let mut check = lines;
+    check[bind.line].text.clone_from(&new_text);
+    let verified = parse_config_lines(&check).iter().any(|b| {
+        b.line == bind.line
+            && b.entry.same_bind(entry)
+            && match style {
+                DescriptionStyle::Bindd => b.entry.description == description,
+                DescriptionStyle::Comment => b.comment == description,
+            }
+    });
+    if !verified {
+        return Err("The rewritten line would not map back to this bind".to_string());
+    }
+
+    Ok(Box::new(PendingWrite {
+        config: config.to_path_buf(),
+        entry: entry.clone(),
+        description: description.to_string(),
+        style,
+        line,
+        new_text,
+    }))
+}
+
+/// Write the change, keeping a backup, and reload Hyprland when it runs with that config
+pub fn apply_write(pending: &PendingWrite) -> Result<String, String> {
+    let backup = replace_config_line(
+        &pending.line,
+        &pending.new_text,
+        &crate::config::backup_dir(),
+    )
+    .map_err(|e| format!("Failed to write {}: {e}", pending.line.path.display()))?;
+    let saved = format!(
+        "Saved {}:{} (backup: {})",
+        pending.line.path.display(),
+        pending.line.number,
+        backup.display()
+    );
+    if pending.config == hyprland_config_path() {
+        reload_hyprland(None)
+            .map_err(|e| format!("{saved}, but reloading Hyprland failed: {e}"))?;
+    }
+    Ok(saved)
+}
+
+/// Use trailing `# comments` from `config` as descriptions for binds without one
+///
+/// Only binds defined on exactly one config line are filled in.
+pub fn apply_comment_descriptions(keybindings: &mut KeyBindings, config: &Path) {
+    let Ok(lines) = read_config_lines(config) else {
+        return;
+    };
+    let binds = parse_config_lines(&lines);
+
+    for entry in keybindings
+        .entries
+        .iter_mut()
+        .filter(|e| e.description.is_empty())
+    {
+        let mut matches = binds.iter().filter(|b| b.entry.same_bind(entry));
+        if let (Some(bind), None) = (matches.next(), matches.next())
+            && !bind.comment.is_empty()
+        {
+            entry.description.clone_from(&bind.comment);
+        }
+    }
+}
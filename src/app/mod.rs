@@ -1,20 +1,32 @@
+mod editor;
 pub mod sorting;
 mod state;
 
 pub use state::AppState;
+use state::AppliedTheme;
 
+use crate::hyprland::{Annotation, KeyBindEntry, SourceChain};
 use crate::ui::SortColumn;
+use crate::ui::details::{AnnotationDraft, DetailAction};
+use crate::ui::edit::{SaveDialog, SaveDialogAction};
+use crate::ui::instances::InstanceAction;
+use crate::ui::system_theme::{ColorScheme, SystemTheme};
+use crate::ui::table::{EditResult, TableResponse, TableView};
+use editor::{DescriptionEdit, DescriptionSave};
 use eframe::egui;
+use std::path::PathBuf;
 
 pub struct KeybindsApp {
     state: AppState,
 }
 
 impl KeybindsApp {
-    pub fn new() -> Self {
-        Self {
-            state: AppState::new(),
-        }
+    pub fn new(ctx: &egui::Context, sources: SourceChain) -> Self {
+        let mut app = Self {
+            state: AppState::new(sources),
+        };
+        app.apply_fonts(ctx);
+        app
     }
 
     fn handle_sort_click(&mut self, column: SortColumn) {
@@ -24,14 +36,18 @@ impl KeybindsApp {
         self.state.sort_state = new_state;
     }
 
-    fn get_filtered_and_sorted_entries(&self) -> Vec<crate::hyprland::KeyBindEntry> {
-        sorting::filter_and_sort(
+    fn get_filtered_and_sorted_entries(&self) -> Vec<KeyBindEntry> {
+        let mut entries = sorting::filter_and_sort(
             &self.state.keybindings.entries,
             &self.state.search_query,
             &self.state.search_options,
             self.state.sort_column,
             self.state.sort_state,
-        )
+        );
+        if self.state.flags.group_by_category {
+            self.state.categorizer.group(&mut entries);
+        }
+        entries
     }
 
     fn load_logo_texture_if_needed(&mut self, ctx: &egui::Context) {
@@ -49,6 +65,9 @@ impl KeybindsApp {
     }
 
     fn handle_zen_mode_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
         let prev_zen = self.state.flags.zen_mode;
         crate::ui::zen::handle_zen_keyboard_shortcuts(ctx, &mut self.state.flags.zen_mode);
         if self.state.flags.zen_mode != prev_zen {
@@ -57,9 +76,8 @@ impl KeybindsApp {
     }
 
     fn handle_search_bar_focus(&self, ctx: &egui::Context) {
-        let search_bar_focused = ctx.memory(|m| m.focused() == Some(egui::Id::new("search_bar")));
         if !self.state.flags.zen_mode
-            && !search_bar_focused
+            && !ctx.wants_keyboard_input()
             && ctx.input(|i| i.key_pressed(egui::Key::Slash))
         {
             ctx.memory_mut(|m| m.request_focus(egui::Id::new("search_bar")));
@@ -70,24 +88,150 @@ impl KeybindsApp {
         }
     }
 
-    fn apply_theme_or_css(&mut self, ctx: &egui::Context) {
-        if crate::ui::styling::css::has_custom_theme() {
-            let path = crate::ui::styling::css::default_css_path();
-            if let Ok(meta) = std::fs::metadata(&path)
-                && let Ok(modified) = meta.modified()
-            {
-                let changed = self.state.last_css_mtime.is_none_or(|prev| modified > prev);
-                if changed {
-                    let _ = crate::ui::styling::css::apply_from_path(ctx, &path.to_string_lossy());
-                    self.state.last_css_mtime = Some(modified);
+    /// Desktop colour scheme while following it; starts listening the first time
+    fn system_scheme(&mut self, ctx: &egui::Context) -> Option<ColorScheme> {
+        if self.state.theme != crate::ui::Theme::System {
+            return None;
+        }
+        self.state
+            .system_theme
+            .get_or_insert_with(|| SystemTheme::start(ctx))
+            .scheme()
+    }
+
+    /// Act on files changed in the config directory since the last frame
+    fn handle_file_changes(&mut self, ctx: &egui::Context) {
+        use crate::ui::styling::import::pywal_colors_path;
+
+        let watcher = self.state.watcher.get_or_insert_with(|| {
+            let pywal = pywal_colors_path();
+            let dirs = crate::config::WatchDirs {
+                config: crate::config::config_dir(),
+                themes: crate::config::themes_dir(),
+                pywal: pywal.parent().map(PathBuf::from).unwrap_or_default(),
+            };
+            let repaint = ctx.clone();
+            crate::config::ConfigWatcher::start(dirs, move || repaint.request_repaint())
+                .unwrap_or_else(|e| {
+                    eprintln!("Failed to watch the config directory: {e}");
+                    crate::config::ConfigWatcher::default()
+                })
+        });
+        let changes = watcher.take();
+        if changes.config {
+            self.state.reload_config();
+        }
+        if changes.css {
+            self.state.applied_theme = None;
+        }
+        self.sync_pywal(changes.pywal);
+    }
+
+    /// Import the pywal theme when sync is turned on and whenever `colors.json` changed
+    fn sync_pywal(&mut self, changed: bool) {
+        use crate::ui::styling::import::{ImportSource, import_theme, pywal_colors_path};
+
+        if !self.state.pywal_sync {
+            self.state.pywal_synced = false;
+            return;
+        }
+        if self.state.pywal_synced && !changed {
+            return;
+        }
+        self.state.pywal_synced = true;
+        match import_theme(&ImportSource::Pywal(pywal_colors_path()), None) {
+            Ok((name, _)) => {
+                if self.state.css_theme.as_ref() != Some(&name) {
+                    self.state.css_theme = Some(name);
+                    self.state.save_config();
                 }
+                self.state.applied_theme = None;
             }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    /// Set up the fonts and UI scale when they changed, and keep a scale changed with
+    /// `Ctrl+=` or `Ctrl+-`
+    fn apply_fonts(&mut self, ctx: &egui::Context) {
+        use crate::ui::styling::fonts::{SCALE_RANGE, setup_custom_fonts};
+
+        let zoom = ctx.zoom_factor();
+        if let Some(applied) = &self.state.applied_fonts
+            && (zoom - applied.scale).abs() > f32::EPSILON
+        {
+            self.state.fonts.scale = zoom.clamp(*SCALE_RANGE.start(), *SCALE_RANGE.end());
+            self.state.save_config();
+        }
+        let fonts = &self.state.fonts;
+        let applied = self.state.applied_fonts.as_ref();
+        if applied == Some(fonts) {
+            return;
+        }
+        if applied.is_none_or(|a| {
+            a.family != fonts.family || a.weight != fonts.weight || a.monospace != fonts.monospace
+        }) {
+            setup_custom_fonts(ctx, fonts);
+        }
+        ctx.set_zoom_factor(fonts.scale);
+        self.state.applied_fonts = Some(fonts.clone());
+    }
+
+    /// Apply the picked theme when it, the desktop scheme, or its file changed
+    fn apply_theme_or_css(&mut self, ctx: &egui::Context) {
+        use crate::ui::styling::{css, themes};
+
+        self.handle_file_changes(ctx);
+        let wanted = AppliedTheme {
+            css_theme: self
+                .state
+                .theme_preview
+                .clone()
+                .or_else(|| self.state.css_theme.clone()),
+            theme: self.state.theme,
+            system_scheme: self.system_scheme(ctx),
+        };
+        if self.state.applied_theme.as_ref() == Some(&wanted) {
+            return;
+        }
+
+        let named = wanted
+            .css_theme
+            .as_ref()
+            .and_then(|name| themes::find_theme(name));
+        let default_path = css::default_css_path();
+        let (report, label) = if let Some(theme) = &named {
+            (theme.apply(ctx), theme.name.clone())
+        } else if css::has_custom_theme() {
+            (
+                css::apply_from_path(ctx, &default_path.to_string_lossy()),
+                default_path.display().to_string(),
+            )
         } else {
-            match self.state.theme {
-                crate::ui::Theme::Dark => ctx.set_visuals(egui::Visuals::dark()),
-                crate::ui::Theme::Light => ctx.set_visuals(egui::Visuals::light()),
+            css::clear_element_styles(ctx);
+            let visuals = if wanted.theme == crate::ui::Theme::Light
+                || wanted.system_scheme == Some(ColorScheme::Light)
+            {
+                egui::Visuals::light()
+            } else {
+                egui::Visuals::dark()
+            };
+            ctx.set_visuals(visuals);
+            self.state.applied_theme = Some(wanted);
+            return;
+        };
+        // The options window lists these too; print them for terminal users
+        if self.state.theme_preview.is_none() {
+            match report {
+                Ok(diagnostics) => {
+                    for diagnostic in diagnostics {
+                        eprintln!("{label}:{diagnostic}");
+                    }
+                }
+                Err(e) => eprintln!("{e}"),
             }
         }
+        self.state.applied_theme = Some(wanted);
     }
 
     fn handle_zen_info_modal(&mut self, ctx: &egui::Context) {
@@ -108,6 +252,9 @@ impl KeybindsApp {
             self.state.flags.show_options_window = false;
         }
 
+        if !self.state.flags.show_options_window {
+            self.state.theme_preview = None;
+        }
         let options_viewport_id = egui::ViewportId::from_hash_of("options");
         if self.state.flags.show_options_window {
             ctx.show_viewport_immediate(
@@ -123,15 +270,25 @@ impl KeybindsApp {
                     }
                     egui::CentralPanel::default().show(vctx, |ui| {
                         let prev_zen = self.state.flags.zen_mode;
+                        let previewed = self.state.theme_preview.take();
                         let mut opts = crate::ui::options::OptionsState {
                             theme: &mut self.state.theme,
+                            css_theme: &mut self.state.css_theme,
+                            theme_preview: &mut self.state.theme_preview,
+                            pywal_sync: &mut self.state.pywal_sync,
+                            fonts: &mut self.state.fonts,
+                            icon_set: &mut self.state.icon_set,
                             column_visibility: &mut self.state.column_visibility,
                             search_options: &mut self.state.search_options,
                             zen_mode: &mut self.state.flags.zen_mode,
+                            group_by_category: &mut self.state.flags.group_by_category,
                             show_zen_info_modal: &mut self.state.flags.show_zen_info_modal,
                             export_request: &mut self.state.flags.export_request,
                         };
                         crate::ui::options::render_options_contents(vctx, ui, &mut opts);
+                        if self.state.theme_preview != previewed {
+                            ctx.request_repaint();
+                        }
                         if !prev_zen && self.state.flags.zen_mode {
                             self.state.flags.show_options_window = false;
                         }
@@ -149,8 +306,7 @@ impl KeybindsApp {
                 let _ = std::fs::create_dir_all(&dir);
                 let epoch = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0);
+                    .map_or(0, |d| d.as_secs());
                 let file_name = format!("keybindings_{epoch}.json");
                 let path = dir.join(file_name);
                 if std::fs::write(&path, json).is_ok() {
@@ -184,8 +340,8 @@ impl KeybindsApp {
     }
 
     fn handle_keyboard_navigation(&mut self, ctx: &egui::Context, filtered_len: usize) {
-        let search_bar_focused = ctx.memory(|m| m.focused() == Some(egui::Id::new("search_bar")));
-        if !search_bar_focused && !self.state.flags.show_options_window && filtered_len > 0 {
+        if !ctx.wants_keyboard_input() && !self.state.flags.show_options_window && filtered_len > 0
+        {
             let mut sel = self.state.selected_row.unwrap_or(0);
             let changed = if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
                 sel = (sel + 1).min(filtered_len - 1);
@@ -216,17 +372,244 @@ impl KeybindsApp {
         }
     }
 
+    fn start_description_edit(&mut self, ctx: &egui::Context, entry: &KeyBindEntry) {
+        self.state.description_edit = Some(DescriptionEdit {
+            entry: entry.clone(),
+            text: entry.description.clone(),
+        });
+        ctx.memory_mut(|m| m.request_focus(egui::Id::new("description_editor")));
+    }
+
+    fn finish_description_edit(&mut self, result: EditResult) {
+        let Some(edit) = self.state.description_edit.take() else {
+            return;
+        };
+        let text = edit.text.trim();
+        if result == EditResult::Cancel || text == edit.entry.description {
+            return;
+        }
+        let Some(config) = &self.state.edit_config else {
+            self.state.description_save = Some(DescriptionSave::Failed(
+                "These binds were not loaded from a Hyprland config that can be edited".to_string(),
+            ));
+            return;
+        };
+        let save =
+            match editor::prepare_write(&edit.entry, text, self.state.description_style, config) {
+                Ok(pending) => DescriptionSave::Preview(pending),
+                Err(e) => DescriptionSave::Failed(e),
+            };
+        self.state.description_save = Some(save);
+    }
+
+    fn handle_table_response(
+        &mut self,
+        ctx: &egui::Context,
+        response: TableResponse,
+        filtered: &[KeyBindEntry],
+    ) {
+        if let Some(clicked_column) = response.clicked_column {
+            self.handle_sort_click(clicked_column);
+        }
+        if let Some(row) = response.clicked_row {
+            self.state.selected_row = Some(row);
+        }
+        if let Some(result) = response.edit_result {
+            self.finish_description_edit(result);
+        } else if let Some(row) = response.edit_row
+            && let Some(entry) = filtered.get(row)
+            && self.state.description_save.is_none()
+        {
+            self.state.selected_row = Some(row);
+            self.start_description_edit(ctx, entry);
+        }
+    }
+
+    fn handle_description_save(&mut self, ctx: &egui::Context) {
+        let Some(save) = &self.state.description_save else {
+            return;
+        };
+        let location;
+        let dialog = match save {
+            DescriptionSave::Preview(pending) => {
+                location = format!("{}:{}", pending.line.path.display(), pending.line.number);
+                SaveDialog::Preview {
+                    location,
+                    old_line: &pending.line.text,
+                    new_line: &pending.new_text,
+                    style: pending.style,
+                }
+            }
+            DescriptionSave::Failed(message) => SaveDialog::Failed(message),
+            DescriptionSave::Done(message) => SaveDialog::Done(message),
+        };
+
+        match crate::ui::edit::render_save_dialog(ctx, &dialog) {
+            Some(SaveDialogAction::Confirm) => {
+                if let Some(DescriptionSave::Preview(pending)) = self.state.description_save.take()
+                {
+                    let save = match editor::apply_write(&pending) {
+                        Ok(message) => {
+                            self.state.reload_keybindings();
+                            DescriptionSave::Done(message)
+                        }
+                        Err(e) => DescriptionSave::Failed(e),
+                    };
+                    self.state.description_save = Some(save);
+                }
+            }
+            Some(SaveDialogAction::SetStyle(style)) => {
+                if let Some(DescriptionSave::Preview(pending)) = self.state.description_save.take()
+                {
+                    self.state.description_style = style;
+                    self.state.save_config();
+                    let save = match editor::prepare_write(
+                        &pending.entry,
+                        &pending.description,
+                        style,
+                        &pending.config,
+                    ) {
+                        Ok(pending) => DescriptionSave::Preview(pending),
+                        Err(e) => DescriptionSave::Failed(e),
+                    };
+                    self.state.description_save = Some(save);
+                }
+            }
+            Some(SaveDialogAction::Close) => self.state.description_save = None,
+            None => {}
+        }
+    }
+
+    fn handle_detail_panel(&mut self, ctx: &egui::Context, filtered: &[KeyBindEntry]) {
+        let entry = self.state.selected_row.and_then(|row| filtered.get(row));
+        let bind = entry.map(KeyBindEntry::bind_id);
+        if bind.as_ref() != self.state.annotation_draft.as_ref().map(|d| &d.bind) {
+            self.state.annotation_draft = bind.map(|bind| {
+                let annotation = self.state.annotations.get(&bind).cloned();
+                AnnotationDraft::new(bind, annotation.as_ref())
+            });
+        }
+
+        let orphans: Vec<&Annotation> = self
+            .state
+            .orphans
+            .iter()
+            .filter_map(|bind| self.state.annotations.get(bind))
+            .collect();
+        let action = crate::ui::details::render_detail_panel(
+            ctx,
+            entry,
+            self.state.annotation_draft.as_mut(),
+            &orphans,
+        );
+
+        match action {
+            Some(DetailAction::Save) => {
+                if let Some(draft) = &self.state.annotation_draft {
+                    self.state.set_annotation(draft.to_annotation());
+                }
+            }
+            Some(DetailAction::Revert) => self.state.annotation_draft = None,
+            Some(DetailAction::DeleteOrphan(bind)) => self.state.remove_annotation(&bind),
+            None => {}
+        }
+    }
+
+    fn handle_history_window(&mut self, ctx: &egui::Context) {
+        if !self.state.flags.show_diff_window {
+            self.state.diff = None;
+            return;
+        }
+        if self.state.diff.is_none() {
+            self.state.open_history();
+        }
+        let labels: Vec<String> = self
+            .state
+            .snapshots
+            .iter()
+            .map(|p| crate::ui::diff::snapshot_label(p))
+            .collect();
+        let changed = crate::ui::diff::render_diff_window(
+            ctx,
+            &mut self.state.flags.show_diff_window,
+            &labels,
+            &mut self.state.diff_selection,
+            self.state.diff.as_ref(),
+        );
+        if changed {
+            self.state.update_diff();
+        }
+    }
+
+    fn handle_instances_window(&mut self, ctx: &egui::Context) {
+        if !self.state.flags.show_instances_window {
+            self.state.instances = None;
+            self.state.instance_pairs = None;
+            return;
+        }
+        if self.state.instances.is_none() {
+            self.state.open_instances();
+        }
+        let action = crate::ui::instances::render_instances_window(
+            ctx,
+            &mut self.state.flags.show_instances_window,
+            self.state.instances.as_deref().unwrap_or_default(),
+            self.state.sources.instance(),
+            &mut self.state.instance_selection,
+            self.state.instance_pairs.as_ref(),
+        );
+        match action {
+            Some(InstanceAction::View(signature)) => self.state.view_instance(signature.as_deref()),
+            Some(InstanceAction::Compare) => self.state.compare_instances(),
+            Some(InstanceAction::Refresh) => self.state.open_instances(),
+            None => {}
+        }
+    }
+
+    fn handle_diagnostics_window(&mut self, ctx: &egui::Context) {
+        if !self.state.flags.show_diagnostics_window {
+            return;
+        }
+        let diagnostics = crate::ui::diagnostics::Diagnostics {
+            loaded_from: self.state.loaded_from,
+            source_errors: &self.state.source_errors,
+            warnings: &self.state.parse_warnings,
+            error_message: self.state.error_message.as_deref(),
+        };
+        let reload = crate::ui::diagnostics::render_diagnostics_window(
+            ctx,
+            &mut self.state.flags.show_diagnostics_window,
+            &diagnostics,
+        );
+        if reload {
+            self.state.reload_keybindings();
+        }
+    }
+
     fn render_main_ui(&mut self, ctx: &egui::Context) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let filtered = self.get_filtered_and_sorted_entries();
+        let filtered = self.get_filtered_and_sorted_entries();
+        if self.state.flags.show_detail_panel && !self.state.flags.zen_mode {
+            self.handle_detail_panel(ctx, &filtered);
+        }
 
+        egui::CentralPanel::default().show(ctx, |ui| {
             if !self.state.flags.zen_mode {
                 crate::ui::header::render_header(
                     ui,
                     &mut self.state.flags.show_options_window,
+                    &mut self.state.flags.show_detail_panel,
+                    &mut self.state.flags.show_diff_window,
+                    &mut self.state.flags.show_instances_window,
                     self.state.error_message.as_ref(),
                     self.state.logo_texture.as_ref(),
                 );
+                crate::ui::header::render_source_status(
+                    ui,
+                    self.state.loaded_from,
+                    &self.state.source_errors,
+                    self.state.parse_warnings.len(),
+                    &mut self.state.flags.show_diagnostics_window,
+                );
 
                 crate::ui::header::render_search_bar(ui, &mut self.state.search_query);
 
@@ -238,16 +621,33 @@ impl KeybindsApp {
             }
             self.handle_keyboard_navigation(ctx, filtered.len());
 
-            if let Some(clicked_column) = crate::ui::table::render_table(
+            let editing_row = self
+                .state
+                .description_edit
+                .as_ref()
+                .and_then(|edit| filtered.iter().position(|e| e.same_bind(&edit.entry)));
+            let editing = editing_row.zip(
+                self.state
+                    .description_edit
+                    .as_mut()
+                    .map(|edit| &mut edit.text),
+            );
+
+            let view = TableView {
+                column_visibility: &self.state.column_visibility,
+                sort_column: self.state.sort_column,
+                sort_state: self.state.sort_state,
+                group_by_category: self.state.flags.group_by_category,
+                editable: self.state.edit_config.is_some(),
+            };
+            let response = crate::ui::table::render_table(
                 ui,
                 &filtered,
-                &self.state.column_visibility,
-                self.state.sort_column,
-                self.state.sort_state,
+                &view,
                 self.state.selected_row,
-            ) {
-                self.handle_sort_click(clicked_column);
-            }
+                editing,
+            );
+            self.handle_table_response(ctx, response, &filtered);
         });
     }
 }
@@ -258,10 +658,15 @@ impl eframe::App for KeybindsApp {
         self.handle_zen_mode_shortcuts(ctx);
         self.handle_search_bar_focus(ctx);
         self.apply_theme_or_css(ctx);
+        self.apply_fonts(ctx);
         self.handle_zen_info_modal(ctx);
         self.handle_options_window(ctx);
         self.handle_export_request();
         self.handle_export_modal(ctx);
+        self.handle_description_save(ctx);
+        self.handle_history_window(ctx);
+        self.handle_instances_window(ctx);
+        self.handle_diagnostics_window(ctx);
         self.render_main_ui(ctx);
     }
 }
@@ -1,6 +1,5 @@
 use crate::hyprland::{KeyBindEntry, SearchOptions};
 use crate::ui::{SortColumn, SortState};
-use std::cmp::Ordering;
 
 pub fn filter_and_sort(
     entries: &[KeyBindEntry],
@@ -39,18 +38,14 @@ fn apply_sort(entries: &mut [KeyBindEntry], sort_column: SortColumn) {
             entries.sort_by(|a, b| a.description.cmp(&b.description));
         }
         SortColumn::Keybind => {
-            entries.sort_by(|a, b| {
-                let mod_cmp = a.modifiers.cmp(&b.modifiers);
-                if mod_cmp == Ordering::Equal {
-                    a.key.cmp(&b.key)
-                } else {
-                    mod_cmp
-                }
-            });
+            entries.sort_by_cached_key(|e| (e.modifiers.clone(), e.normalized_key().sort_key()));
         }
         SortColumn::Command => {
             entries.sort_by(|a, b| a.command.cmp(&b.command));
         }
+        SortColumn::Category => {
+            entries.sort_by(|a, b| a.category.cmp(&b.category));
+        }
     }
 }
 
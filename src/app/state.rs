@@ -1,8 +1,30 @@
-use crate::config::UserConfig;
-use crate::hyprland::{KeyBindings, SearchOptions, fetch_hyprctl_binds, parse_binds_output};
+use super::editor::{DescriptionEdit, DescriptionSave, apply_comment_descriptions};
+use crate::config::ConfigWatcher;
+use crate::hyprland::HyprlandInstance;
+use crate::hyprland::diff::BindPair;
+use crate::hyprland::{
+    Annotation, Annotations, BindDiff, BindId, Categorizer, DescriptionStyle, KeyBindings,
+    ParseWarning, SearchOptions, SourceChain, SourceError, SourceKind, read_keybindings_json,
+};
+use crate::ui::details::AnnotationDraft;
+use crate::ui::diff::DiffSelection;
+use crate::ui::instances::InstanceSelection;
+use crate::ui::styling::fonts::FontSettings;
+use crate::ui::styling::icons::IconSet;
+use crate::ui::system_theme::{ColorScheme, SystemTheme};
 use crate::ui::{ColumnVisibility, SortColumn, SortState, Theme};
 use eframe::egui;
 
+/// Theme applied to the window, to re-apply it only when something changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedTheme {
+    /// Named CSS theme; `None` for `hyprbind-theme.css` or the built-in theme
+    pub css_theme: Option<String>,
+    pub theme: Theme,
+    /// Desktop colour scheme, while following it
+    pub system_scheme: Option<ColorScheme>,
+}
+
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Default)]
 pub struct AppFlags {
@@ -10,10 +32,31 @@ pub struct AppFlags {
     pub zen_mode: bool,
     pub show_zen_info_modal: bool,
     pub export_request: bool,
+    pub show_detail_panel: bool,
+    pub group_by_category: bool,
+    pub show_diff_window: bool,
+    pub show_diagnostics_window: bool,
+    pub show_instances_window: bool,
 }
 
 pub struct AppState {
+    pub sources: SourceChain,
+    /// Source the current binds came from
+    pub loaded_from: Option<SourceKind>,
+    /// Hyprland config description edits are written to; `None` when the binds can't be edited
+    pub edit_config: Option<std::path::PathBuf>,
+    /// Sources that failed during the last load
+    pub source_errors: Vec<SourceError>,
+    /// Binds skipped by the parser during the last load
+    pub parse_warnings: Vec<ParseWarning>,
+    /// Keybindings as loaded, before annotations are merged in
+    pub loaded_keybindings: KeyBindings,
     pub keybindings: KeyBindings,
+    pub annotations: Annotations,
+    /// Annotated binds missing from the last load; only checked against this session's live binds
+    pub orphans: Vec<BindId>,
+    pub annotation_draft: Option<AnnotationDraft>,
+    pub categorizer: Categorizer,
     pub search_query: String,
     pub error_message: Option<String>,
     pub search_options: SearchOptions,
@@ -21,59 +64,263 @@ pub struct AppState {
     pub sort_state: SortState,
     pub flags: AppFlags,
     pub theme: Theme,
+    /// Desktop colour scheme for `Theme::System`; started the first time it is needed
+    pub system_theme: Option<SystemTheme>,
     pub column_visibility: ColumnVisibility,
     pub logo_texture: Option<egui::TextureHandle>,
     pub selected_row: Option<usize>,
     pub export_modal_path: Option<String>,
-    pub last_css_mtime: Option<std::time::SystemTime>,
+    /// `None` re-applies the theme on the next frame
+    pub applied_theme: Option<AppliedTheme>,
+    /// Watches the config directory; started with the first frame
+    pub watcher: Option<ConfigWatcher>,
+    /// Named CSS theme picked in the options window
+    pub css_theme: Option<String>,
+    /// Theme hovered in the picker, shown instead of `css_theme` while hovered
+    pub theme_preview: Option<String>,
+    /// Keep the `pywal` theme in sync with pywal's `colors.json`
+    pub pywal_sync: bool,
+    /// Whether the pywal theme was imported since sync was turned on
+    pub pywal_synced: bool,
+    pub fonts: FontSettings,
+    /// Fonts set up in the window; `None` sets them up on the next frame
+    pub applied_fonts: Option<FontSettings>,
+    pub icon_set: IconSet,
+    pub description_style: DescriptionStyle,
+    pub description_edit: Option<DescriptionEdit>,
+    pub description_save: Option<DescriptionSave>,
+    /// History snapshots, newest first
+    pub snapshots: Vec<std::path::PathBuf>,
+    pub diff_selection: DiffSelection,
+    pub diff: Option<Result<BindDiff, String>>,
+    /// Hyprland instances found in the runtime directory; `None` until scanned
+    pub instances: Option<Vec<HyprlandInstance>>,
+    pub instance_selection: InstanceSelection,
+    pub instance_pairs: Option<Result<Vec<BindPair>, String>>,
 }
 
 impl AppState {
-    pub fn new() -> Self {
-        let (keybindings, error_message) = match fetch_hyprctl_binds() {
-            Ok(raw_output) => (parse_binds_output(&raw_output), None),
-            Err(e) => (
-                KeyBindings::new(),
-                Some(format!("Failed to load keybindings: {e}")),
-            ),
-        };
-
+    pub fn new(sources: SourceChain) -> Self {
         let mut state = Self {
-            keybindings,
+            sources,
+            loaded_from: None,
+            edit_config: None,
+            source_errors: Vec::new(),
+            parse_warnings: Vec::new(),
+            loaded_keybindings: KeyBindings::new(),
+            keybindings: KeyBindings::new(),
+            annotations: crate::config::load_annotations(),
+            orphans: Vec::new(),
+            annotation_draft: None,
+            categorizer: Categorizer::default(),
             search_query: String::new(),
-            error_message,
+            error_message: None,
             search_options: SearchOptions::default(),
             sort_column: SortColumn::Keybind,
             sort_state: SortState::None,
             flags: AppFlags::default(),
             theme: Theme::Dark,
+            system_theme: None,
             column_visibility: ColumnVisibility::default(),
             logo_texture: None,
             selected_row: None,
             export_modal_path: None,
-            last_css_mtime: None,
+            applied_theme: None,
+            watcher: None,
+            css_theme: None,
+            theme_preview: None,
+            pywal_sync: false,
+            pywal_synced: false,
+            fonts: FontSettings::default(),
+            applied_fonts: None,
+            icon_set: IconSet::default(),
+            description_style: DescriptionStyle::default(),
+            description_edit: None,
+            description_save: None,
+            snapshots: Vec::new(),
+            diff_selection: DiffSelection::default(),
+            diff: None,
+            instances: None,
+            instance_selection: InstanceSelection::default(),
+            instance_pairs: None,
         };
 
         state.load_config();
+        state.reload_keybindings();
         state
     }
 
+    pub fn reload_keybindings(&mut self) {
+        match self.sources.load() {
+            Ok(loaded) => {
+                let mut keybindings = loaded.keybindings;
+                if loaded.live
+                    && let Some(config) = &loaded.config_path
+                {
+                    apply_comment_descriptions(&mut keybindings, config);
+                }
+                self.categorizer.apply(&mut keybindings);
+                // Other instances, `--input` files and empty loads would make every annotation look orphaned
+                self.orphans = if loaded.live && !keybindings.entries.is_empty() {
+                    self.annotations
+                        .orphans(&keybindings)
+                        .iter()
+                        .map(|a| a.bind.clone())
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                self.loaded_keybindings = keybindings;
+                self.loaded_from = Some(loaded.source);
+                self.edit_config = loaded.config_path;
+                self.source_errors = loaded.skipped;
+                self.parse_warnings = loaded.warnings;
+                self.error_message = None;
+                if loaded.live
+                    && let Err(e) = crate::config::save_snapshot(&self.loaded_keybindings)
+                {
+                    self.error_message = Some(format!("Failed to save snapshot: {e}"));
+                }
+            }
+            Err(e) => {
+                self.loaded_keybindings = KeyBindings::new();
+                self.orphans = Vec::new();
+                self.loaded_from = None;
+                self.edit_config = None;
+                self.source_errors = e.errors;
+                self.parse_warnings = Vec::new();
+                self.error_message = None;
+            }
+        }
+        if self.edit_config.is_none() {
+            self.description_edit = None;
+        }
+        self.keybindings = self.annotations.apply(&self.loaded_keybindings);
+    }
+
+    /// Store an annotation and merge it into the displayed keybindings
+    pub fn set_annotation(&mut self, annotation: Annotation) {
+        self.annotations.set(annotation);
+        self.annotations_changed();
+    }
+
+    pub fn remove_annotation(&mut self, bind: &BindId) {
+        self.annotations.remove(bind);
+        self.orphans.retain(|b| b != bind);
+        self.annotations_changed();
+    }
+
+    fn annotations_changed(&mut self) {
+        if let Err(e) = crate::config::save_annotations(&self.annotations) {
+            self.error_message = Some(format!("Failed to save annotations: {e}"));
+        }
+        self.keybindings = self.annotations.apply(&self.loaded_keybindings);
+    }
+
+    /// Scan for instances and preselect two of them for comparison
+    pub fn open_instances(&mut self) {
+        let instances = self.instances.insert(crate::hyprland::list_instances());
+        let current = self
+            .sources
+            .instance()
+            .map(String::from)
+            .or_else(|| std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok());
+        let signatures: Vec<&String> = instances.iter().map(|i| &i.signature).collect();
+        let left = current
+            .filter(|c| signatures.contains(&c))
+            .or_else(|| signatures.first().map(|s| (*s).clone()));
+        let right = signatures
+            .iter()
+            .find(|s| Some(**s) != left.as_ref())
+            .map(|s| (*s).clone());
+        self.instance_selection.left = left;
+        self.instance_selection.right = right;
+        self.compare_instances();
+    }
+
+    /// Load binds of both selected instances and line them up
+    pub fn compare_instances(&mut self) {
+        let (Some(left), Some(right)) = (
+            &self.instance_selection.left,
+            &self.instance_selection.right,
+        ) else {
+            self.instance_pairs = None;
+            return;
+        };
+        let load = |signature: &str| {
+            SourceChain::for_instance(signature)
+                .load()
+                .map(|loaded| loaded.keybindings)
+                .map_err(|e| format!("Failed to load {signature}: {e}"))
+        };
+        self.instance_pairs =
+            Some(load(left).and_then(|l| load(right).map(|r| BindDiff::side_by_side(&l, &r))));
+    }
+
+    /// Show binds of another instance; `None` is the current session
+    pub fn view_instance(&mut self, signature: Option<&str>) {
+        self.sources.set_instance(signature);
+        self.reload_keybindings();
+    }
+
+    /// Refresh the snapshot list and compare the previous snapshot with the current binds
+    pub fn open_history(&mut self) {
+        self.snapshots = crate::config::list_snapshots();
+        self.diff_selection = DiffSelection {
+            from: Some(usize::from(self.snapshots.len() > 1)),
+            to: None,
+        };
+        self.update_diff();
+    }
+
+    pub fn update_diff(&mut self) {
+        let load = |index: Option<usize>| match index.and_then(|i| self.snapshots.get(i)) {
+            Some(path) => read_keybindings_json(path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display())),
+            None => Ok(self.loaded_keybindings.clone()),
+        };
+        self.diff = Some(
+            load(self.diff_selection.from)
+                .and_then(|from| load(self.diff_selection.to).map(|to| BindDiff::new(&from, &to))),
+        );
+    }
+
+    /// Apply `config.json` and `icons.json` again after one changed on disk
+    pub fn reload_config(&mut self) {
+        self.load_config();
+        crate::ui::styling::icons::load_icon_map(self.icon_set);
+        self.categorizer.apply(&mut self.loaded_keybindings);
+        self.keybindings = self.annotations.apply(&self.loaded_keybindings);
+    }
+
     fn load_config(&mut self) {
         if let Some(cfg) = crate::config::load() {
             self.theme = cfg.theme;
+            self.css_theme = cfg.css_theme;
+            self.pywal_sync = cfg.pywal_sync;
+            self.fonts = cfg.fonts;
+            self.icon_set = cfg.icon_set;
             self.column_visibility = cfg.column_visibility;
             self.search_options = cfg.search_options;
             self.flags.zen_mode = cfg.zen_mode;
+            self.description_style = cfg.description_style;
+            self.flags.group_by_category = cfg.group_by_category;
+            self.categorizer = Categorizer::new(cfg.category_rules);
         }
     }
 
     pub fn save_config(&self) {
-        let cfg = UserConfig {
-            theme: self.theme,
-            column_visibility: self.column_visibility.clone(),
-            search_options: self.search_options.clone(),
-            zen_mode: self.flags.zen_mode,
-        };
+        let mut cfg = crate::config::load().unwrap_or_default();
+        cfg.theme = self.theme;
+        cfg.css_theme.clone_from(&self.css_theme);
+        cfg.pywal_sync = self.pywal_sync;
+        cfg.fonts.clone_from(&self.fonts);
+        cfg.icon_set = self.icon_set;
+        cfg.column_visibility = self.column_visibility.clone();
+        cfg.search_options = self.search_options.clone();
+        cfg.zen_mode = self.flags.zen_mode;
+        cfg.description_style = self.description_style;
+        cfg.group_by_category = self.flags.group_by_category;
         let _ = crate::config::save(&cfg);
     }
 }
@@ -1,51 +1,433 @@
-use clap::Parser;
+use crate::hyprland::bind_source::LoadedBinds;
+use crate::hyprland::launcher::{Launcher, LauncherProfile};
+use crate::hyprland::parser::{self, parse_combo};
+use crate::hyprland::source::dispatch;
+use crate::hyprland::terminal::TableStyle;
+use crate::hyprland::{
+    BindDiff, Categorizer, KeyBindings, SearchOptions, SourceChain, SourceKind,
+    hyprland_config_path, read_config_lines, read_keybindings_json,
+};
+use crate::ui::styling::stylesheet::Severity;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::env::{Bash, EnvCompleter, Fish, Zsh};
+use clap_complete::{ArgValueCandidates, CompleteEnv, CompletionCandidate};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process;
 
 /// A GUI to display Hyprland keybindings
+///
+/// Runs the GUI when no command is given.
 // Allow this to avoid make another struct
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Parser)]
 #[command(name = "HyprBind")]
 #[command(version, about, long_about = None)]
 pub struct Cli {
-    /// Write default CSS theme file
-    #[arg(long)]
+    /// Deprecated: use `hyprbind theme write-default`
+    #[arg(long, hide = true)]
     pub write_default_css: bool,
 
-    /// Overwrite existing CSS file (use with --write-default-css)
-    #[arg(long, requires = "write_default_css")]
+    /// Deprecated: use `hyprbind theme write-default --force`
+    #[arg(long, requires = "write_default_css", hide = true)]
     pub force: bool,
 
-    /// Output keybindings as JSON
-    #[arg(short, long)]
+    /// Deprecated: use `hyprbind export --format json`
+    #[arg(short, long, hide = true)]
     pub json: bool,
 
-    /// Output keybindings in dmenu-compatible format
-    #[arg(short, long)]
+    /// Deprecated: use `hyprbind export --format dmenu`
+    #[arg(short, long, hide = true)]
     pub dmenu: bool,
+
+    /// Deprecated: use `hyprbind export --format <FORMAT>`
+    #[arg(long, value_enum, conflicts_with_all = ["json", "dmenu"], hide = true)]
+    pub format: Option<OutputFormat>,
+
+    /// Deprecated: use `hyprbind export --main-mod <MOD>`
+    #[arg(long, value_name = "MOD", hide = true)]
+    pub main_mod: Option<String>,
+
+    /// Group output binds by category
+    #[arg(long, global = true)]
+    pub group_by_category: bool,
+
+    #[command(flatten)]
+    pub sources: SourceArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Where to load keybindings from, and how to report problems
+#[derive(Args, Default)]
+pub struct SourceArgs {
+    /// Load keybindings from JSON or `hyprctl binds` text instead of hyprctl ("-" for stdin)
+    #[arg(long, global = true, value_name = "FILE|-")]
+    pub input: Option<PathBuf>,
+
+    /// Bind sources to try in order, e.g. "socket,config"
+    /// (hyprctl, hyprctl-json, socket, config, file, sway)
+    #[arg(long, global = true, value_name = "SOURCES", value_delimiter = ',')]
+    pub source: Vec<SourceKind>,
+
+    /// Config file read by the config and sway sources instead of the default one
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Read live binds from this Hyprland instance instead of the current one
+    #[arg(long, global = true, value_name = "SIGNATURE")]
+    pub instance: Option<String>,
+
+    /// Print skipped binds and failed sources to stderr
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Fail when any bind could not be parsed
+    #[arg(long, global = true)]
+    pub strict: bool,
+}
+
+/// Output options shared by `list`, `search` and `export`
+#[derive(Args)]
+pub struct OutputArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+
+    /// Modifier to write as `$mainMod` (use with --format hyprconf)
+    #[arg(long, value_name = "MOD")]
+    pub main_mod: Option<String>,
+
+    /// Show modifiers and keys as icons of the icon set (use with --format table)
+    #[arg(long)]
+    pub icons: bool,
+
+    #[command(flatten)]
+    pub launcher: LauncherArgs,
+}
+
+/// Options of the launcher formats (dmenu, rofi, fuzzel, wofi), overriding `config.json`
+#[derive(Args, Default)]
+pub struct LauncherArgs {
+    /// Line template, e.g. '{keys}\t{description}\t{command}'
+    /// (placeholders: keys, text, description, command, dispatcher, arg, submap, category, index)
+    #[arg(long)]
+    pub template: Option<String>,
+
+    /// Write key names instead of icons
+    #[arg(long)]
+    pub plain: bool,
+
+    /// Append ` #<index>` to each line, to map a selection back to its bind
+    #[arg(long)]
+    pub index: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print all keybindings
+    List {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print keybindings whose keys, command or description contain QUERY; exits 1 if none do
+    Search {
+        query: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print what a key combination is bound to; exits 1 if unbound
+    ///
+    /// The combination can be written like `SUPER+SHIFT+Q`, `super shift q`,
+    /// `'$mainMod, Q'` or `Mod4+q`.
+    Show {
+        #[arg(required = true, num_args = 1.., value_name = "COMBO")]
+        combo: Vec<String>,
+        /// Only show binds in this submap ("" for global binds)
+        #[arg(long, value_name = "NAME", add = ArgValueCandidates::new(submap_candidates))]
+        submap: Option<String>,
+    },
+    /// Print keybindings in a format for other tools
+    Export {
+        /// Output format
+        #[arg(long, value_enum)]
+        format: OutputFormat,
+        /// Modifier to write as `$mainMod` (use with --format hyprconf)
+        #[arg(long, value_name = "MOD")]
+        main_mod: Option<String>,
+        #[command(flatten)]
+        launcher: LauncherArgs,
+    },
+    /// Report skipped binds and keys bound more than once; exits 1 on problems
+    Check,
+    /// Show added, removed and changed binds between two JSON files
+    Diff {
+        /// Older keybindings (e.g. from --json or the history directory)
+        old: PathBuf,
+        /// Newer keybindings
+        new: PathBuf,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    /// Manage the CSS theme
+    Theme {
+        #[command(subcommand)]
+        command: ThemeCommand,
+    },
+    /// Print a shell completion script
+    Completions { shell: CompletionShell },
+    /// Print the man page
+    Man,
+    /// Rofi script mode: list binds, then run the selected one
+    ///
+    /// Use as `rofi -show hyprbind -modi 'hyprbind:hyprbind rofi'`.
+    Rofi {
+        /// Selected line, passed by rofi
+        #[arg(hide = true)]
+        selection: Option<String>,
+        #[command(flatten)]
+        launcher: LauncherArgs,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ThemeCommand {
+    /// Write the default CSS theme file
+    WriteDefault {
+        /// Overwrite an existing CSS file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Report problems in a CSS theme; exits 1 on errors
+    Check {
+        /// CSS file (default: hyprbind-theme.css in the config directory)
+        path: Option<PathBuf>,
+    },
+    /// Build a theme from pywal, base16 or Hyprland colours
+    Import {
+        /// `pywal`, `hyprland`, a pywal colors.json or a base16 .yaml scheme
+        #[arg(id = "import_source", value_name = "SOURCE")]
+        source: String,
+        /// Theme name (default: pywal, hyprland or base16-<scheme>)
+        #[arg(long)]
+        name: Option<String>,
+        /// Also pick the theme in config.json
+        #[arg(long)]
+        select: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Nushell,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DiffFormat {
+    /// Readable list, coloured when printing to a terminal
+    Text,
+    /// JSON with added, removed and changed binds
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns for reading in a terminal, grouped by submap
+    Table,
+    /// JSON with every field
+    Json,
+    /// `keybind : description` lines for dmenu
+    Dmenu,
+    /// Lines for rofi -dmenu, with icons of started programs
+    Rofi,
+    /// Lines for fuzzel --dmenu, with icons of started programs
+    Fuzzel,
+    /// Lines for wofi --dmenu
+    Wofi,
+    /// hyprland.conf bind lines
+    Hyprconf,
 }
 
 pub enum CliAction {
-    RunGui,
-    WriteDefaultCss { force: bool },
-    OutputJson,
-    OutputDmenu,
+    RunGui {
+        sources: SourceArgs,
+    },
+    WriteDefaultCss {
+        force: bool,
+    },
+    CheckTheme {
+        path: Option<PathBuf>,
+    },
+    ImportTheme {
+        source: String,
+        name: Option<String>,
+        select: bool,
+    },
+    Output {
+        sources: SourceArgs,
+        output: OutputArgs,
+        /// Only print binds matching this search
+        query: Option<String>,
+        grouped: bool,
+    },
+    Show {
+        sources: SourceArgs,
+        combo: String,
+        submap: Option<String>,
+    },
+    Check {
+        sources: SourceArgs,
+    },
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        format: DiffFormat,
+    },
+    Completions {
+        shell: CompletionShell,
+    },
+    Man,
+    Rofi {
+        sources: SourceArgs,
+        launcher: LauncherArgs,
+        grouped: bool,
+    },
+}
+
+fn deprecated(flag: &str, replacement: &str) {
+    eprintln!("warning: {flag} is deprecated, use `hyprbind {replacement}` instead");
+}
+
+/// Name of the installed binary, used in completions and the man page
+const BIN_NAME: &str = "hyprbind";
+
+/// The clap command, as completions see it
+pub fn command() -> clap::Command {
+    Cli::command().bin_name(BIN_NAME)
 }
 
 pub fn parse_args() -> CliAction {
-    let cli = Cli::parse();
+    // Answer `COMPLETE=<shell> hyprbind -- ...` requests from the completion scripts
+    CompleteEnv::with_factory(command).complete();
+    action_from(Cli::parse())
+}
+
+/// Action of a `theme` subcommand
+fn theme_action(command: ThemeCommand) -> CliAction {
+    match command {
+        ThemeCommand::WriteDefault { force } => CliAction::WriteDefaultCss { force },
+        ThemeCommand::Check { path } => CliAction::CheckTheme { path },
+        ThemeCommand::Import {
+            source,
+            name,
+            select,
+        } => CliAction::ImportTheme {
+            source,
+            name,
+            select,
+        },
+    }
+}
+
+/// Map parsed arguments to the action to run
+pub fn action_from(cli: Cli) -> CliAction {
+    let sources = cli.sources;
+    let grouped = cli.group_by_category;
 
+    match cli.command {
+        Some(Command::List { output }) => {
+            return CliAction::Output {
+                sources,
+                output,
+                query: None,
+                grouped,
+            };
+        }
+        Some(Command::Search { query, output }) => {
+            return CliAction::Output {
+                sources,
+                output,
+                query: Some(query),
+                grouped,
+            };
+        }
+        Some(Command::Show { combo, submap }) => {
+            return CliAction::Show {
+                sources,
+                combo: combo.join(" "),
+                submap,
+            };
+        }
+        Some(Command::Export {
+            format,
+            main_mod,
+            launcher,
+        }) => {
+            return CliAction::Output {
+                sources,
+                output: OutputArgs {
+                    format,
+                    main_mod,
+                    icons: false,
+                    launcher,
+                },
+                query: None,
+                grouped,
+            };
+        }
+        Some(Command::Check) => return CliAction::Check { sources },
+        Some(Command::Diff { old, new, format }) => {
+            return CliAction::Diff { old, new, format };
+        }
+        Some(Command::Theme { command }) => return theme_action(command),
+        Some(Command::Completions { shell }) => return CliAction::Completions { shell },
+        Some(Command::Man) => return CliAction::Man,
+        Some(Command::Rofi { launcher, .. }) => {
+            return CliAction::Rofi {
+                sources,
+                launcher,
+                grouped,
+            };
+        }
+        None => {}
+    }
+
+    // Flags from before subcommands existed
     if cli.write_default_css {
+        deprecated("--write-default-css", "theme write-default");
         return CliAction::WriteDefaultCss { force: cli.force };
     }
-    if cli.json {
-        return CliAction::OutputJson;
-    }
-    if cli.dmenu {
-        return CliAction::OutputDmenu;
+    let format = if cli.json {
+        deprecated("--json", "export --format json");
+        Some(OutputFormat::Json)
+    } else if cli.dmenu {
+        deprecated("--dmenu", "export --format dmenu");
+        Some(OutputFormat::Dmenu)
+    } else {
+        if cli.format.is_some() {
+            deprecated("--format", "export --format");
+        }
+        cli.format
+    };
+    match format {
+        Some(format) => CliAction::Output {
+            sources,
+            output: OutputArgs {
+                format,
+                main_mod: cli.main_mod,
+                icons: false,
+                launcher: LauncherArgs::default(),
+            },
+            query: None,
+            grouped,
+        },
+        None => CliAction::RunGui { sources },
     }
-
-    CliAction::RunGui
 }
 
 pub fn handle_write_css(force: bool) {
@@ -68,34 +450,416 @@ pub fn handle_write_css(force: bool) {
     }
 }
 
-pub fn handle_json_output() {
-    match crate::hyprland::fetch_hyprctl_binds() {
-        Ok(raw_output) => {
-            let kb = crate::hyprland::parse_binds_output(&raw_output);
-            match kb.to_json() {
-                Ok(s) => println!("{s}"),
-                Err(e) => {
-                    eprintln!("Failed to serialize JSON: {e}");
-                    process::exit(1);
+/// Build the source chain, exiting on failure
+///
+/// `--source` wins, then `--input` alone, then `sources` in `config.json`,
+/// then the default chain.
+pub fn bind_sources(args: &SourceArgs) -> SourceChain {
+    let kinds = if !args.source.is_empty() {
+        args.source.clone()
+    } else if args.input.is_some() {
+        vec![SourceKind::File]
+    } else {
+        crate::config::load()
+            .map(|cfg| cfg.sources)
+            .filter(|sources| !sources.is_empty())
+            .unwrap_or_else(|| SourceChain::DEFAULT.to_vec())
+    };
+    let mut chain = SourceChain::from_kinds(&kinds, args.input.as_deref(), args.config.as_deref())
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to read input: {e}");
+            process::exit(1);
+        });
+    chain.set_instance(args.instance.as_deref());
+    chain
+}
+
+/// Load keybindings, exiting on failure
+///
+/// With `--strict`, skipped binds are a failure too.
+fn load(input: &SourceArgs) -> LoadedBinds {
+    let loaded = bind_sources(input).load().unwrap_or_else(|e| {
+        eprintln!("Failed to load keybindings:");
+        for error in &e.errors {
+            eprintln!("  {error}");
+        }
+        if e.errors.is_empty() {
+            eprintln!("  {e}");
+        }
+        process::exit(1);
+    });
+    if input.verbose {
+        for error in &loaded.skipped {
+            eprintln!("Skipped source {error}");
+        }
+    }
+    if input.verbose || input.strict {
+        for warning in &loaded.warnings {
+            eprintln!("Skipped bind at {warning}");
+            if input.verbose {
+                for line in warning.raw.lines() {
+                    eprintln!("    {line}");
                 }
             }
         }
-        Err(e) => {
-            eprintln!("Failed to load keybindings: {e}");
+    }
+    if input.strict && !loaded.warnings.is_empty() {
+        eprintln!(
+            "{} bind(s) could not be parsed from {}",
+            loaded.warnings.len(),
+            loaded.source
+        );
+        process::exit(1);
+    }
+    loaded
+}
+
+/// Load and categorise keybindings, exiting on failure
+fn load_keybindings(input: &SourceArgs, grouped: bool) -> KeyBindings {
+    let mut kb = load(input).keybindings;
+    let rules = crate::config::load()
+        .map(|cfg| cfg.category_rules)
+        .unwrap_or_default();
+    let categorizer = Categorizer::new(rules);
+    categorizer.apply(&mut kb);
+    if grouped {
+        categorizer.group(&mut kb.entries);
+    }
+    kb
+}
+
+/// Print keybindings, optionally only those matching `query`; exits 1 if a query matches nothing
+pub fn handle_output(input: &SourceArgs, output: &OutputArgs, query: Option<&str>, grouped: bool) {
+    let mut kb = load_keybindings(input, grouped);
+    if let Some(query) = query {
+        let options = SearchOptions {
+            annotations: false,
+            ..SearchOptions::default()
+        };
+        kb.entries.retain(|e| e.matches(query, &options));
+        if kb.entries.is_empty() {
             process::exit(1);
         }
     }
+
+    match output.format {
+        OutputFormat::Json => match kb.to_json() {
+            Ok(s) => println!("{s}"),
+            Err(e) => {
+                eprintln!("Failed to serialize JSON: {e}");
+                process::exit(1);
+            }
+        },
+        OutputFormat::Dmenu | OutputFormat::Rofi | OutputFormat::Fuzzel | OutputFormat::Wofi => {
+            let launcher = match output.format {
+                OutputFormat::Rofi => Launcher::Rofi,
+                OutputFormat::Fuzzel => Launcher::Fuzzel,
+                OutputFormat::Wofi => Launcher::Wofi,
+                _ => Launcher::Dmenu,
+            };
+            println!(
+                "{}",
+                kb.to_launcher(&launcher_profile(launcher, &output.launcher, grouped))
+            );
+        }
+        OutputFormat::Hyprconf => print!("{}", kb.to_hyprconf(output.main_mod.as_deref(), grouped)),
+        OutputFormat::Table => {
+            let stdout = std::io::stdout();
+            let terminal = stdout.is_terminal();
+            let style = TableStyle {
+                width: terminal_width(terminal),
+                color: terminal && std::env::var_os("NO_COLOR").is_none(),
+                icons: output.icons,
+                category: grouped,
+            };
+            print!("{}", kb.to_table(&style));
+        }
+    }
 }
 
-pub fn handle_dmenu_output() {
-    match crate::hyprland::fetch_hyprctl_binds() {
-        Ok(raw_output) => {
-            let kb = crate::hyprland::parse_binds_output(&raw_output);
-            println!("{}", kb.to_dmenu());
+/// Settings of `launcher` from `config.json`, overridden by the command line
+fn launcher_profile(launcher: Launcher, args: &LauncherArgs, grouped: bool) -> LauncherProfile {
+    let settings = crate::config::load()
+        .and_then(|mut cfg| cfg.launchers.remove(&launcher))
+        .unwrap_or_default();
+    let mut profile = LauncherProfile::new(launcher, &settings);
+    if let Some(template) = &args.template {
+        profile.template.clone_from(template);
+    }
+    profile.plain |= args.plain;
+    profile.index |= args.index;
+    profile.grouped = grouped;
+    profile
+}
+
+/// Rofi script mode: list binds, or run the one rofi reports as selected
+///
+/// Rofi sets `ROFI_RETV=1` on selection and passes the row's `info`, the bind's index,
+/// in `ROFI_INFO`.
+pub fn handle_rofi(input: &SourceArgs, launcher: &LauncherArgs, grouped: bool) {
+    let kb = load_keybindings(input, grouped);
+    if std::env::var("ROFI_RETV").is_ok_and(|retv| retv == "1") {
+        let Some(entry) = std::env::var("ROFI_INFO")
+            .ok()
+            .and_then(|info| info.parse::<usize>().ok())
+            .and_then(|index| kb.entries.get(index))
+        else {
+            eprintln!("No bind selected");
+            process::exit(1);
+        };
+        let (dispatcher, arg) = entry.dispatcher_and_arg();
+        if let Err(e) = dispatch(input.instance.as_deref(), dispatcher, arg) {
+            eprintln!("Failed to run {}: {e}", entry.combo_label());
+            process::exit(1);
         }
-        Err(e) => {
-            eprintln!("Failed to load keybindings: {e}");
+        return;
+    }
+
+    println!("\0prompt\x1fKeybinds");
+    println!("\0no-custom\x1ftrue");
+    println!(
+        "{}",
+        kb.to_launcher(&launcher_profile(Launcher::Rofi, launcher, grouped))
+    );
+}
+
+/// `COLUMNS`, or the size of the terminal on stdout; `None` when piped
+fn terminal_width(terminal: bool) -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| {
+            terminal
+                .then(|| rustix::termios::tcgetwinsize(std::io::stdout()).ok())
+                .flatten()
+                .map(|size| usize::from(size.ws_col))
+                .filter(|&cols| cols > 0)
+        })
+}
+
+/// `$variables` of the Hyprland config, for combos written like in it
+fn config_variables(input: &SourceArgs) -> Vec<(String, String)> {
+    let path = input.config.clone().unwrap_or_else(hyprland_config_path);
+    read_config_lines(&path)
+        .map(|lines| parser::config_variables(&lines))
+        .unwrap_or_default()
+}
+
+/// Print the binds of a key combination in every submap, or only `submap`; exits 1 if it is unbound
+pub fn handle_show(input: &SourceArgs, combo: &str, submap: Option<&str>) {
+    let (modmask, key) = parse_combo(combo, &config_variables(input)).unwrap_or_else(|e| {
+        eprintln!("Invalid key combination '{combo}': {e}");
+        process::exit(2);
+    });
+    let kb = load_keybindings(input, false);
+    let bound: Vec<_> = kb
+        .entries
+        .iter()
+        .filter(|e| e.has_combo(modmask, &key))
+        .filter(|e| submap.is_none_or(|s| e.submap == s))
+        .collect();
+    if bound.is_empty() {
+        eprintln!("{combo} is not bound");
+        process::exit(1);
+    }
+    for entry in bound {
+        let submap = if entry.submap.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", entry.submap)
+        };
+        if entry.description.is_empty() {
+            println!("{}{submap}: {}", entry.combo_label(), entry.command);
+        } else {
+            println!(
+                "{}{submap}: {} ({})",
+                entry.combo_label(),
+                entry.command,
+                entry.description
+            );
+        }
+    }
+}
+
+/// Report skipped sources, skipped binds and keys bound more than once; exits 1 on problems
+pub fn handle_check(input: &SourceArgs) {
+    let loaded = load(input);
+    for error in &loaded.skipped {
+        println!("note: skipped source {error}");
+    }
+    for warning in &loaded.warnings {
+        println!("error: skipped bind at {warning}");
+        for line in warning.raw.lines() {
+            println!("    {line}");
+        }
+    }
+    let duplicates = loaded.keybindings.duplicate_combos();
+    for binds in &duplicates {
+        let first = binds[0];
+        let submap = if first.submap.is_empty() {
+            String::new()
+        } else {
+            format!(" in submap {}", first.submap)
+        };
+        println!(
+            "warning: {} is bound {} times{submap}:",
+            first.combo_label(),
+            binds.len()
+        );
+        for bind in binds {
+            println!("    {}", bind.command);
+        }
+    }
+
+    let problems = loaded.warnings.len() + duplicates.len();
+    println!(
+        "{} binds from {}, {problems} problem(s)",
+        loaded.keybindings.entries.len(),
+        loaded.source
+    );
+    if problems > 0 {
+        process::exit(1);
+    }
+}
+
+/// Print problems in a CSS theme; exits 1 on errors and 2 if it can't be read
+pub fn handle_theme_check(path: Option<&Path>) {
+    let default_path = crate::ui::styling::css::default_css_path();
+    let path = path.unwrap_or(&default_path);
+    let diagnostics = crate::ui::styling::css::check_file(path).unwrap_or_else(|e| {
+        eprintln!("{}: {e}", path.display());
+        process::exit(2);
+    });
+    for diagnostic in &diagnostics {
+        println!("{}:{diagnostic}", path.display());
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    println!(
+        "{}: {errors} error(s), {} warning(s)",
+        path.display(),
+        diagnostics.len() - errors
+    );
+    if errors > 0 {
+        process::exit(1);
+    }
+}
+
+/// Write a theme built from pywal, base16 or Hyprland colours; exits 1 on failure
+pub fn handle_theme_import(source: &str, name: Option<&str>, select: bool) {
+    use crate::ui::styling::import::{ImportSource, import_theme};
+
+    let imported = ImportSource::parse(source).and_then(|source| import_theme(&source, name));
+    let (name, path) = imported.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    println!("Theme '{name}' written to {}", path.display());
+    if select {
+        let mut cfg = crate::config::load().unwrap_or_default();
+        cfg.css_theme = Some(name);
+        if let Err(e) = crate::config::save(&cfg) {
+            eprintln!("Failed to save config: {e}");
             process::exit(1);
         }
     }
 }
+
+/// Submaps of the current binds, for completing `--submap`
+fn submap_candidates() -> Vec<CompletionCandidate> {
+    let Ok(loaded) = bind_sources(&SourceArgs::default()).load() else {
+        return Vec::new();
+    };
+    let mut submaps: Vec<String> = loaded
+        .keybindings
+        .entries
+        .into_iter()
+        .map(|e| e.submap)
+        .filter(|s| !s.is_empty())
+        .collect();
+    submaps.sort();
+    submaps.dedup();
+    submaps.into_iter().map(CompletionCandidate::new).collect()
+}
+
+/// Nushell command listing submaps, since its completions cannot call back into hyprbind
+const NUSHELL_SUBMAPS: &str = r#"def "nu-complete hyprbind submaps" [] {
+  ^hyprbind export --format json | from json | get entries.submap | uniq | where $it != ""
+}
+
+"#;
+
+/// Write the completion script for `shell`
+///
+/// Bash, zsh and fish call back into hyprbind for candidates, so submap names are
+/// completed from the current binds. Nushell gets a static script with a submap completer.
+pub fn write_completions(shell: CompletionShell, buf: &mut dyn Write) -> io::Result<()> {
+    let dynamic: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+        CompletionShell::Nushell => {
+            let mut script = Vec::new();
+            clap_complete::generate(
+                clap_complete_nushell::Nushell,
+                &mut command(),
+                BIN_NAME,
+                &mut script,
+            );
+            let script = String::from_utf8_lossy(&script).replace(
+                "--submap: string",
+                r#"--submap: string@"nu-complete hyprbind submaps""#,
+            );
+            return write!(buf, "{NUSHELL_SUBMAPS}{script}");
+        }
+    };
+    dynamic.write_registration("COMPLETE", BIN_NAME, BIN_NAME, BIN_NAME, buf)
+}
+
+/// Print a completion script
+pub fn handle_completions(shell: CompletionShell) {
+    if let Err(e) = write_completions(shell, &mut io::stdout()) {
+        eprintln!("Failed to write completions: {e}");
+        process::exit(1);
+    }
+}
+
+/// Print the man page in roff
+pub fn handle_man() {
+    let man = clap_mangen::Man::new(command().name(BIN_NAME));
+    if let Err(e) = man.render(&mut io::stdout()) {
+        eprintln!("Failed to write the man page: {e}");
+        process::exit(1);
+    }
+}
+
+/// Print the differences between two JSON files; exits 1 if they differ, like diff(1)
+pub fn handle_diff(old: &Path, new: &Path, format: DiffFormat) {
+    let read = |path: &Path| {
+        read_keybindings_json(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read {}: {e}", path.display());
+            process::exit(2);
+        })
+    };
+    let diff = BindDiff::new(&read(old), &read(new));
+
+    match format {
+        DiffFormat::Text => {
+            let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            print!("{}", diff.to_text(color));
+        }
+        DiffFormat::Json => match diff.to_json() {
+            Ok(s) => println!("{s}"),
+            Err(e) => {
+                eprintln!("Failed to serialize JSON: {e}");
+                process::exit(2);
+            }
+        },
+    }
+
+    if !diff.is_empty() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,23 @@
+use std::{fs, io};
+
+use crate::hyprland::Annotations;
+
+use super::paths::{annotations_path, config_dir};
+
+/// Load annotations; a missing or unreadable file yields no annotations
+pub fn load_annotations() -> Annotations {
+    fs::read_to_string(annotations_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_annotations(annotations: &Annotations) -> io::Result<()> {
+    let dir = config_dir();
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+    }
+    let data =
+        serde_json::to_string_pretty(annotations).map_err(|e| io::Error::other(e.to_string()))?;
+    fs::write(annotations_path(), data)
+}
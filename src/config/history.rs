@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::hyprland::KeyBindings;
+
+use super::paths::history_dir;
+
+/// Number of snapshots kept; older ones are deleted
+const MAX_SNAPSHOTS: usize = 50;
+
+/// Snapshot files in `dir`, newest first
+fn snapshots_in(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.extension().is_some_and(|ext| ext == "json")
+                        && p.file_name()
+                            .and_then(|n| n.to_str())
+                            .is_some_and(|n| n.starts_with("snapshot_"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    paths.reverse();
+    paths
+}
+
+/// Save `keybindings` into `dir` unless the newest snapshot is identical
+///
+/// Returns the path of the new snapshot, or `None` if nothing changed.
+pub fn save_snapshot_in(dir: &Path, keybindings: &KeyBindings) -> io::Result<Option<PathBuf>> {
+    let json = keybindings
+        .to_json()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    let existing = snapshots_in(dir);
+    if let Some(latest) = existing.first()
+        && fs::read_to_string(latest).is_ok_and(|data| data == json)
+    {
+        return Ok(None);
+    }
+
+    fs::create_dir_all(dir)?;
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let path = dir.join(format!("snapshot_{millis:015}.json"));
+    fs::write(&path, json)?;
+
+    for old in existing.iter().skip(MAX_SNAPSHOTS - 1) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(Some(path))
+}
+
+/// Save a snapshot into the history directory
+pub fn save_snapshot(keybindings: &KeyBindings) -> io::Result<Option<PathBuf>> {
+    save_snapshot_in(&history_dir(), keybindings)
+}
+
+/// Snapshots in the history directory, newest first
+pub fn list_snapshots() -> Vec<PathBuf> {
+    snapshots_in(&history_dir())
+}
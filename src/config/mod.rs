@@ -1,5 +1,11 @@
+pub mod annotations;
+pub mod history;
 pub mod paths;
 pub mod user;
+pub mod watch;
 
-pub use paths::{config_dir, export_dir};
-pub use user::{UserConfig, load, save};
+pub use annotations::{load_annotations, save_annotations};
+pub use history::{list_snapshots, save_snapshot};
+pub use paths::{backup_dir, config_dir, export_dir, icons_path, themes_dir};
+pub use user::{load, save};
+pub use watch::{ConfigWatcher, WatchDirs};
@@ -12,6 +12,27 @@ pub fn export_dir() -> PathBuf {
     config_dir().join("exports")
 }
 
+pub fn history_dir() -> PathBuf {
+    config_dir().join("history")
+}
+
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+/// Labels of keys replacing those of the icon set
+pub fn icons_path() -> PathBuf {
+    config_dir().join("icons.json")
+}
+
+pub fn backup_dir() -> PathBuf {
+    config_dir().join("backups")
+}
+
+pub(super) fn annotations_path() -> PathBuf {
+    config_dir().join("annotations.json")
+}
+
 pub(super) fn config_path() -> PathBuf {
     config_dir().join("config.json")
 }
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::{fs, io};
 
-use crate::hyprland::SearchOptions;
+use crate::hyprland::launcher::{Launcher, LauncherSettings};
+use crate::hyprland::{CategoryRule, DescriptionStyle, SearchOptions, SourceKind};
+use crate::ui::styling::fonts::FontSettings;
+use crate::ui::styling::icons::IconSet;
 use crate::ui::types::{ColumnVisibility, Theme};
 
 use super::paths::{config_dir, config_path};
@@ -12,6 +16,33 @@ pub struct UserConfig {
     pub column_visibility: ColumnVisibility,
     pub search_options: SearchOptions,
     pub zen_mode: bool,
+    #[serde(default)]
+    pub description_style: DescriptionStyle,
+    /// Show binds in category groups
+    #[serde(default)]
+    pub group_by_category: bool,
+    /// Category rules checked before the built-in ones
+    #[serde(default)]
+    pub category_rules: Vec<CategoryRule>,
+    /// Bind sources tried in order; empty uses the default chain
+    #[serde(default)]
+    pub sources: Vec<SourceKind>,
+    /// Output settings of each launcher format
+    #[serde(default)]
+    pub launchers: HashMap<Launcher, LauncherSettings>,
+    /// CSS theme picked by name, from the themes directory or the presets;
+    /// `None` uses `hyprbind-theme.css`, or the built-in theme without one
+    #[serde(default)]
+    pub css_theme: Option<String>,
+    /// Re-import the pywal theme whenever `colors.json` changes, and use it
+    #[serde(default)]
+    pub pywal_sync: bool,
+    /// Interface and monospace fonts, and the UI scale
+    #[serde(default)]
+    pub fonts: FontSettings,
+    /// How keys and modifiers are drawn; `icons.json` overrides single keys
+    #[serde(default)]
+    pub icon_set: IconSet,
 }
 
 impl Default for UserConfig {
@@ -21,6 +52,15 @@ impl Default for UserConfig {
             column_visibility: ColumnVisibility::default(),
             search_options: SearchOptions::default(),
             zen_mode: false,
+            description_style: DescriptionStyle::default(),
+            group_by_category: false,
+            category_rules: Vec::new(),
+            sources: Vec::new(),
+            launchers: HashMap::new(),
+            css_theme: None,
+            pywal_sync: false,
+            fonts: FontSettings::default(),
+            icon_set: IconSet::default(),
         }
     }
 }
@@ -0,0 +1,171 @@
+use rustix::event::{PollFd, PollFlags, Timespec, poll};
+use rustix::fs::inotify::{self, CreateFlags, ReadFlags, WatchFlags};
+use rustix::io::Errno;
+use std::io;
+use std::mem::MaybeUninit;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Quiet time after the last event before changes are reported, so an editor saving through
+/// a temporary file and a rename causes one reload
+const DEBOUNCE: Timespec = Timespec {
+    tv_sec: 0,
+    tv_nsec: 150_000_000,
+};
+
+/// Events of a file being written, replaced, or removed; directories are watched since
+/// saving through a rename replaces the file
+const WATCH_FLAGS: WatchFlags = WatchFlags::CLOSE_WRITE
+    .union(WatchFlags::MOVED_TO)
+    .union(WatchFlags::MOVED_FROM)
+    .union(WatchFlags::CREATE)
+    .union(WatchFlags::DELETE);
+
+const CSS_FILE: &str = "hyprbind-theme.css";
+const CONFIG_FILE: &str = "config.json";
+const ICONS_FILE: &str = "icons.json";
+const THEMES_DIR: &str = "themes";
+const PYWAL_FILE: &str = "colors.json";
+
+/// Files changed since the last `ConfigWatcher::take`
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Changes {
+    /// `hyprbind-theme.css` or a theme in the themes directory
+    pub css: bool,
+    /// `config.json` or `icons.json`
+    pub config: bool,
+    /// pywal's `colors.json`
+    pub pywal: bool,
+}
+
+impl Changes {
+    pub const fn is_empty(self) -> bool {
+        !(self.css || self.config || self.pywal)
+    }
+
+    const fn merge(&mut self, other: Self) {
+        self.css |= other.css;
+        self.config |= other.config;
+        self.pywal |= other.pywal;
+    }
+}
+
+/// Directories watched for changes
+#[derive(Debug, Clone)]
+pub struct WatchDirs {
+    /// Holds `config.json`, `icons.json` and `hyprbind-theme.css`
+    pub config: PathBuf,
+    pub themes: PathBuf,
+    /// Holds pywal's `colors.json`
+    pub pywal: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dir {
+    Config,
+    Themes,
+    Pywal,
+}
+
+/// What a change of `name` in `dir` means
+fn classify(dir: Dir, name: &str) -> Changes {
+    Changes {
+        css: match dir {
+            Dir::Config => name == CSS_FILE || name == THEMES_DIR,
+            Dir::Themes => Path::new(name).extension().is_some_and(|ext| ext == "css"),
+            Dir::Pywal => false,
+        },
+        config: dir == Dir::Config && (name == CONFIG_FILE || name == ICONS_FILE),
+        pywal: dir == Dir::Pywal && name == PYWAL_FILE,
+    }
+}
+
+/// Watches the config, themes and pywal directories with inotify; the default one
+/// watches nothing
+#[derive(Debug, Clone, Default)]
+pub struct ConfigWatcher {
+    changes: Arc<Mutex<Changes>>,
+}
+
+impl ConfigWatcher {
+    /// Watch `dirs` from a background thread, calling `on_change` once changes settle
+    ///
+    /// Missing directories are skipped; the themes directory is picked up when it is created.
+    pub fn start(dirs: WatchDirs, on_change: impl Fn() + Send + 'static) -> io::Result<Self> {
+        let fd = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK)?;
+        let mut watches: Vec<(i32, Dir)> = [
+            (&dirs.config, Dir::Config),
+            (&dirs.themes, Dir::Themes),
+            (&dirs.pywal, Dir::Pywal),
+        ]
+        .into_iter()
+        .filter_map(|(path, dir)| {
+            inotify::add_watch(&fd, path, WATCH_FLAGS)
+                .ok()
+                .map(|wd| (wd, dir))
+        })
+        .collect();
+
+        let changes = Arc::new(Mutex::new(Changes::default()));
+        let shared = Arc::clone(&changes);
+        std::thread::spawn(move || {
+            let mut buf = [MaybeUninit::uninit(); 4096];
+            let mut pending = Changes::default();
+            loop {
+                // Wait for the first event, then until no event came for `DEBOUNCE`
+                let timeout = (!pending.is_empty()).then_some(&DEBOUNCE);
+                match poll(&mut [PollFd::new(&fd, PollFlags::IN)], timeout) {
+                    Ok(0) => {
+                        shared
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .merge(std::mem::take(&mut pending));
+                        on_change();
+                        continue;
+                    }
+                    Ok(_) | Err(Errno::INTR) => {}
+                    Err(_) => return,
+                }
+
+                let mut reader = inotify::Reader::new(&fd, &mut buf);
+                loop {
+                    let (wd, flags, name) = match reader.next() {
+                        Ok(event) => (
+                            event.wd(),
+                            event.events(),
+                            event.file_name().map(|n| n.to_string_lossy().into_owned()),
+                        ),
+                        Err(Errno::INTR) => continue,
+                        Err(Errno::AGAIN) => break,
+                        Err(_) => return,
+                    };
+                    if flags.contains(ReadFlags::IGNORED) {
+                        watches.retain(|(w, _)| *w != wd);
+                        continue;
+                    }
+                    let (Some(&(_, dir)), Some(name)) =
+                        (watches.iter().find(|(w, _)| *w == wd), name)
+                    else {
+                        continue;
+                    };
+                    if dir == Dir::Config
+                        && name == THEMES_DIR
+                        && flags.contains(ReadFlags::ISDIR)
+                        && flags.intersects(ReadFlags::CREATE | ReadFlags::MOVED_TO)
+                        && let Ok(wd) = inotify::add_watch(&fd, &dirs.themes, WATCH_FLAGS)
+                    {
+                        watches.push((wd, Dir::Themes));
+                    }
+                    pending.merge(classify(dir, &name));
+                }
+            }
+        });
+        Ok(Self { changes })
+    }
+
+    /// Changes since the last call
+    pub fn take(&self) -> Changes {
+        std::mem::take(&mut *self.changes.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
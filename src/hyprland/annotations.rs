@@ -0,0 +1,81 @@
+use super::models::{BindId, KeyBindEntry, KeyBindings};
+use serde::{Deserialize, Serialize};
+
+/// Local annotation for a single bind, stored by `HyprBind`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub bind: BindId,
+    /// Replaces the bind's own description when not empty
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Annotation {
+    pub const fn is_empty(&self) -> bool {
+        self.description.is_empty() && self.notes.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// All annotations, as stored in the annotations file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Annotations {
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+impl Annotations {
+    pub fn get(&self, bind: &BindId) -> Option<&Annotation> {
+        self.annotations.iter().find(|a| &a.bind == bind)
+    }
+
+    /// Insert or replace the annotation for its bind; empty annotations are removed
+    pub fn set(&mut self, annotation: Annotation) {
+        if annotation.is_empty() {
+            self.remove(&annotation.bind);
+        } else if let Some(existing) = self
+            .annotations
+            .iter_mut()
+            .find(|a| a.bind == annotation.bind)
+        {
+            *existing = annotation;
+        } else {
+            self.annotations.push(annotation);
+        }
+    }
+
+    pub fn remove(&mut self, bind: &BindId) {
+        self.annotations.retain(|a| &a.bind != bind);
+    }
+
+    /// Copy of `keybindings` with descriptions, notes and tags merged in
+    pub fn apply(&self, keybindings: &KeyBindings) -> KeyBindings {
+        let mut merged = keybindings.clone();
+        for entry in &mut merged.entries {
+            if let Some(annotation) = self.get(&entry.bind_id()) {
+                if !annotation.description.is_empty() {
+                    entry.description.clone_from(&annotation.description);
+                }
+                entry.notes.clone_from(&annotation.notes);
+                entry.tags.clone_from(&annotation.tags);
+            }
+        }
+        merged
+    }
+
+    /// Annotations whose bind no longer exists in `keybindings`
+    pub fn orphans(&self, keybindings: &KeyBindings) -> Vec<&Annotation> {
+        let ids: Vec<BindId> = keybindings
+            .entries
+            .iter()
+            .map(KeyBindEntry::bind_id)
+            .collect();
+        self.annotations
+            .iter()
+            .filter(|a| !ids.contains(&a.bind))
+            .collect()
+    }
+}
@@ -0,0 +1,468 @@
+use super::error::{LoadError, SourceError, SourceErrorReason};
+use super::models::{KeyBindings, ParseWarning, ParsedBinds};
+use super::parser::{
+    parse_bind_input, parse_binds_output_with_warnings, parse_config_lines_with_warnings,
+    parse_hyprctl_json,
+};
+use super::source::{
+    fetch_hyprctl_binds, fetch_hyprctl_binds_json, hyprland_config_path, read_config_lines,
+    read_sway_config_lines, request_hyprland_socket, sway_config_path,
+};
+use super::sway::parse_sway_lines;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Kinds of bind sources, as named on the command line and in `config.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceKind {
+    /// `hyprctl binds`
+    Hyprctl,
+    /// `hyprctl binds -j`
+    HyprctlJson,
+    /// The Hyprland IPC socket
+    Socket,
+    /// Bind lines in `hyprland.conf` and the files it sources
+    Config,
+    /// `--input`: `KeyBindings` JSON or `hyprctl binds` text
+    File,
+    /// `bindsym`/`bindcode` lines in a Sway or i3 config
+    Sway,
+}
+
+impl SourceKind {
+    pub const ALL: [Self; 6] = [
+        Self::Hyprctl,
+        Self::HyprctlJson,
+        Self::Socket,
+        Self::Config,
+        Self::File,
+        Self::Sway,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Hyprctl => "hyprctl",
+            Self::HyprctlJson => "hyprctl-json",
+            Self::Socket => "socket",
+            Self::Config => "config",
+            Self::File => "file",
+            Self::Sway => "sway",
+        }
+    }
+}
+
+impl fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|k| k.name()).collect();
+                format!(
+                    "unknown source '{s}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Something keybindings can be loaded from
+pub trait BindSource {
+    fn kind(&self) -> SourceKind;
+
+    /// Whether the binds come from the Hyprland instance of this session, rather than another
+    /// instance or a file; only these are saved to history and get config comments merged in
+    fn is_live(&self) -> bool;
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason>;
+
+    /// Hyprland config the binds are defined in, when description edits can be written to it;
+    /// `None` for other instances, which may run with a different config
+    fn config_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Read from another Hyprland instance (`None`: the current one); ignored by offline sources
+    fn set_instance(&mut self, _instance: Option<&str>) {}
+
+    /// Whether the binds are the local config's whatever the instance, so they must not
+    /// stand in for another instance's
+    fn local_config(&self) -> bool {
+        false
+    }
+}
+
+/// Reject text that parsed to no binds, which usually is an error message
+fn non_empty(raw: &str, parsed: ParsedBinds) -> Result<ParsedBinds, SourceErrorReason> {
+    if parsed.keybindings.entries.is_empty() && !raw.trim().is_empty() {
+        let first_line = raw.trim().lines().next().unwrap_or_default();
+        return Err(SourceErrorReason::InvalidData(format!(
+            "no binds found in reply: {first_line}"
+        )));
+    }
+    Ok(parsed)
+}
+
+/// `hyprctl binds`
+#[derive(Default)]
+pub struct HyprctlSource {
+    pub instance: Option<String>,
+}
+
+impl BindSource for HyprctlSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Hyprctl
+    }
+
+    fn is_live(&self) -> bool {
+        self.instance.is_none()
+    }
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+        let raw = fetch_hyprctl_binds(self.instance.as_deref())?;
+        non_empty(&raw, parse_binds_output_with_warnings(&raw))
+    }
+
+    fn config_path(&self) -> Option<PathBuf> {
+        self.instance.is_none().then(hyprland_config_path)
+    }
+
+    fn set_instance(&mut self, instance: Option<&str>) {
+        self.instance = instance.map(String::from);
+    }
+}
+
+/// `hyprctl binds -j`
+#[derive(Default)]
+pub struct HyprctlJsonSource {
+    pub instance: Option<String>,
+}
+
+impl BindSource for HyprctlJsonSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::HyprctlJson
+    }
+
+    fn is_live(&self) -> bool {
+        self.instance.is_none()
+    }
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+        let raw = fetch_hyprctl_binds_json(self.instance.as_deref())?;
+        Ok(parse_hyprctl_json(&raw)?.into())
+    }
+
+    fn config_path(&self) -> Option<PathBuf> {
+        self.instance.is_none().then(hyprland_config_path)
+    }
+
+    fn set_instance(&mut self, instance: Option<&str>) {
+        self.instance = instance.map(String::from);
+    }
+}
+
+/// `j/binds` on the Hyprland IPC socket, without needing hyprctl
+#[derive(Default)]
+pub struct SocketSource {
+    pub instance: Option<String>,
+}
+
+impl BindSource for SocketSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Socket
+    }
+
+    fn is_live(&self) -> bool {
+        self.instance.is_none()
+    }
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+        let raw = request_hyprland_socket(self.instance.as_deref(), "j/binds")?;
+        Ok(parse_hyprctl_json(&raw)?.into())
+    }
+
+    fn config_path(&self) -> Option<PathBuf> {
+        self.instance.is_none().then(hyprland_config_path)
+    }
+
+    fn set_instance(&mut self, instance: Option<&str>) {
+        self.instance = instance.map(String::from);
+    }
+}
+
+/// Bind lines of a Hyprland config, following `source =`
+///
+/// Trailing `# comments` are used as descriptions of binds without one.
+pub struct ConfigSource {
+    pub path: PathBuf,
+}
+
+impl Default for ConfigSource {
+    fn default() -> Self {
+        Self {
+            path: hyprland_config_path(),
+        }
+    }
+}
+
+impl BindSource for ConfigSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Config
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    fn local_config(&self) -> bool {
+        true
+    }
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+        let lines = read_config_lines(&self.path).map_err(|e| read_config_error(&self.path, e))?;
+        let (binds, warnings) = parse_config_lines_with_warnings(&lines);
+        let entries = binds
+            .into_iter()
+            .map(|bind| {
+                let mut entry = bind.entry;
+                if entry.description.is_empty() {
+                    entry.description = bind.comment;
+                }
+                entry
+            })
+            .collect();
+        Ok(ParsedBinds {
+            keybindings: KeyBindings { entries },
+            warnings,
+        })
+    }
+
+    fn config_path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+}
+
+/// Map a missing config file to `Unavailable`
+fn read_config_error(path: &Path, e: io::Error) -> SourceErrorReason {
+    if e.kind() == io::ErrorKind::NotFound {
+        SourceErrorReason::Unavailable(format!("{} does not exist", path.display()))
+    } else {
+        SourceErrorReason::Io(e)
+    }
+}
+
+/// `bindsym`/`bindcode` lines of a Sway or i3 config, following `include`
+pub struct SwaySource {
+    pub path: PathBuf,
+}
+
+impl Default for SwaySource {
+    fn default() -> Self {
+        Self {
+            path: sway_config_path(),
+        }
+    }
+}
+
+impl BindSource for SwaySource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Sway
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    fn local_config(&self) -> bool {
+        true
+    }
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+        let lines =
+            read_sway_config_lines(&self.path).map_err(|e| read_config_error(&self.path, e))?;
+        Ok(parse_sway_lines(&lines))
+    }
+}
+
+/// `--input`: a file or stdin (`-`) with `KeyBindings` JSON or hyprctl output
+pub struct FileSource {
+    path: Option<PathBuf>,
+    /// Stdin is read once up front, so reloading keeps the same binds
+    stdin: Option<String>,
+}
+
+impl FileSource {
+    pub fn new(path: Option<&Path>) -> io::Result<Self> {
+        let stdin = if path == Some(Path::new("-")) {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            Some(text)
+        } else {
+            None
+        };
+        Ok(Self {
+            path: path.map(Path::to_path_buf),
+            stdin,
+        })
+    }
+}
+
+impl BindSource for FileSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::File
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+        if let Some(text) = &self.stdin {
+            return Ok(parse_bind_input(text)?);
+        }
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| SourceErrorReason::Unavailable("no --input file given".to_string()))?;
+        Ok(parse_bind_input(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+/// Keybindings loaded through a `SourceChain`
+#[derive(Debug)]
+pub struct LoadedBinds {
+    pub keybindings: KeyBindings,
+    /// Source the binds came from
+    pub source: SourceKind,
+    pub live: bool,
+    /// Hyprland config description edits are written to; `None` when the binds can't be edited
+    pub config_path: Option<PathBuf>,
+    /// Binds the parser skipped
+    pub warnings: Vec<ParseWarning>,
+    /// Sources tried before it, and why they failed
+    pub skipped: Vec<SourceError>,
+}
+
+/// Sources tried in order until one succeeds
+pub struct SourceChain {
+    sources: Vec<Box<dyn BindSource>>,
+    instance: Option<String>,
+}
+
+impl SourceChain {
+    /// Used when neither the command line nor `config.json` choose sources
+    pub const DEFAULT: [SourceKind; 3] =
+        [SourceKind::Hyprctl, SourceKind::Socket, SourceKind::Config];
+
+    pub fn new(sources: Vec<Box<dyn BindSource>>) -> Self {
+        Self {
+            sources,
+            instance: None,
+        }
+    }
+
+    /// Live sources of one Hyprland instance
+    pub fn for_instance(signature: &str) -> Self {
+        let mut chain = Self::new(vec![
+            Box::new(HyprctlSource::default()),
+            Box::new(SocketSource::default()),
+        ]);
+        chain.set_instance(Some(signature));
+        chain
+    }
+
+    /// Instance the live sources read from; `None` is the current one
+    pub fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    pub fn set_instance(&mut self, instance: Option<&str>) {
+        self.instance = instance.map(String::from);
+        for source in &mut self.sources {
+            source.set_instance(instance);
+        }
+    }
+
+    /// Chain of the given kinds
+    ///
+    /// `input` is the path used by `file`, `config` the one used by `config` and `sway`
+    /// instead of their default locations.
+    pub fn from_kinds(
+        kinds: &[SourceKind],
+        input: Option<&Path>,
+        config: Option<&Path>,
+    ) -> io::Result<Self> {
+        let mut sources: Vec<Box<dyn BindSource>> = Vec::new();
+        for kind in kinds {
+            sources.push(match kind {
+                SourceKind::Hyprctl => Box::new(HyprctlSource::default()),
+                SourceKind::HyprctlJson => Box::new(HyprctlJsonSource::default()),
+                SourceKind::Socket => Box::new(SocketSource::default()),
+                SourceKind::Config => {
+                    Box::new(config.map_or_else(ConfigSource::default, |p| ConfigSource {
+                        path: p.to_path_buf(),
+                    }))
+                }
+                SourceKind::File => Box::new(FileSource::new(input)?),
+                SourceKind::Sway => {
+                    Box::new(config.map_or_else(SwaySource::default, |p| SwaySource {
+                        path: p.to_path_buf(),
+                    }))
+                }
+            });
+        }
+        Ok(Self::new(sources))
+    }
+
+    /// Load from the first source that succeeds
+    ///
+    /// With an instance set, config and Sway sources are skipped rather than showing the local
+    /// config as that instance's binds.
+    pub fn load(&self) -> Result<LoadedBinds, LoadError> {
+        let mut skipped = Vec::new();
+        for source in &self.sources {
+            if let Some(instance) = &self.instance
+                && source.local_config()
+            {
+                skipped.push(SourceError {
+                    source: source.kind(),
+                    reason: SourceErrorReason::Unavailable(format!(
+                        "reads the local config, not the binds of instance {instance}"
+                    )),
+                });
+                continue;
+            }
+            match source.load() {
+                Ok(parsed) => {
+                    // `code:N` keys read the keyboard layout from hyprctl; do that here rather than on the first frame
+                    parsed.keybindings.parse_keys();
+                    return Ok(LoadedBinds {
+                        keybindings: parsed.keybindings,
+                        warnings: parsed.warnings,
+                        source: source.kind(),
+                        live: source.is_live(),
+                        config_path: source.config_path(),
+                        skipped,
+                    });
+                }
+                Err(reason) => skipped.push(SourceError {
+                    source: source.kind(),
+                    reason,
+                }),
+            }
+        }
+        Err(LoadError { errors: skipped })
+    }
+}
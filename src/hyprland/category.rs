@@ -0,0 +1,211 @@
+use super::models::{KeyBindEntry, KeyBindings};
+use serde::{Deserialize, Serialize};
+
+/// Category for binds no rule matched
+pub const OTHER_CATEGORY: &str = "Other";
+
+/// Assigns `category` to binds matching all of its non-empty criteria
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryRule {
+    pub category: String,
+    /// Dispatcher names (e.g. "workspace"), matched case-insensitively
+    #[serde(default)]
+    pub dispatchers: Vec<String>,
+    /// Substrings of the dispatcher argument (e.g. "playerctl")
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Key name prefixes (e.g. `XF86Audio`)
+    #[serde(default)]
+    pub keys: Vec<String>,
+}
+
+impl CategoryRule {
+    fn new(category: &str, dispatchers: &[&str], args: &[&str], keys: &[&str]) -> Self {
+        let owned = |items: &[&str]| items.iter().map(|s| (*s).to_string()).collect();
+        Self {
+            category: category.to_string(),
+            dispatchers: owned(dispatchers),
+            args: owned(args),
+            keys: owned(keys),
+        }
+    }
+
+    /// Check if the rule matches; a rule without criteria never matches
+    pub fn matches(&self, entry: &KeyBindEntry) -> bool {
+        if self.dispatchers.is_empty() && self.args.is_empty() && self.keys.is_empty() {
+            return false;
+        }
+        let (dispatcher, arg) = entry.dispatcher_and_arg();
+        let arg = arg.to_lowercase();
+        // The key as written and its keysym, so `code:10` also matches rules for `1`
+        let keys = [
+            entry.key().to_lowercase(),
+            entry.normalized_key().name().to_lowercase(),
+        ];
+
+        let dispatcher_match = self.dispatchers.is_empty()
+            || self
+                .dispatchers
+                .iter()
+                .any(|d| d.eq_ignore_ascii_case(dispatcher));
+        let arg_match =
+            self.args.is_empty() || self.args.iter().any(|a| arg.contains(&a.to_lowercase()));
+        let key_match = self.keys.is_empty()
+            || self.keys.iter().any(|k| {
+                let k = k.to_lowercase();
+                keys.iter().any(|key| key.starts_with(&k))
+            });
+
+        dispatcher_match && arg_match && key_match
+    }
+}
+
+const EXEC: &[&str] = &["exec", "execr"];
+
+const MEDIA_KEYS: &[&str] = &["XF86Audio", "XF86MonBrightness", "XF86KbdBrightness"];
+
+const MEDIA_TOOLS: &[&str] = &[
+    "wpctl",
+    "pactl",
+    "pamixer",
+    "pulsemixer",
+    "amixer",
+    "playerctl",
+    "brightnessctl",
+];
+
+const SCREENSHOT_TOOLS: &[&str] = &["grim", "hyprshot", "flameshot", "hyprpicker"];
+
+const SYSTEM_TOOLS: &[&str] = &[
+    "hyprlock",
+    "swaylock",
+    "wlogout",
+    "systemctl",
+    "loginctl",
+    "hyprctl reload",
+];
+
+const SYSTEM_DISPATCHERS: &[&str] = &[
+    "exit",
+    "dpms",
+    "submap",
+    "pass",
+    "sendshortcut",
+    "global",
+    "forcerendererreload",
+];
+
+const WORKSPACE_DISPATCHERS: &[&str] = &[
+    "workspace",
+    "movetoworkspace",
+    "movetoworkspacesilent",
+    "togglespecialworkspace",
+    "focusworkspaceoncurrentmonitor",
+    "movecurrentworkspacetomonitor",
+    "moveworkspacetomonitor",
+    "swapactiveworkspaces",
+    "renameworkspace",
+    "focusmonitor",
+];
+
+const WINDOW_DISPATCHERS: &[&str] = &[
+    "movefocus",
+    "movewindow",
+    "swapwindow",
+    "resizeactive",
+    "moveactive",
+    "resizewindowpixel",
+    "movewindowpixel",
+    "killactive",
+    "forcekillactive",
+    "closewindow",
+    "togglefloating",
+    "setfloating",
+    "settiled",
+    "fullscreen",
+    "fullscreenstate",
+    "pseudo",
+    "pin",
+    "centerwindow",
+    "togglesplit",
+    "swapsplit",
+    "layoutmsg",
+    "cyclenext",
+    "swapnext",
+    "focuswindow",
+    "focusurgentorlast",
+    "focuscurrentorlast",
+    "bringactivetotop",
+    "alterzorder",
+    "togglegroup",
+    "changegroupactive",
+    "moveintogroup",
+    "moveoutofgroup",
+    "movewindoworgroup",
+    "lockgroups",
+    "lockactivegroup",
+    "mouse",
+];
+
+/// Built-in rules, checked in order after the user's rules
+fn builtin_rules() -> Vec<CategoryRule> {
+    vec![
+        CategoryRule::new("Media", &[], &[], MEDIA_KEYS),
+        CategoryRule::new("Media", EXEC, MEDIA_TOOLS, &[]),
+        CategoryRule::new("Screenshots", EXEC, SCREENSHOT_TOOLS, &[]),
+        CategoryRule::new("System", EXEC, SYSTEM_TOOLS, &[]),
+        CategoryRule::new("System", SYSTEM_DISPATCHERS, &[], &[]),
+        CategoryRule::new("Workspaces", WORKSPACE_DISPATCHERS, &[], &[]),
+        CategoryRule::new("Windows", WINDOW_DISPATCHERS, &[], &[]),
+        CategoryRule::new("Launchers", EXEC, &[], &[]),
+    ]
+}
+
+/// Rule-based bind categoriser
+#[derive(Debug, Clone)]
+pub struct Categorizer {
+    rules: Vec<CategoryRule>,
+}
+
+impl Default for Categorizer {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl Categorizer {
+    /// Categoriser checking `user_rules` before the built-in rules
+    pub fn new(user_rules: Vec<CategoryRule>) -> Self {
+        let mut rules = user_rules;
+        rules.retain(|r| !r.category.trim().is_empty());
+        rules.extend(builtin_rules());
+        Self { rules }
+    }
+
+    pub fn categorize(&self, entry: &KeyBindEntry) -> &str {
+        self.rules
+            .iter()
+            .find(|r| r.matches(entry))
+            .map_or(OTHER_CATEGORY, |r| r.category.as_str())
+    }
+
+    /// Set the category of every entry
+    pub fn apply(&self, keybindings: &mut KeyBindings) {
+        for entry in &mut keybindings.entries {
+            entry.category = self.categorize(entry).to_string();
+        }
+    }
+
+    /// Position of a category in group order (rule order, `Other` last)
+    pub fn rank(&self, category: &str) -> usize {
+        self.rules
+            .iter()
+            .position(|r| r.category == category)
+            .unwrap_or(self.rules.len())
+    }
+
+    /// Stable-sort entries into category groups, keeping their order within a group
+    pub fn group(&self, entries: &mut [KeyBindEntry]) {
+        entries.sort_by_key(|e| self.rank(&e.category));
+    }
+}
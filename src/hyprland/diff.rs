@@ -0,0 +1,202 @@
+use super::models::{KeyBindEntry, KeyBindings};
+use super::parser::modifiers_to_modmask;
+use serde::Serialize;
+use std::fmt::Write;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// A bind whose combo stayed the same while its action or description changed
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedBind {
+    pub before: KeyBindEntry,
+    pub after: KeyBindEntry,
+}
+
+/// Differences between two sets of keybindings
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BindDiff {
+    pub added: Vec<KeyBindEntry>,
+    pub removed: Vec<KeyBindEntry>,
+    pub changed: Vec<ChangedBind>,
+}
+
+/// A row of a side-by-side comparison; `None` where one side lacks the bind
+#[derive(Debug, Clone)]
+pub struct BindPair {
+    pub left: Option<KeyBindEntry>,
+    pub right: Option<KeyBindEntry>,
+}
+
+impl BindPair {
+    /// Both sides have the bind with the same action and description
+    pub fn is_same(&self) -> bool {
+        match (&self.left, &self.right) {
+            (Some(left), Some(right)) => same_content(left, right),
+            _ => false,
+        }
+    }
+}
+
+/// Binds are matched by submap, modifiers and key
+fn same_combo(a: &KeyBindEntry, b: &KeyBindEntry) -> bool {
+    a.submap == b.submap
+        && modifiers_to_modmask(&a.modifiers) == modifiers_to_modmask(&b.modifiers)
+        && a.normalized_key().same_key(b.normalized_key())
+}
+
+fn same_content(a: &KeyBindEntry, b: &KeyBindEntry) -> bool {
+    a.dispatcher_and_arg() == b.dispatcher_and_arg()
+        && a.description == b.description
+        && a.flags == b.flags
+}
+
+fn combo_label(entry: &KeyBindEntry) -> String {
+    let mut label = entry.combo_label();
+    if !entry.submap.is_empty() {
+        let _ = write!(label, " [{}]", entry.submap);
+    }
+    label
+}
+
+fn action_label(entry: &KeyBindEntry) -> String {
+    if entry.description.is_empty() {
+        entry.command.clone()
+    } else {
+        format!("{} ({})", entry.command, entry.description)
+    }
+}
+
+impl BindDiff {
+    /// Compare `old` with `new`
+    ///
+    /// Identical binds are paired first, so a combo bound more than once only
+    /// reports the binds that actually differ.
+    pub fn new(old: &KeyBindings, new: &KeyBindings) -> Self {
+        let mut unmatched_new: Vec<Option<&KeyBindEntry>> = new.entries.iter().map(Some).collect();
+        let mut unmatched_old = Vec::new();
+
+        for entry in &old.entries {
+            let identical = unmatched_new
+                .iter()
+                .position(|n| n.is_some_and(|n| same_combo(entry, n) && same_content(entry, n)));
+            match identical {
+                Some(i) => unmatched_new[i] = None,
+                None => unmatched_old.push(entry),
+            }
+        }
+
+        let mut diff = Self::default();
+        for entry in unmatched_old {
+            let same = unmatched_new
+                .iter_mut()
+                .find(|n| n.is_some_and(|n| same_combo(entry, n)));
+            match same.and_then(Option::take) {
+                Some(after) => diff.changed.push(ChangedBind {
+                    before: entry.clone(),
+                    after: after.clone(),
+                }),
+                None => diff.removed.push(entry.clone()),
+            }
+        }
+        diff.added = unmatched_new.into_iter().flatten().cloned().collect();
+        diff
+    }
+
+    /// Line up `left` and `right` by combo, matching like `new`
+    ///
+    /// Rows follow the order of `left`, with binds only in `right` at the end.
+    pub fn side_by_side(left: &KeyBindings, right: &KeyBindings) -> Vec<BindPair> {
+        let mut matches: Vec<Option<usize>> = vec![None; left.entries.len()];
+        let mut taken = vec![false; right.entries.len()];
+
+        let passes: [fn(&KeyBindEntry, &KeyBindEntry) -> bool; 2] =
+            [|a, b| same_combo(a, b) && same_content(a, b), same_combo];
+        for matches_pass in passes {
+            for (l, entry) in left.entries.iter().enumerate() {
+                if matches[l].is_some() {
+                    continue;
+                }
+                let found = right
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .position(|(r, other)| !taken[r] && matches_pass(entry, other));
+                if let Some(r) = found {
+                    taken[r] = true;
+                    matches[l] = Some(r);
+                }
+            }
+        }
+
+        let mut rows: Vec<BindPair> = left
+            .entries
+            .iter()
+            .zip(matches)
+            .map(|(entry, r)| BindPair {
+                left: Some(entry.clone()),
+                right: r.map(|r| right.entries[r].clone()),
+            })
+            .collect();
+        rows.extend(
+            right
+                .entries
+                .iter()
+                .zip(taken)
+                .filter(|(_, taken)| !taken)
+                .map(|(entry, _)| BindPair {
+                    left: None,
+                    right: Some(entry.clone()),
+                }),
+        );
+        rows
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Export as JSON
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Export as a readable summary, with ANSI colours when `color` is set
+    pub fn to_text(&self, color: bool) -> String {
+        let paint = |code: &str, text: String| {
+            if color {
+                format!("{code}{text}{RESET}")
+            } else {
+                text
+            }
+        };
+
+        let mut out = String::new();
+        for entry in &self.added {
+            let line = format!("+ {}: {}", combo_label(entry), action_label(entry));
+            let _ = writeln!(out, "{}", paint(GREEN, line));
+        }
+        for entry in &self.removed {
+            let line = format!("- {}: {}", combo_label(entry), action_label(entry));
+            let _ = writeln!(out, "{}", paint(RED, line));
+        }
+        for change in &self.changed {
+            let line = format!("~ {}", combo_label(&change.after));
+            let _ = writeln!(out, "{}", paint(YELLOW, line));
+            let before = format!("    - {}", action_label(&change.before));
+            let _ = writeln!(out, "{}", paint(RED, before));
+            let after = format!("    + {}", action_label(&change.after));
+            let _ = writeln!(out, "{}", paint(GREEN, after));
+        }
+        let _ = writeln!(
+            out,
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        );
+        out
+    }
+}
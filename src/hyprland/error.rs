@@ -0,0 +1,84 @@
+use super::bind_source::SourceKind;
+use std::fmt;
+use std::io;
+
+/// Why a single source could not provide keybindings
+#[derive(Debug)]
+pub enum SourceErrorReason {
+    /// The source cannot be used here (e.g. Hyprland is not running)
+    Unavailable(String),
+    /// A command ran but reported failure
+    CommandFailed(String),
+    Io(io::Error),
+    /// The source answered with data that could not be parsed
+    InvalidData(String),
+}
+
+impl fmt::Display for SourceErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(msg) => write!(f, "not available: {msg}"),
+            Self::CommandFailed(msg) => write!(f, "command failed: {msg}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::InvalidData(msg) => write!(f, "invalid data: {msg}"),
+        }
+    }
+}
+
+impl From<io::Error> for SourceErrorReason {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SourceErrorReason {
+    fn from(e: serde_json::Error) -> Self {
+        Self::InvalidData(e.to_string())
+    }
+}
+
+/// A source that failed, and why
+#[derive(Debug)]
+pub struct SourceError {
+    pub source: SourceKind,
+    pub reason: SourceErrorReason,
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.source, self.reason)
+    }
+}
+
+impl std::error::Error for SourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.reason {
+            SourceErrorReason::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Every source in the chain failed
+#[derive(Debug, Default)]
+pub struct LoadError {
+    /// Failures in chain order
+    pub errors: Vec<SourceError>,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.errors.is_empty() {
+            return write!(f, "no bind sources configured");
+        }
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LoadError {}
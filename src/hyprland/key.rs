@@ -0,0 +1,637 @@
+use super::source::hyprland_option;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Maximum `include` nesting followed in XKB symbols files
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Named keysyms in their canonical case, in sort order
+const NAMED: &[&str] = &[
+    "Return",
+    "Escape",
+    "Tab",
+    "ISO_Left_Tab",
+    "BackSpace",
+    "Delete",
+    "Insert",
+    "space",
+    "Left",
+    "Right",
+    "Up",
+    "Down",
+    "Home",
+    "End",
+    "Prior",
+    "Next",
+    "Page_Up",
+    "Page_Down",
+    "Print",
+    "Sys_Req",
+    "Pause",
+    "Break",
+    "Menu",
+    "Caps_Lock",
+    "Num_Lock",
+    "Scroll_Lock",
+    "Super_L",
+    "Super_R",
+    "Shift_L",
+    "Shift_R",
+    "Control_L",
+    "Control_R",
+    "Alt_L",
+    "Alt_R",
+    "Meta_L",
+    "Meta_R",
+    "Hyper_L",
+    "Hyper_R",
+    "ISO_Level3_Shift",
+    "Mode_switch",
+    "Multi_key",
+    "Cancel",
+    "Undo",
+    "Redo",
+    "Find",
+    "Help",
+];
+
+/// Keysyms of punctuation and the character they type, in sort order
+const PUNCTUATION: &[(&str, &str)] = &[
+    ("grave", "`"),
+    ("asciitilde", "~"),
+    ("exclam", "!"),
+    ("at", "@"),
+    ("numbersign", "#"),
+    ("dollar", "$"),
+    ("percent", "%"),
+    ("asciicircum", "^"),
+    ("ampersand", "&"),
+    ("asterisk", "*"),
+    ("parenleft", "("),
+    ("parenright", ")"),
+    ("minus", "-"),
+    ("underscore", "_"),
+    ("equal", "="),
+    ("plus", "+"),
+    ("bracketleft", "["),
+    ("bracketright", "]"),
+    ("braceleft", "{"),
+    ("braceright", "}"),
+    ("backslash", "\\"),
+    ("bar", "|"),
+    ("semicolon", ";"),
+    ("colon", ":"),
+    ("apostrophe", "'"),
+    ("quotedbl", "\""),
+    ("comma", ","),
+    ("period", "."),
+    ("less", "<"),
+    ("greater", ">"),
+    ("slash", "/"),
+    ("question", "?"),
+];
+
+/// Keypad keysyms after `KP_` in their canonical case, and their label
+const KEYPAD: &[(&str, &str)] = &[
+    ("Add", "+"),
+    ("Subtract", "-"),
+    ("Multiply", "*"),
+    ("Divide", "/"),
+    ("Decimal", "."),
+    ("Separator", ","),
+    ("Equal", "="),
+    ("Enter", "Enter"),
+    ("Space", "Space"),
+    ("Tab", "Tab"),
+    ("Home", "Home"),
+    ("End", "End"),
+    ("Left", "Left"),
+    ("Right", "Right"),
+    ("Up", "Up"),
+    ("Down", "Down"),
+    ("Prior", "PgUp"),
+    ("Next", "PgDn"),
+    ("Page_Up", "PgUp"),
+    ("Page_Down", "PgDn"),
+    ("Begin", "Begin"),
+    ("Insert", "Ins"),
+    ("Delete", "Del"),
+];
+
+/// Linux event codes of mouse buttons (`BTN_LEFT` and on) and their names
+const MOUSE_BUTTONS: &[(u32, &str)] = &[
+    (272, "Left"),
+    (273, "Right"),
+    (274, "Middle"),
+    (275, "Side"),
+    (276, "Extra"),
+    (277, "Forward"),
+    (278, "Back"),
+    (279, "Task"),
+];
+
+/// Mouse wheel directions, as Hyprland names them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wheel {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Wheel {
+    const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Up => "mouse_up",
+            Self::Down => "mouse_down",
+            Self::Left => "mouse_left",
+            Self::Right => "mouse_right",
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Up => "Wheel Up",
+            Self::Down => "Wheel Down",
+            Self::Left => "Wheel Left",
+            Self::Right => "Wheel Right",
+        }
+    }
+}
+
+/// A bind's key, normalised from the name Hyprland reports
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Keysym such as `q`, `Return` or `XF86AudioMute`, in its canonical case
+    Sym(String),
+    /// `code:N` keycode, with the keysym it types in the active layout
+    Code {
+        code: u32,
+        sym: Option<String>,
+    },
+    /// `mouse:N` button, by Linux event code
+    Mouse(u32),
+    Wheel(Wheel),
+}
+
+/// Keysym `name` in its canonical case: letters lowercase, known names as xkbcommon
+/// spells them, and anything else as written
+fn canonical_sym(name: &str) -> String {
+    let lower = name.to_ascii_lowercase();
+    if name.len() == 1 {
+        return lower;
+    }
+    if let Some(n) = function_key(&lower) {
+        return format!("F{n}");
+    }
+    if let Some(rest) = lower.strip_prefix("kp_") {
+        let rest = KEYPAD
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(rest))
+            .map_or_else(|| rest.to_string(), |(k, _)| (*k).to_string());
+        return format!("KP_{rest}");
+    }
+    if lower.starts_with("xf86") {
+        return format!("XF86{}", &name[4..]);
+    }
+    NAMED
+        .iter()
+        .copied()
+        .chain(PUNCTUATION.iter().map(|(k, _)| *k))
+        .find(|k| k.eq_ignore_ascii_case(name))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Number of a function key name such as `f5`, lowercase
+fn function_key(lower: &str) -> Option<u32> {
+    lower.strip_prefix('f')?.parse().ok().filter(|n| *n > 0)
+}
+
+/// Display label of a keysym
+fn sym_label(sym: &str) -> String {
+    if sym.chars().count() == 1 {
+        return sym.to_uppercase();
+    }
+    if let Some(&(_, c)) = PUNCTUATION.iter().find(|(k, _)| *k == sym) {
+        return c.to_string();
+    }
+    if sym == "space" {
+        return "Space".to_string();
+    }
+    if let Some(rest) = sym.strip_prefix("KP_") {
+        let label = KEYPAD
+            .iter()
+            .find(|(k, _)| *k == rest)
+            .map_or(rest, |(_, label)| label);
+        return format!("KP {label}");
+    }
+    sym.to_string()
+}
+
+/// Sort group and rank of a keysym
+fn sym_order(sym: &str) -> (u8, u32, String) {
+    let lower = sym.to_ascii_lowercase();
+    let mut chars = sym.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_alphabetic() {
+            return (0, 0, lower);
+        }
+        if let Some(digit) = c.to_digit(10) {
+            return (1, digit, String::new());
+        }
+    }
+    if let Some(n) = function_key(&lower) {
+        return (2, n, String::new());
+    }
+    let rank = |i: usize| u32::try_from(i).unwrap_or(u32::MAX);
+    if let Some(i) = NAMED.iter().position(|k| *k == sym) {
+        return (3, rank(i), String::new());
+    }
+    if let Some(i) = PUNCTUATION.iter().position(|(k, _)| *k == sym) {
+        return (4, rank(i), String::new());
+    }
+    if lower.starts_with("kp_") {
+        return (5, 0, lower);
+    }
+    if lower.starts_with("xf86") {
+        return (7, 0, lower);
+    }
+    (6, 0, lower)
+}
+
+impl Key {
+    /// Normalise a key name from a bind, resolving keycodes in the active layout
+    pub fn parse(raw: &str) -> Self {
+        if raw.trim().to_ascii_lowercase().starts_with("code:") {
+            Self::parse_with(raw, Keymap::active())
+        } else {
+            Self::parse_with(raw, &Keymap::default())
+        }
+    }
+
+    /// Normalise a key name, resolving keycodes in `keymap`
+    pub fn parse_with(raw: &str, keymap: &Keymap) -> Self {
+        let raw = raw.trim();
+        let lower = raw.to_ascii_lowercase();
+        if let Some(code) = lower.strip_prefix("code:").and_then(|c| c.parse().ok()) {
+            return Self::Code {
+                code,
+                sym: keymap.sym(code).map(canonical_sym),
+            };
+        }
+        if let Some(code) = lower.strip_prefix("mouse:").and_then(|c| c.parse().ok()) {
+            return Self::Mouse(code);
+        }
+        if let Some(wheel) = Wheel::ALL.into_iter().find(|w| w.name() == lower) {
+            return Self::Wheel(wheel);
+        }
+        Self::Sym(canonical_sym(raw))
+    }
+
+    /// Name for lookups: the keysym where known, otherwise Hyprland's `code:N` or `mouse:N`
+    pub fn name(&self) -> String {
+        match self {
+            Self::Sym(sym) | Self::Code { sym: Some(sym), .. } => sym.clone(),
+            Self::Code { code, sym: None } => format!("code:{code}"),
+            Self::Mouse(code) => format!("mouse:{code}"),
+            Self::Wheel(wheel) => wheel.name().to_string(),
+        }
+    }
+
+    /// Label for people: `Q`, `[`, `KP +`, `Mouse Left`, `Wheel Up`
+    pub fn label(&self) -> String {
+        match self {
+            Self::Sym(sym) | Self::Code { sym: Some(sym), .. } => sym_label(sym),
+            Self::Code { code, sym: None } => format!("code:{code}"),
+            Self::Mouse(code) => MOUSE_BUTTONS.iter().find(|(c, _)| c == code).map_or_else(
+                || format!("Mouse {code}"),
+                |(_, name)| format!("Mouse {name}"),
+            ),
+            Self::Wheel(wheel) => wheel.label().to_string(),
+        }
+    }
+
+    /// Order in the table: letters, digits, F-keys, editing and navigation keys,
+    /// punctuation, keypad, other keysyms, XF86 keys, mouse buttons, the wheel,
+    /// then keycodes the layout doesn't have
+    pub fn sort_key(&self) -> (u8, u32, String) {
+        match self {
+            Self::Sym(sym) | Self::Code { sym: Some(sym), .. } => sym_order(sym),
+            Self::Mouse(code) => (8, *code, String::new()),
+            Self::Wheel(wheel) => (9, *wheel as u32, String::new()),
+            Self::Code { code, sym: None } => (10, *code, String::new()),
+        }
+    }
+
+    /// Whether both name the same key, e.g. `code:24` and `q` in a US layout
+    pub fn same_key(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Sym(a) | Self::Code { sym: Some(a), .. },
+                Self::Sym(b) | Self::Code { sym: Some(b), .. },
+            ) => a.eq_ignore_ascii_case(b),
+            (Self::Code { code: a, sym: None }, Self::Code { code: b, sym: None })
+            | (Self::Mouse(a), Self::Mouse(b)) => a == b,
+            (Self::Wheel(a), Self::Wheel(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+/// Keysyms typed by keycodes without modifiers, from XKB keymap files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keymap {
+    syms: HashMap<u32, String>,
+}
+
+/// Remove `//` and `#` comments
+fn strip_comments(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let end = [line.find("//"), line.find('#')]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(line.len());
+            &line[..end]
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Text between the brace at `open` and its closing brace
+fn braced(text: &str, open: usize) -> Option<&str> {
+    let mut depth = 0;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Body of the `kind "name"` section, or of the default one without a name
+fn section<'a>(text: &'a str, kind: &str, name: Option<&str>) -> Option<&'a str> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    while let Some(found) = text[start..].find(kind) {
+        let at = start + found;
+        start = at + kind.len();
+        let rest = &text[start..];
+        let Some(open) = rest.find('{') else { break };
+        let Some(title) = rest[..open].trim().strip_prefix('"') else {
+            continue;
+        };
+        let title = title.trim_end().trim_end_matches('"');
+        let line_start = text[..at].rfind('\n').map_or(0, |i| i + 1);
+        let header = text[line_start..at].to_string()
+            + text[..line_start].lines().last().unwrap_or_default();
+        if let Some(body) = braced(text, start + open) {
+            sections.push((title, header.contains("default"), body));
+        }
+    }
+    let found = name.map_or_else(
+        || {
+            sections
+                .iter()
+                .find(|(_, default, _)| *default)
+                .or_else(|| sections.first())
+        },
+        |name| sections.iter().find(|(t, _, _)| *t == name),
+    );
+    found.map(|(_, _, body)| *body)
+}
+
+/// Keycodes by key name from an `xkb_keycodes` body, following aliases
+fn parse_keycodes(body: &str) -> HashMap<String, u32> {
+    let mut codes = HashMap::new();
+    let mut aliases = Vec::new();
+    for statement in body.split(';') {
+        let statement = statement.trim();
+        let (alias, rest) = statement
+            .strip_prefix("alias")
+            .map_or((false, statement), |rest| (true, rest.trim_start()));
+        let Some((name, value)) = rest.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        let value = value.trim();
+        if !name.starts_with('<') {
+            continue;
+        }
+        if alias {
+            aliases.push((name.to_string(), value.to_string()));
+        } else if let Ok(code) = value.parse() {
+            codes.insert(name.to_string(), code);
+        }
+    }
+    for (alias, target) in aliases {
+        if let Some(&code) = codes.get(&target) {
+            codes.entry(alias).or_insert(code);
+        }
+    }
+    codes
+}
+
+/// First keysym of the first group in the body of a `key <NAME> { ... }` statement
+fn first_keysym(body: &str) -> Option<&str> {
+    let mut search = 0;
+    while let Some(found) = body[search..].find('[') {
+        let at = search + found;
+        search = at + 1;
+        // `symbols[Group1]` and `type[Group1]` name a group rather than list keysyms
+        let before = body[..at].trim_end().chars().last();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let end = body[at..].find(']')? + at;
+        let sym = body[at + 1..end].split(',').next()?.trim();
+        return (!sym.is_empty() && sym != "NoSymbol" && sym != "VoidSymbol").then_some(sym);
+    }
+    None
+}
+
+/// Position of the next statement starting with the word `word`, such as `key  <TAB>`
+/// but not `key.type`
+fn find_word(text: &str, word: &str, next: impl Fn(char) -> bool) -> Option<usize> {
+    let mut search = 0;
+    while let Some(found) = text[search..].find(word) {
+        let at = search + found;
+        search = at + word.len();
+        let before = text[..at].chars().last();
+        let after = text[search..].trim_start().chars().next();
+        if !before.is_some_and(|c| c.is_alphanumeric() || c == '_') && after.is_some_and(&next) {
+            return Some(at);
+        }
+    }
+    None
+}
+
+/// Reads `xkb_symbols` sections, following their includes
+struct SymbolsReader<'a> {
+    dir: &'a Path,
+    keycodes: &'a HashMap<String, u32>,
+    syms: HashMap<u32, String>,
+}
+
+impl SymbolsReader<'_> {
+    /// Read `file(section)` entries of an include such as `pc+us(intl)+inet(evdev)`
+    fn include(&mut self, spec: &str, depth: usize) {
+        if depth > MAX_INCLUDE_DEPTH {
+            return;
+        }
+        for part in spec
+            .split(['+', '|'])
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            let (file, name) = part
+                .split_once('(')
+                .map_or((part, None), |(f, n)| (f, Some(n.trim_end_matches(')'))));
+            if file.contains("..") {
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(self.dir.join(file)) else {
+                continue;
+            };
+            let text = strip_comments(&text);
+            if let Some(body) = section(&text, "xkb_symbols", name) {
+                self.read(body, depth + 1);
+            }
+        }
+    }
+
+    /// Apply the includes and keys of a section body in order
+    fn read(&mut self, body: &str, depth: usize) {
+        let mut rest = body;
+        loop {
+            let include = find_word(rest, "include", |c| c == '"');
+            let key = find_word(rest, "key", |c| c == '<');
+            let next = match (include, key) {
+                (Some(i), Some(k)) => i.min(k),
+                (Some(i), None) => i,
+                (None, Some(k)) => k,
+                (None, None) => break,
+            };
+            if Some(next) == include {
+                rest = &rest[next + "include".len()..];
+                let Some(open) = rest.find('"') else { break };
+                let Some(len) = rest[open + 1..].find('"') else {
+                    break;
+                };
+                let spec = rest[open + 1..open + 1 + len].to_string();
+                rest = &rest[open + len + 2..];
+                self.include(&spec, depth);
+                continue;
+            }
+            rest = &rest[next + "key".len()..];
+            let Some(name_end) = rest.find('>') else {
+                break;
+            };
+            let name = rest[..=name_end].trim();
+            let Some(open) = rest.find('{') else { break };
+            let Some(key_body) = braced(rest, open) else {
+                break;
+            };
+            if let (Some(&code), Some(sym)) = (self.keycodes.get(name), first_keysym(key_body)) {
+                self.syms.insert(code, sym.to_string());
+            }
+            rest = &rest[open + key_body.len() + 2..];
+        }
+    }
+}
+
+/// `XKB_CONFIG_ROOT`, or where the XKB data is usually installed
+fn xkb_root() -> PathBuf {
+    std::env::var("XKB_CONFIG_ROOT")
+        .map_or_else(|_| PathBuf::from("/usr/share/X11/xkb"), PathBuf::from)
+}
+
+/// First entry of a comma-separated layout list, empty for none
+fn first_layout(list: Option<String>) -> Option<String> {
+    list.and_then(|l| l.split(',').next().map(|s| s.trim().to_string()))
+        .filter(|l| !l.is_empty())
+}
+
+impl Keymap {
+    /// Keymap of `layout` and `variant` from the XKB data in `root`, as Hyprland's
+    /// default `evdev` rules build it
+    pub fn from_xkb(root: &Path, layout: &str, variant: Option<&str>) -> Self {
+        let keycodes = std::fs::read_to_string(root.join("keycodes").join("evdev"))
+            .map(|text| strip_comments(&text))
+            .ok()
+            .and_then(|text| section(&text, "xkb_keycodes", None).map(parse_keycodes))
+            .unwrap_or_default();
+        let layout = variant
+            .filter(|v| !v.is_empty())
+            .map_or_else(|| layout.to_string(), |v| format!("{layout}({v})"));
+        let mut reader = SymbolsReader {
+            dir: &root.join("symbols"),
+            keycodes: &keycodes,
+            syms: HashMap::new(),
+        };
+        reader.include(&format!("pc+{layout}+inet(evdev)"), 0);
+        Self { syms: reader.syms }
+    }
+
+    /// Keymap of a compiled keymap file, like the one `input:kb_file` names
+    ///
+    /// Includes inside it are read from the XKB data in `root`.
+    pub fn from_keymap(text: &str, root: &Path) -> Self {
+        let text = strip_comments(text);
+        let keycodes = section(&text, "xkb_keycodes", None)
+            .map(parse_keycodes)
+            .unwrap_or_default();
+        let mut reader = SymbolsReader {
+            dir: &root.join("symbols"),
+            keycodes: &keycodes,
+            syms: HashMap::new(),
+        };
+        if let Some(body) = section(&text, "xkb_symbols", None) {
+            reader.read(body, 0);
+        }
+        Self { syms: reader.syms }
+    }
+
+    /// Keymap of the active layout: Hyprland's `input:kb_file` or `input:kb_layout`
+    /// and `kb_variant`, then `XKB_DEFAULT_LAYOUT`, then `us`; read once
+    pub fn active() -> &'static Self {
+        static ACTIVE: OnceLock<Keymap> = OnceLock::new();
+        ACTIVE.get_or_init(|| {
+            let root = xkb_root();
+            if let Some(file) = hyprland_option("input:kb_file").filter(|f| !f.is_empty())
+                && let Ok(text) = std::fs::read_to_string(file)
+            {
+                return Self::from_keymap(&text, &root);
+            }
+            let layout = first_layout(hyprland_option("input:kb_layout"))
+                .or_else(|| first_layout(std::env::var("XKB_DEFAULT_LAYOUT").ok()))
+                .unwrap_or_else(|| "us".to_string());
+            let variant = first_layout(hyprland_option("input:kb_variant"))
+                .or_else(|| first_layout(std::env::var("XKB_DEFAULT_VARIANT").ok()));
+            Self::from_xkb(&root, &layout, variant.as_deref())
+        })
+    }
+
+    /// Keysym typed by `code`
+    pub fn sym(&self, code: u32) -> Option<&str> {
+        self.syms.get(&code).map(String::as_str)
+    }
+}
@@ -0,0 +1,189 @@
+use super::models::{KeyBindEntry, KeyBindings};
+use crate::ui::styling::icons::{get_icon, get_key_icon};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Launchers with an output profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Launcher {
+    Dmenu,
+    /// Adds `\0icon`, `meta` and `info` row options
+    Rofi,
+    /// Adds `\0icon` row options
+    Fuzzel,
+    Wofi,
+}
+
+impl Launcher {
+    /// Line template used when none is configured
+    pub const fn default_template(self) -> &'static str {
+        match self {
+            Self::Dmenu => "{keys} : {text}",
+            Self::Rofi | Self::Fuzzel | Self::Wofi => "{keys}  {text}",
+        }
+    }
+}
+
+/// Per-launcher settings in `config.json`; command-line options override them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LauncherSettings {
+    /// Line template, e.g. `{keys}\t{description}\t{command}`
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Key names instead of icons
+    #[serde(default)]
+    pub plain: bool,
+    /// Append ` #<index>` to each line
+    #[serde(default)]
+    pub index: bool,
+}
+
+/// How `KeyBindings::to_launcher` writes lines
+#[derive(Debug, Clone)]
+pub struct LauncherProfile {
+    pub launcher: Launcher,
+    pub template: String,
+    pub plain: bool,
+    pub index: bool,
+    /// Prefix lines with `[Category]`
+    pub grouped: bool,
+}
+
+impl LauncherProfile {
+    pub fn new(launcher: Launcher, settings: &LauncherSettings) -> Self {
+        Self {
+            launcher,
+            template: settings
+                .template
+                .clone()
+                .unwrap_or_else(|| launcher.default_template().to_string()),
+            plain: settings.plain,
+            index: settings.index,
+            grouped: false,
+        }
+    }
+}
+
+/// Modifiers and key as icons (`A + B`) or names (`A+B`)
+fn keys_label(entry: &KeyBindEntry, plain: bool) -> String {
+    if plain {
+        return entry.combo_label();
+    }
+    let key = get_key_icon(entry.normalized_key());
+    if entry.modifiers.is_empty() {
+        return key;
+    }
+    let modifier_icons: Vec<String> = entry
+        .modifiers
+        .split('+')
+        .map(|m| get_icon(m.trim()))
+        .collect();
+    format!("{} + {key}", modifier_icons.join(" + "))
+}
+
+/// Icon name of the program an `exec` bind starts, e.g. `firefox` for `exec [float] /usr/bin/firefox -P`
+pub fn exec_icon(entry: &KeyBindEntry) -> Option<String> {
+    let (dispatcher, arg) = entry.dispatcher_and_arg();
+    if !matches!(dispatcher, "exec" | "execr") {
+        return None;
+    }
+    // Skip window rules like `[workspace 2 silent]`
+    let program = arg.rsplit_once(']').map_or(arg, |(_, rest)| rest);
+    let program = program
+        .split_whitespace()
+        .find(|word| !word.contains('='))?;
+    let name = Path::new(program).file_name()?.to_string_lossy();
+    Some(name.into_owned())
+}
+
+/// Fill `{placeholders}` of a template; `\t` and `\n` escapes become tabs and newlines
+pub fn render_template(template: &str, entry: &KeyBindEntry, keys: &str, index: usize) -> String {
+    let text = if entry.description.is_empty() {
+        &entry.command
+    } else {
+        &entry.description
+    };
+    let (dispatcher, arg) = entry.dispatcher_and_arg();
+    let index = index.to_string();
+    let values = [
+        ("keys", keys),
+        ("text", text),
+        ("description", &entry.description),
+        ("command", &entry.command),
+        ("dispatcher", dispatcher),
+        ("arg", arg),
+        ("submap", &entry.submap),
+        ("category", &entry.category),
+        ("index", &index),
+    ];
+    let escapes = [("\\t", "\t"), ("\\n", "\n")];
+
+    // One pass, so placeholders inside substituted values are left alone
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '\\']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let placeholder = values.iter().find_map(|(name, value)| {
+            let after = rest
+                .strip_prefix('{')?
+                .strip_prefix(name)?
+                .strip_prefix('}')?;
+            Some((*value, after))
+        });
+        let escape = escapes
+            .iter()
+            .find_map(|(from, to)| Some((*to, rest.strip_prefix(from)?)));
+        if let Some((value, after)) = placeholder.or(escape) {
+            out.push_str(value);
+            rest = after;
+        } else {
+            // `{` and `\` are one byte
+            out.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+impl KeyBindings {
+    /// Export one line per bind for a launcher
+    ///
+    /// Rofi and fuzzel lines get row options after a `\0`: the icon of the program
+    /// an `exec` bind starts, and for rofi the command as `meta` (searchable) and the
+    /// bind's index as `info`, which rofi's script mode passes back on selection.
+    pub fn to_launcher(&self, profile: &LauncherProfile) -> String {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let keys = keys_label(entry, profile.plain);
+                let mut line = render_template(&profile.template, entry, &keys, index);
+                if profile.grouped && !entry.category.is_empty() {
+                    line = format!("[{}] {line}", entry.category);
+                }
+                if profile.index {
+                    line = format!("{line} #{index}");
+                }
+
+                let mut options = Vec::new();
+                if matches!(profile.launcher, Launcher::Rofi | Launcher::Fuzzel)
+                    && let Some(icon) = exec_icon(entry)
+                {
+                    options.push(format!("icon\x1f{icon}"));
+                }
+                if profile.launcher == Launcher::Rofi {
+                    options.push(format!("meta\x1f{}", entry.command));
+                    options.push(format!("info\x1f{index}"));
+                }
+                if !options.is_empty() {
+                    line = format!("{line}\0{}", options.join("\x1f"));
+                }
+                line
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
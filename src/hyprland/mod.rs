@@ -1,7 +1,28 @@
+pub mod annotations;
+pub mod bind_source;
+pub mod category;
+pub mod diff;
+pub mod error;
+pub mod key;
+pub mod launcher;
 pub mod models;
 pub mod parser;
 pub mod source;
+pub mod sway;
+pub mod terminal;
 
-pub use models::{KeyBindEntry, KeyBindings, SearchOptions};
-pub use parser::parse_binds_output;
-pub use source::fetch_hyprctl_binds;
+pub use annotations::{Annotation, Annotations};
+pub use bind_source::{SourceChain, SourceKind};
+pub use category::{Categorizer, CategoryRule};
+pub use diff::BindDiff;
+pub use error::SourceError;
+pub use key::Key;
+pub use models::{
+    BindId, ConfigBind, ConfigLine, DescriptionStyle, HyprlandInstance, KeyBindEntry, KeyBindings,
+    ParseWarning, SearchOptions,
+};
+pub use parser::{parse_config_lines, rewrite_bind_description};
+pub use source::{
+    hyprland_config_path, hyprland_option, list_instances, read_config_lines,
+    read_keybindings_json, reload_hyprland, replace_config_line,
+};
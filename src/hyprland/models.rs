@@ -1,12 +1,20 @@
-use crate::ui::styling::icons::get_icon;
+use super::key::Key;
+use super::parser::{modifiers_to_modmask, modmask_to_string};
 use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 
 /// Options for searching keybindings
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchOptions {
     pub keybind: bool,
     pub command: bool,
     pub description: bool,
+    /// Notes and tags from local annotations
+    pub annotations: bool,
 }
 
 impl Default for SearchOptions {
@@ -15,44 +23,302 @@ impl Default for SearchOptions {
             keybind: true,
             command: true,
             description: true,
+            annotations: true,
         }
     }
 }
 
+/// Bind flags (the letters following `bind`, e.g. `bindel`)
+// Allow this to mirror Hyprland's flag set one-to-one
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BindFlags {
+    /// `l`: works while an input inhibitor (lockscreen) is active
+    pub locked: bool,
+    /// `r`: triggers on key release
+    pub release: bool,
+    /// `o`: triggers on long press
+    pub long_press: bool,
+    /// `e`: repeats while held
+    pub repeat: bool,
+    /// `n`: key event is also passed to the active window
+    pub non_consuming: bool,
+    /// `m`: mouse bind (dispatcher is `mouse`)
+    pub mouse: bool,
+    /// `t`: transparent, cannot be shadowed by other binds
+    pub transparent: bool,
+    /// `i`: ignores modifiers
+    pub ignore_mods: bool,
+}
+
+impl BindFlags {
+    /// Flag letters in Hyprland syntax, without the description flag `d`
+    pub fn letters(self) -> String {
+        [
+            (self.locked, 'l'),
+            (self.release, 'r'),
+            (self.long_press, 'o'),
+            (self.repeat, 'e'),
+            (self.non_consuming, 'n'),
+            (self.mouse, 'm'),
+            (self.transparent, 't'),
+            (self.ignore_mods, 'i'),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, letter)| *letter)
+        .collect()
+    }
+}
+
 /// Keybind entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyBindEntry {
     /// Modifier keys (e.g., "SUPER", "SUPER SHIFT")
     pub modifiers: String,
-    /// Key name (e.g., "A", "F", "Return")
-    pub key: String,
+    /// Key name (e.g., "A", "F", "Return"); read-only, so `normalized_key` can't go stale
+    key: String,
     /// Command (e.g., "exec kitty", "killactive")
     pub command: String,
     /// Description (obtained from bind line comment)
     pub description: String,
+    /// Submap the bind belongs to (empty for the global map)
+    #[serde(default)]
+    pub submap: String,
+    /// Dispatcher (e.g., "exec", "killactive")
+    #[serde(default)]
+    pub dispatcher: String,
+    /// Dispatcher argument (e.g., "kitty")
+    #[serde(default)]
+    pub arg: String,
+    /// Bind flags
+    #[serde(default)]
+    pub flags: BindFlags,
+    /// Notes from the local annotation file
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    /// Tags from the local annotation file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Category assigned by the categoriser (e.g. "Workspaces")
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    /// `key` normalised, parsed the first time it is needed and kept by clones
+    #[serde(skip)]
+    normalized_key: OnceLock<Key>,
+}
+
+/// Stable identity of a bind, independent of its description
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BindId {
+    pub submap: String,
+    pub modmask: u32,
+    /// Key name, lowercased
+    pub key: String,
+    pub dispatcher: String,
+    pub arg: String,
+}
+
+impl std::fmt::Display for BindId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let modifiers = modmask_to_string(self.modmask);
+        if !modifiers.is_empty() {
+            write!(f, "{modifiers}+")?;
+        }
+        write!(f, "{} → {}", self.key, self.dispatcher)?;
+        if !self.arg.is_empty() {
+            write!(f, " {}", self.arg)?;
+        }
+        if !self.submap.is_empty() {
+            write!(f, " [{}]", self.submap)?;
+        }
+        Ok(())
+    }
 }
 
 impl KeyBindEntry {
-    pub const fn new(modifiers: String, key: String, command: String, description: String) -> Self {
+    pub fn new(modifiers: String, key: String, command: String, description: String) -> Self {
         Self {
             modifiers,
             key,
             command,
             description,
+            submap: String::new(),
+            dispatcher: String::new(),
+            arg: String::new(),
+            flags: BindFlags::default(),
+            notes: String::new(),
+            tags: Vec::new(),
+            category: String::new(),
+            normalized_key: OnceLock::new(),
+        }
+    }
+
+    /// Dispatcher and argument, split from `command` when not set explicitly
+    pub fn dispatcher_and_arg(&self) -> (&str, &str) {
+        if self.dispatcher.is_empty() {
+            self.command
+                .split_once(' ')
+                .map_or((self.command.as_str(), ""), |(d, a)| (d, a.trim()))
+        } else {
+            (&self.dispatcher, &self.arg)
+        }
+    }
+
+    /// Stable identity of this bind (submap, modmask, key and action)
+    pub fn bind_id(&self) -> BindId {
+        let (dispatcher, arg) = self.dispatcher_and_arg();
+        BindId {
+            submap: self.submap.clone(),
+            modmask: modifiers_to_modmask(&self.modifiers),
+            key: self.key.to_lowercase(),
+            dispatcher: dispatcher.to_string(),
+            arg: arg.to_string(),
+        }
+    }
+
+    /// Key name as the bind writes it
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Normalised key, with `code:N` resolved in the active layout
+    pub fn normalized_key(&self) -> &Key {
+        self.normalized_key.get_or_init(|| Key::parse(&self.key))
+    }
+
+    /// Modifiers and the key's label, e.g. `SUPER+SHIFT+[` for `bracketleft`
+    pub fn combo_label(&self) -> String {
+        let label = self.normalized_key().label();
+        if self.modifiers.is_empty() {
+            label
+        } else {
+            format!("{}+{label}", self.modifiers)
         }
     }
 
+    /// Check if this bind is triggered by `modmask` and `key`, which may name the key
+    /// differently (`Return` and `return`, `code:24` and `q`)
+    pub fn has_combo(&self, modmask: u32, key: &str) -> bool {
+        modifiers_to_modmask(&self.modifiers) == modmask
+            && self.normalized_key().same_key(&Key::parse(key))
+    }
+
+    /// Check if both entries describe the same bind
+    pub fn same_bind(&self, other: &Self) -> bool {
+        self.bind_id() == other.bind_id()
+    }
+
     /// Check if this entry matches the search query
     pub fn matches(&self, query: &str, options: &SearchOptions) -> bool {
         let query_lower = query.to_lowercase();
-        let keybind_match = options.keybind
-            && (self.modifiers.to_lowercase().contains(&query_lower)
-                || self.key.to_lowercase().contains(&query_lower));
+        let keybind_match = options.keybind && {
+            let key = self.normalized_key();
+            self.modifiers.to_lowercase().contains(&query_lower)
+                || [self.key.clone(), key.name(), key.label()]
+                    .iter()
+                    .any(|name| name.to_lowercase().contains(&query_lower))
+        };
         let command_match = options.command && self.command.to_lowercase().contains(&query_lower);
         let description_match =
             options.description && self.description.to_lowercase().contains(&query_lower);
+        let annotation_match = options.annotations
+            && (self.notes.to_lowercase().contains(&query_lower)
+                || self
+                    .tags
+                    .iter()
+                    .any(|t| t.to_lowercase().contains(&query_lower)));
 
-        keybind_match || command_match || description_match
+        keybind_match || command_match || description_match || annotation_match
+    }
+}
+
+/// How a description is written into `hyprland.conf`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DescriptionStyle {
+    /// Convert `bind` to `bindd` and store it as the description field
+    #[default]
+    Bindd,
+    /// Append it as a trailing `# comment`
+    Comment,
+}
+
+/// A single line of a Hyprland config, possibly from a sourced file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLine {
+    /// File the line was read from
+    pub path: PathBuf,
+    /// 1-based line number within `path`
+    pub number: usize,
+    /// Raw line text without the line ending
+    pub text: String,
+}
+
+/// A bind parsed from a config line
+#[derive(Debug, Clone)]
+pub struct ConfigBind {
+    pub entry: KeyBindEntry,
+    /// Index into the parsed `ConfigLine` slice
+    pub line: usize,
+    /// Trailing comment, if any
+    pub comment: String,
+}
+
+/// A running Hyprland instance found in the runtime directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyprlandInstance {
+    /// Value of `HYPRLAND_INSTANCE_SIGNATURE` in that session
+    pub signature: String,
+    pub pid: Option<u32>,
+    /// Wayland socket name, e.g. `wayland-1`
+    pub wayland_display: Option<String>,
+}
+
+impl HyprlandInstance {
+    /// Short label for pickers
+    pub fn label(&self) -> String {
+        self.wayland_display.as_ref().map_or_else(
+            || self.signature.clone(),
+            |display| format!("{display} ({})", self.signature),
+        )
+    }
+}
+
+/// A bind definition the parser skipped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParseWarning {
+    /// File the bind is in; `None` for `hyprctl binds` output
+    pub path: Option<PathBuf>,
+    /// 1-based line number of the bind
+    pub line: usize,
+    pub reason: String,
+    /// Text of the skipped bind
+    pub raw: String,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}:{}: {}", path.display(), self.line, self.reason),
+            None => write!(f, "line {}: {}", self.line, self.reason),
+        }
+    }
+}
+
+/// Keybindings with the warnings collected while parsing them
+#[derive(Debug, Clone, Default)]
+pub struct ParsedBinds {
+    pub keybindings: KeyBindings,
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl From<KeyBindings> for ParsedBinds {
+    fn from(keybindings: KeyBindings) -> Self {
+        Self {
+            keybindings,
+            warnings: Vec::new(),
+        }
     }
 }
 
@@ -74,42 +340,125 @@ impl KeyBindings {
         self.entries.push(entry);
     }
 
+    /// Parse every key now, so drawing and searching don't have to
+    pub fn parse_keys(&self) {
+        for entry in &self.entries {
+            entry.normalized_key();
+        }
+    }
+
     /// Export as JSON
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
 
-    /// Export as dmenu-compatible format with NERD FONT icons
-    pub fn to_dmenu(&self) -> String {
-        self.entries
-            .iter()
-            .map(|entry| {
-                // Get icon for each modifier and key then combine by +
-                let keybind = if entry.modifiers.is_empty() {
-                    get_icon(&entry.key)
-                } else {
-                    let modifiers: Vec<&str> = entry.modifiers.split('+').collect();
-                    let modifier_icons: Vec<String> =
-                        modifiers.iter().map(|m| get_icon(m.trim())).collect();
-                    let key_icon = get_icon(&entry.key);
-                    format!("{} + {}", modifier_icons.join(" + "), key_icon)
-                };
-
-                // Get display text: description if available, otherwise command
-                let display_text = if !entry.description.is_empty() {
-                    entry.description.clone()
-                } else if !entry.command.is_empty() {
-                    entry.command.clone()
-                } else {
-                    String::new()
-                };
-
-                // Output line "keybind : display_text"
-                format!("{keybind} : {display_text}")
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
+    /// Binds sharing a submap, modifiers and key, in order of first appearance
+    pub fn duplicate_combos(&self) -> Vec<Vec<&KeyBindEntry>> {
+        let mut groups: Vec<Vec<&KeyBindEntry>> = Vec::new();
+        for entry in &self.entries {
+            let modmask = modifiers_to_modmask(&entry.modifiers);
+            match groups.iter_mut().find(|g| {
+                g[0].submap == entry.submap
+                    && modifiers_to_modmask(&g[0].modifiers) == modmask
+                    && g[0].normalized_key().same_key(entry.normalized_key())
+            }) {
+                Some(group) => group.push(entry),
+                None => groups.push(vec![entry]),
+            }
+        }
+        groups.retain(|g| g.len() > 1);
+        groups
     }
+
+    /// Export as `hyprland.conf` bind lines
+    ///
+    /// Binds are grouped by submap, each non-global submap wrapped in
+    /// `submap = <name>` / `submap = reset`. When `main_mod` is given (e.g. "SUPER"),
+    /// a `$mainMod` variable is declared and used in place of that modifier.
+    /// Commas in descriptions cannot be expressed in `bindd` and become `;`.
+    /// With `grouped`, a `# <category>` comment precedes each run of binds in a category.
+    pub fn to_hyprconf(&self, main_mod: Option<&str>, grouped: bool) -> String {
+        let main_mod = main_mod.map(str::to_uppercase);
+        let mut out = String::from("# Generated by HyprBind\n");
+        if let Some(main_mod) = &main_mod {
+            let _ = writeln!(out, "$mainMod = {main_mod}");
+        }
+
+        let mut submaps: Vec<&str> = Vec::new();
+        for entry in &self.entries {
+            if !submaps.contains(&entry.submap.as_str()) {
+                submaps.push(&entry.submap);
+            }
+        }
+        // Global binds first
+        submaps.sort_by_key(|s| !s.is_empty());
+
+        for submap in submaps {
+            out.push('\n');
+            if !submap.is_empty() {
+                let _ = writeln!(out, "submap = {submap}");
+            }
+            let mut category = None;
+            for entry in self.entries.iter().filter(|e| e.submap == submap) {
+                if grouped && !entry.category.is_empty() && category != Some(&entry.category) {
+                    let _ = writeln!(out, "# {}", escape_hyprconf(&entry.category));
+                    category = Some(&entry.category);
+                }
+                out.push_str(&hyprconf_line(entry, main_mod.as_deref()));
+                out.push('\n');
+            }
+            if !submap.is_empty() {
+                out.push_str("submap = reset\n");
+            }
+        }
+
+        out
+    }
+}
+
+/// Escape a value for `hyprland.conf`, where a single `#` starts a comment
+fn escape_hyprconf(value: &str) -> String {
+    value.replace('#', "##")
+}
+
+fn hyprconf_line(entry: &KeyBindEntry, main_mod: Option<&str>) -> String {
+    let modifiers = entry
+        .modifiers
+        .split('+')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            if main_mod.is_some_and(|mm| m.eq_ignore_ascii_case(mm)) {
+                "$mainMod"
+            } else {
+                m
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    let has_description = !entry.description.is_empty();
+    let mut keyword = format!("bind{}", entry.flags.letters());
+    if has_description {
+        keyword.push('d');
+    }
+
+    let mut fields = vec![modifiers, escape_hyprconf(&entry.key)];
+    if has_description {
+        fields.push(escape_hyprconf(&entry.description.replace(',', ";")));
+    }
+    let (dispatcher, arg) = entry.dispatcher_and_arg();
+    if entry.flags.mouse {
+        // Mouse binds take the mouse action as their only argument
+        fields.push(escape_hyprconf(arg));
+    } else {
+        fields.push(escape_hyprconf(dispatcher));
+        if !arg.is_empty() {
+            fields.push(escape_hyprconf(arg));
+        }
+    }
+
+    format!("{keyword} = {}", fields.join(", "))
 }
 
 impl Default for KeyBindings {
@@ -1,26 +1,114 @@
-use super::models::{KeyBindEntry, KeyBindings};
+use super::models::{
+    BindFlags, ConfigBind, ConfigLine, DescriptionStyle, KeyBindEntry, KeyBindings, ParseWarning,
+    ParsedBinds,
+};
+use serde::Deserialize;
 use std::collections::HashMap;
 
-/// Parse the output text from hyprctl binds
-pub fn parse_binds_output(output: &str) -> KeyBindings {
-    let mut keybindings = KeyBindings::new();
-    let blocks: Vec<&str> = output.split("\n\n").collect();
+/// Parse the output text from hyprctl binds, keeping a warning for each skipped block
+pub fn parse_binds_output_with_warnings(output: &str) -> ParsedBinds {
+    let mut parsed = ParsedBinds::default();
+    let mut line = 1;
 
-    for block in blocks {
+    for block in output.split("\n\n") {
+        let block_line = line + block.lines().take_while(|l| l.trim().is_empty()).count();
+        line += block.matches('\n').count() + 2;
         if block.trim().is_empty() {
             continue;
         }
 
-        if let Some(entry) = parse_bind_block(block) {
-            keybindings.add_entry(entry);
+        match parse_bind_block(block) {
+            Ok(entry) => parsed.keybindings.add_entry(entry),
+            Err(reason) => parsed.warnings.push(ParseWarning {
+                path: None,
+                line: block_line,
+                reason,
+                raw: block.trim().to_string(),
+            }),
         }
     }
 
-    keybindings
+    parsed
 }
 
-/// Parse a single bind block
-fn parse_bind_block(block: &str) -> Option<KeyBindEntry> {
+/// Parse `KeyBindings` JSON or `hyprctl binds` text, detected from the first character
+///
+/// `{` starts `KeyBindings` JSON, `[` the output of `hyprctl binds -j`.
+pub fn parse_bind_input(input: &str) -> Result<ParsedBinds, serde_json::Error> {
+    match input.trim_start().chars().next() {
+        Some('{') => serde_json::from_str::<KeyBindings>(input).map(ParsedBinds::from),
+        Some('[') => parse_hyprctl_json(input).map(ParsedBinds::from),
+        _ => Ok(parse_binds_output_with_warnings(input)),
+    }
+}
+
+/// A bind as printed by `hyprctl binds -j`
+// Allow this to mirror hyprctl's JSON fields one-to-one
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct HyprctlBind {
+    locked: bool,
+    mouse: bool,
+    release: bool,
+    repeat: bool,
+    #[serde(rename = "longPress")]
+    long_press: bool,
+    non_consuming: bool,
+    transparent: bool,
+    ignore_mods: bool,
+    modmask: u32,
+    submap: String,
+    key: String,
+    keycode: u32,
+    description: String,
+    dispatcher: String,
+    arg: String,
+}
+
+/// Key of a bind from `hyprctl`, which leaves the key empty and sets the keycode for `code:N`
+fn bind_key(key: String, keycode: u32) -> String {
+    if key.is_empty() && keycode != 0 {
+        format!("code:{keycode}")
+    } else {
+        key
+    }
+}
+
+/// Parse the output of `hyprctl binds -j`
+pub fn parse_hyprctl_json(output: &str) -> Result<KeyBindings, serde_json::Error> {
+    let binds: Vec<HyprctlBind> = serde_json::from_str(output)?;
+    let entries = binds
+        .into_iter()
+        .map(|bind| {
+            let command = join_command(&bind.dispatcher, &bind.arg);
+            let mut entry = KeyBindEntry::new(
+                modmask_to_string(bind.modmask),
+                bind_key(bind.key, bind.keycode),
+                command,
+                bind.description,
+            );
+            entry.submap = bind.submap;
+            entry.dispatcher = bind.dispatcher;
+            entry.arg = bind.arg;
+            entry.flags = BindFlags {
+                locked: bind.locked,
+                release: bind.release,
+                long_press: bind.long_press,
+                repeat: bind.repeat,
+                non_consuming: bind.non_consuming,
+                mouse: bind.mouse,
+                transparent: bind.transparent,
+                ignore_mods: bind.ignore_mods,
+            };
+            entry
+        })
+        .collect();
+    Ok(KeyBindings { entries })
+}
+
+/// Parse a single bind block, or say why it cannot be used
+fn parse_bind_block(block: &str) -> Result<KeyBindEntry, String> {
     let mut fields = HashMap::new();
 
     for line in block.lines() {
@@ -32,24 +120,466 @@ fn parse_bind_block(block: &str) -> Option<KeyBindEntry> {
         }
     }
 
-    let modmask = fields.get("modmask")?.parse::<u32>().ok()?;
-    let key = fields.get("key")?.to_string();
-    let dispatcher = fields.get("dispatcher")?.to_string();
+    let field = |name: &str| {
+        fields
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("missing {name}"))
+    };
+    let modmask = field("modmask")?;
+    let modmask = modmask
+        .parse::<u32>()
+        .map_err(|_| format!("invalid modmask '{modmask}'"))?;
+    let keycode = fields
+        .get("keycode")
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(0);
+    let key = bind_key(field("key")?.to_string(), keycode);
+    let dispatcher = field("dispatcher")?.to_string();
     let arg = fields.get("arg").unwrap_or(&"").to_string();
     let description = fields.get("description").unwrap_or(&"").to_string();
+    let submap = fields.get("submap").unwrap_or(&"").to_string();
+
+    // Header line is "bind" followed by flag letters, e.g. "bindle"
+    let flags = block
+        .trim_start()
+        .lines()
+        .next()
+        .and_then(|header| header.trim().strip_prefix("bind"))
+        .and_then(parse_bind_flags)
+        .unwrap_or_default();
 
     let modifiers = modmask_to_string(modmask);
-    let command = if arg.is_empty() {
-        dispatcher
+    let command = join_command(&dispatcher, &arg);
+
+    let mut entry = KeyBindEntry::new(modifiers, key, command, description);
+    entry.submap = submap;
+    entry.dispatcher = dispatcher;
+    entry.arg = arg;
+    entry.flags = flags;
+    Ok(entry)
+}
+
+/// Parse bind flag letters (e.g. "le" from `bindle`)
+///
+/// Returns the flags and whether the description flag `d` was present,
+/// or `None` if an unknown letter is found.
+fn parse_flag_letters(letters: &str) -> Option<(BindFlags, bool)> {
+    let mut flags = BindFlags::default();
+    let mut has_description = false;
+
+    for letter in letters.chars() {
+        match letter {
+            'l' => flags.locked = true,
+            'r' => flags.release = true,
+            'o' => flags.long_press = true,
+            'e' => flags.repeat = true,
+            'n' => flags.non_consuming = true,
+            'm' => flags.mouse = true,
+            't' => flags.transparent = true,
+            'i' => flags.ignore_mods = true,
+            'd' => has_description = true,
+            // Flags without a representation in the model
+            'c' | 'g' | 's' | 'p' | 'u' => {}
+            _ => return None,
+        }
+    }
+
+    Some((flags, has_description))
+}
+
+fn parse_bind_flags(letters: &str) -> Option<BindFlags> {
+    parse_flag_letters(letters).map(|(flags, _)| flags)
+}
+
+fn join_command(dispatcher: &str, arg: &str) -> String {
+    if arg.is_empty() {
+        dispatcher.to_string()
     } else {
         format!("{dispatcher} {arg}")
+    }
+}
+
+/// Parse bind definitions from config lines, keeping where each bind came from
+///
+/// `lines` is expected in evaluation order, with sourced files inlined at
+/// their `source =` line, so variables and submaps carry across files.
+pub fn parse_config_lines(lines: &[ConfigLine]) -> Vec<ConfigBind> {
+    parse_config_lines_with_warnings(lines).0
+}
+
+/// Parse bind definitions from config lines, with a warning for each bind line skipped
+pub fn parse_config_lines_with_warnings(
+    lines: &[ConfigLine],
+) -> (Vec<ConfigBind>, Vec<ParseWarning>) {
+    let mut binds = Vec::new();
+    let mut warnings = Vec::new();
+    let variables = config_variables(lines);
+    let mut submap = String::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let (code, comment) = split_config_comment(&line.text);
+        let Some((keyword, value)) = code.split_once('=') else {
+            continue;
+        };
+        let keyword = keyword.trim();
+        let value = expand_variables(value.trim(), &variables);
+
+        if keyword == "submap" {
+            submap = if value == "reset" {
+                String::new()
+            } else {
+                value
+            };
+        } else if let Some(letters) = keyword.strip_prefix("bind")
+            // Skips options such as `binds:scroll_event_delay`
+            && letters.chars().all(|c| c.is_ascii_alphabetic())
+        {
+            let parsed = parse_flag_letters(letters)
+                .ok_or_else(|| format!("unknown bind flags '{letters}'"))
+                .and_then(|(flags, has_description)| {
+                    parse_config_bind(&value, flags, has_description, &submap)
+                });
+            match parsed {
+                Ok(entry) => binds.push(ConfigBind {
+                    entry,
+                    line: index,
+                    comment: comment.trim().to_string(),
+                }),
+                Err(reason) => warnings.push(ParseWarning {
+                    path: (!line.path.as_os_str().is_empty()).then(|| line.path.clone()),
+                    line: line.number,
+                    reason,
+                    raw: line.text.trim().to_string(),
+                }),
+            }
+        }
+    }
+
+    (binds, warnings)
+}
+
+/// Extract the path of a `source = <path>` line
+pub fn parse_source_directive(line: &str) -> Option<String> {
+    let (code, _) = split_config_comment(line);
+    let (keyword, value) = code.split_once('=')?;
+    if keyword.trim() != "source" {
+        return None;
+    }
+    let path = value.trim();
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Parse the value of a single `bind[flags] = ...` line, or say why it cannot be used
+fn parse_config_bind(
+    value: &str,
+    flags: BindFlags,
+    has_description: bool,
+    submap: &str,
+) -> Result<KeyBindEntry, String> {
+    // The last field (the dispatcher argument) may itself contain commas
+    let field_count = match (flags.mouse, has_description) {
+        (true, false) => 3,
+        (true, true) | (false, false) => 4,
+        (false, true) => 5,
     };
+    let mut fields = value.splitn(field_count, ',').map(str::trim);
+    let mut next = |name: &str| fields.next().ok_or_else(|| format!("missing {name}"));
 
-    Some(KeyBindEntry::new(modifiers, key, command, description))
+    let modifiers = modmask_to_string(modifiers_to_modmask(next("modifiers")?));
+    let key = next("key")?.to_string();
+    if key.is_empty() {
+        return Err("empty key".to_string());
+    }
+    let description = if has_description {
+        next("description")?.to_string()
+    } else {
+        String::new()
+    };
+    let (dispatcher, arg) = if flags.mouse {
+        ("mouse".to_string(), next("dispatcher")?.to_string())
+    } else {
+        (
+            next("dispatcher")?.to_string(),
+            next("argument").unwrap_or_default().to_string(),
+        )
+    };
+    let command = join_command(&dispatcher, &arg);
+
+    let mut entry = KeyBindEntry::new(modifiers, key, command, description);
+    entry.submap = submap.to_string();
+    entry.dispatcher = dispatcher;
+    entry.arg = arg;
+    entry.flags = flags;
+    Ok(entry)
 }
 
-/// Convert modmask (bitmask) to human-readable string
-fn modmask_to_string(modmask: u32) -> String {
+/// Split a line into code and trailing comment, turning `##` into a literal `#`
+fn split_config_comment(line: &str) -> (String, String) {
+    let mut code = String::with_capacity(line.len());
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '#' {
+            if chars.peek().is_some_and(|&(_, next)| next == '#') {
+                chars.next();
+            } else {
+                return (code, line[i + 1..].to_string());
+            }
+        }
+        code.push(c);
+    }
+    (code, String::new())
+}
+
+/// Byte offset of the first unescaped `#` in a raw config line
+fn comment_start(line: &str) -> Option<usize> {
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '#' {
+            if chars.peek().is_some_and(|&(_, next)| next == '#') {
+                chars.next();
+            } else {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Rewrite a raw `bind` line so that it carries `description`
+///
+/// With [`DescriptionStyle::Bindd`] the keyword gains the `d` flag and the
+/// description field is inserted or replaced; with [`DescriptionStyle::Comment`]
+/// the trailing comment is set. An empty description removes it. The rest of the
+/// line, including variables and spacing, is preserved. Returns `None` if the line
+/// is not a bind or its fields cannot be located unambiguously.
+pub fn rewrite_bind_description(
+    line: &str,
+    description: &str,
+    style: DescriptionStyle,
+) -> Option<String> {
+    let (code, comment) =
+        comment_start(line).map_or((line, None), |pos| (&line[..pos], Some(&line[pos..])));
+    let eq = code.find('=')?;
+    let (keyword_raw, value) = (&code[..eq], &code[eq + 1..]);
+    let keyword = keyword_raw.trim();
+    let letters = keyword.strip_prefix("bind")?;
+    let (_, has_description) = parse_flag_letters(letters)?;
+
+    match style {
+        DescriptionStyle::Bindd => {
+            if description.contains(',') {
+                return None;
+            }
+            // Inside a field `#` would start a comment; in a comment it is taken literally
+            let escaped = description.replace('#', "##");
+            let commas: Vec<usize> = value.match_indices(',').map(|(i, _)| i).collect();
+            let indent = &keyword_raw[..keyword_raw.len() - keyword_raw.trim_start().len()];
+            let spacing = &keyword_raw[keyword_raw.trim_end().len()..];
+            let new_letters: String = letters.chars().filter(|&c| c != 'd').collect();
+
+            let (new_keyword, new_value) = match (has_description, description.is_empty()) {
+                // Replace the existing description field
+                (true, false) => {
+                    let (&second, &third) = (commas.get(1)?, commas.get(2)?);
+                    (
+                        keyword.to_string(),
+                        format!("{} {escaped}{}", &value[..=second], &value[third..]),
+                    )
+                }
+                // Remove the description field
+                (true, true) => {
+                    let (&second, &third) = (commas.get(1)?, commas.get(2)?);
+                    (
+                        format!("bind{new_letters}"),
+                        format!("{}{}", &value[..second], &value[third..]),
+                    )
+                }
+                // Insert a description field after the key
+                (false, false) => {
+                    let &second = commas.get(1)?;
+                    (
+                        format!("bind{new_letters}d"),
+                        format!("{} {escaped},{}", &value[..=second], &value[second + 1..]),
+                    )
+                }
+                (false, true) => (keyword.to_string(), value.to_string()),
+            };
+            let trailing = comment.unwrap_or_default();
+            Some(format!(
+                "{indent}{new_keyword}{spacing}={new_value}{trailing}"
+            ))
+        }
+        DescriptionStyle::Comment => {
+            let code = code.trim_end();
+            if description.is_empty() {
+                Some(code.to_string())
+            } else {
+                Some(format!("{code} # {description}"))
+            }
+        }
+    }
+}
+
+pub(super) fn expand_variables(value: &str, variables: &[(String, String)]) -> String {
+    let mut out = value.to_string();
+    for (name, replacement) in variables {
+        out = out.replace(&format!("${name}"), replacement);
+    }
+    out
+}
+
+/// Modifier names understood in combos, as in hyprland.conf
+const MODIFIER_NAMES: [&str; 14] = [
+    "SHIFT", "CAPS", "CTRL", "CONTROL", "ALT", "MOD1", "MOD2", "MOD3", "SUPER", "WIN", "LOGO",
+    "MOD4", "META", "MOD5",
+];
+
+/// Variables assumed when the config does not define them, as in the default configs
+const DEFAULT_VARIABLES: [(&str, &str); 2] = [("mainMod", "SUPER"), ("mod", "SUPER")];
+
+/// Key names that differ from the xkb name binds use
+const KEY_ALIASES: [(&str, &str); 4] = [
+    ("enter", "Return"),
+    ("esc", "Escape"),
+    ("del", "Delete"),
+    ("spc", "space"),
+];
+
+/// `$name = value` definitions in config lines, longest names first
+pub fn config_variables(lines: &[ConfigLine]) -> Vec<(String, String)> {
+    let mut variables: Vec<(String, String)> = Vec::new();
+    for line in lines {
+        let (code, _) = split_config_comment(&line.text);
+        let Some((keyword, value)) = code.split_once('=') else {
+            continue;
+        };
+        if let Some(name) = keyword.trim().strip_prefix('$') {
+            let value = expand_variables(value.trim(), &variables);
+            variables.retain(|(n, _)| n != name);
+            variables.push((name.to_string(), value));
+            // Longest names first so `$mainModAlt` is not replaced as `$mainMod`
+            variables.sort_by_key(|(n, _)| std::cmp::Reverse(n.len()));
+        }
+    }
+    variables
+}
+
+/// Last value of an option such as `general:col.active_border` in config lines
+///
+/// Matches both `general:col.active_border = ...` and the option inside a
+/// `general { ... }` block, with `$variables` expanded.
+pub fn config_option(lines: &[ConfigLine], name: &str) -> Option<String> {
+    let variables = config_variables(lines);
+    let mut sections: Vec<String> = Vec::new();
+    let mut found = None;
+    for line in lines {
+        let (code, _) = split_config_comment(&line.text);
+        let code = code.trim();
+        if let Some(section) = code.strip_suffix('{') {
+            sections.push(section.trim().to_string());
+            continue;
+        }
+        if code == "}" {
+            sections.pop();
+            continue;
+        }
+        let Some((keyword, value)) = code.split_once('=') else {
+            continue;
+        };
+        let keyword = keyword.trim();
+        if keyword.starts_with('$') {
+            continue;
+        }
+        let value = expand_variables(value.trim(), &variables);
+        let path = sections
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(keyword))
+            .collect::<Vec<_>>()
+            .join(":");
+        if path == name {
+            found = Some(value);
+        }
+    }
+    found
+}
+
+/// Parse a key combination into modmask and key
+///
+/// Accepts `SUPER+SHIFT+Q`, `super shift q`, `$mainMod, Q` and `Mod4+q`: parts are
+/// separated by `+`, `,` or spaces, and the last part is the key. `$variables` are
+/// expanded from `variables`, with `$mainMod` and `$mod` defaulting to SUPER.
+pub fn parse_combo(combo: &str, variables: &[(String, String)]) -> Result<(u32, String), String> {
+    let mut combo = expand_variables(combo, variables);
+    for (name, value) in DEFAULT_VARIABLES {
+        combo = combo.replace(&format!("${name}"), value);
+    }
+    let mut parts: Vec<&str> = combo
+        .split(|c: char| c == '+' || c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let key = parts
+        .pop()
+        .filter(|_| !combo.trim_end().ends_with(['+', ',']))
+        .ok_or("missing key")?;
+    if let Some(variable) = parts.iter().chain([&key]).find(|p| p.starts_with('$')) {
+        return Err(format!("unknown variable '{variable}'"));
+    }
+    if let Some(part) = parts
+        .iter()
+        .find(|p| !MODIFIER_NAMES.iter().any(|m| m.eq_ignore_ascii_case(p)))
+    {
+        return Err(format!("'{part}' is not a modifier"));
+    }
+    let key = KEY_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(key))
+        .map_or(key, |(_, name)| name);
+    Ok((modifiers_to_modmask(&parts.join(" ")), key.to_string()))
+}
+
+/// Convert a modifier list as written in `hyprland.conf` (e.g. `SUPER SHIFT`,
+/// `SUPER_SHIFT`, `MOD4`) to a modmask, following Hyprland's own matching
+pub fn modifiers_to_modmask(modifiers: &str) -> u32 {
+    let upper = modifiers.to_uppercase();
+    let mut modmask = 0;
+
+    if upper.contains("SHIFT") {
+        modmask |= 0x01;
+    }
+    if upper.contains("CAPS") {
+        modmask |= 0x02;
+    }
+    if upper.contains("CTRL") || upper.contains("CONTROL") {
+        modmask |= 0x04;
+    }
+    if upper.contains("ALT") || upper.contains("MOD1") {
+        modmask |= 0x08;
+    }
+    if upper.contains("MOD2") {
+        modmask |= 0x10;
+    }
+    if upper.contains("MOD3") {
+        modmask |= 0x20;
+    }
+    if upper.contains("SUPER")
+        || upper.contains("WIN")
+        || upper.contains("LOGO")
+        || upper.contains("MOD4")
+        || upper.contains("META")
+    {
+        modmask |= 0x40;
+    }
+    if upper.contains("MOD5") {
+        modmask |= 0x80;
+    }
+
+    modmask
+}
+
+/// Convert modmask (bitmask) to human-readable string, naming every bit `modifiers_to_modmask` reads
+pub fn modmask_to_string(modmask: u32) -> String {
     let mut mods = Vec::new();
 
     if modmask & 0x40 != 0 {
@@ -64,6 +594,18 @@ fn modmask_to_string(modmask: u32) -> String {
     if modmask & 0x01 != 0 {
         mods.push("SHIFT");
     }
+    if modmask & 0x02 != 0 {
+        mods.push("CAPS");
+    }
+    if modmask & 0x10 != 0 {
+        mods.push("MOD2");
+    }
+    if modmask & 0x20 != 0 {
+        mods.push("MOD3");
+    }
+    if modmask & 0x80 != 0 {
+        mods.push("MOD5");
+    }
 
     if mods.is_empty() {
         String::new()
@@ -1,13 +1,430 @@
-use std::io;
+use super::error::SourceErrorReason;
+use super::models::{ConfigLine, HyprlandInstance, KeyBindings};
+use super::parser::{config_option, parse_source_directive};
+use super::sway::parse_include_directive;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
-/// Fetch raw output from hyprctl binds command
-pub fn fetch_hyprctl_binds() -> io::Result<String> {
-    let output = Command::new("hyprctl").arg("binds").output()?;
+/// Maximum `source =` nesting depth followed when reading the config
+const MAX_SOURCE_DEPTH: usize = 16;
+
+/// How long to wait for a reply from the Hyprland socket
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Signature of the given instance, or of the one this process runs in
+fn instance_signature(instance: Option<&str>) -> Result<String, SourceErrorReason> {
+    if let Some(signature) = instance {
+        return Ok(signature.to_string());
+    }
+    std::env::var("HYPRLAND_INSTANCE_SIGNATURE").map_err(|_| {
+        SourceErrorReason::Unavailable(
+            "Hyprland is not running (HYPRLAND_INSTANCE_SIGNATURE is not set)".to_string(),
+        )
+    })
+}
+
+/// Run hyprctl against an instance (the current one for `None`) and return its output
+fn run_hyprctl(instance: Option<&str>, args: &[&str]) -> Result<String, SourceErrorReason> {
+    let signature = instance_signature(instance)?;
+    let output = Command::new("hyprctl")
+        .args(["--instance", &signature])
+        .args(args)
+        .output()
+        .map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                SourceErrorReason::Unavailable("hyprctl is not installed".to_string())
+            } else {
+                SourceErrorReason::Io(e)
+            }
+        })?;
 
     if !output.status.success() {
-        return Err(io::Error::other("hyprctl binds command failed"));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        return Err(SourceErrorReason::CommandFailed(format!(
+            "hyprctl {} ({}): {message}",
+            args.join(" "),
+            output.status
+        )));
     }
 
     Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
+
+/// Fetch raw output from hyprctl binds command
+pub fn fetch_hyprctl_binds(instance: Option<&str>) -> Result<String, SourceErrorReason> {
+    run_hyprctl(instance, &["binds"])
+}
+
+/// Fetch the output of `hyprctl binds -j`
+pub fn fetch_hyprctl_binds_json(instance: Option<&str>) -> Result<String, SourceErrorReason> {
+    run_hyprctl(instance, &["binds", "-j"])
+}
+
+/// Fetch the output of `hyprctl getoption <name> -j`
+pub fn fetch_hyprctl_option(name: &str) -> Result<String, SourceErrorReason> {
+    run_hyprctl(None, &["getoption", name, "-j"])
+}
+
+/// An option of the running Hyprland, or from its config when hyprctl can't tell
+pub fn hyprland_option(name: &str) -> Option<String> {
+    if let Ok(json) = fetch_hyprctl_option(name)
+        && let Some(value) = option_from_json(&json)
+    {
+        return Some(value);
+    }
+    let lines = read_config_lines(&hyprland_config_path()).ok()?;
+    config_option(&lines, name)
+}
+
+/// What `hyprctl getoption` reports for a string option that is not set
+const UNSET_OPTION: &str = "[[EMPTY]]";
+
+/// Value in the output of `hyprctl getoption -j`; colours are an ARGB `int`, gradients `custom`
+///
+/// Unset and empty strings are `None`.
+pub fn option_from_json(json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    if let Some(text) = value["custom"].as_str().or_else(|| value["str"].as_str()) {
+        return (!text.is_empty() && text != UNSET_OPTION).then(|| text.to_string());
+    }
+    value["int"].as_i64().map(|argb| format!("0x{argb:08x}"))
+}
+
+/// Directory holding one directory per running Hyprland instance
+pub fn hyprland_runtime_dir() -> PathBuf {
+    std::env::var("XDG_RUNTIME_DIR")
+        .map_or_else(|_| PathBuf::from("/tmp"), PathBuf::from)
+        .join("hypr")
+}
+
+/// Path of the Hyprland IPC socket of an instance
+fn hyprland_socket_path(instance: Option<&str>) -> Result<PathBuf, SourceErrorReason> {
+    let signature = instance_signature(instance)?;
+    let candidates = [
+        hyprland_runtime_dir().join(&signature).join(".socket.sock"),
+        // Hyprland before 0.40 kept its sockets in /tmp
+        PathBuf::from("/tmp/hypr")
+            .join(&signature)
+            .join(".socket.sock"),
+    ];
+    candidates.into_iter().find(|p| p.exists()).ok_or_else(|| {
+        SourceErrorReason::Unavailable(format!("no Hyprland socket for instance {signature}"))
+    })
+}
+
+/// Send a request (e.g. `j/binds`) to the Hyprland IPC socket and read the reply
+pub fn request_hyprland_socket(
+    instance: Option<&str>,
+    request: &str,
+) -> Result<String, SourceErrorReason> {
+    let path = hyprland_socket_path(instance)?;
+    let mut stream = UnixStream::connect(&path)?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.write_all(request.as_bytes())?;
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    Ok(reply)
+}
+
+/// Hyprland instances with a socket in `hyprland_runtime_dir()`
+pub fn list_instances() -> Vec<HyprlandInstance> {
+    list_instances_in(&hyprland_runtime_dir())
+}
+
+/// Hyprland instances with a socket in `dir`, sorted by signature
+pub fn list_instances_in(dir: &Path) -> Vec<HyprlandInstance> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut instances: Vec<HyprlandInstance> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.join(".socket.sock").exists())
+        .filter_map(|p| {
+            let signature = p.file_name()?.to_string_lossy().into_owned();
+            // hyprland.lock holds the PID and the Wayland socket name
+            let lock = fs::read_to_string(p.join("hyprland.lock")).unwrap_or_default();
+            let mut lock = lock.lines().map(str::trim);
+            let pid = lock.next().and_then(|l| l.parse().ok());
+            let wayland_display = lock.next().filter(|l| !l.is_empty()).map(String::from);
+            Some(HyprlandInstance {
+                signature,
+                pid,
+                wayland_display,
+            })
+        })
+        .collect();
+    instances.sort_by(|a, b| a.signature.cmp(&b.signature));
+    instances
+}
+
+/// Run a dispatcher, as a bind would
+pub fn dispatch(
+    instance: Option<&str>,
+    dispatcher: &str,
+    arg: &str,
+) -> Result<(), SourceErrorReason> {
+    let reply = run_hyprctl(instance, &["dispatch", dispatcher, arg])?;
+    // hyprctl exits with 0 even when the dispatcher fails
+    if reply.trim() == "ok" {
+        Ok(())
+    } else {
+        Err(SourceErrorReason::CommandFailed(format!(
+            "hyprctl dispatch {dispatcher} {arg}: {}",
+            reply.trim()
+        )))
+    }
+}
+
+/// Ask a Hyprland instance (the current one for `None`) to reload its config
+pub fn reload_hyprland(instance: Option<&str>) -> Result<(), SourceErrorReason> {
+    run_hyprctl(instance, &["reload"]).map(|_| ())
+}
+
+/// Read keybindings saved as JSON (e.g. by `--json` or a history snapshot)
+pub fn read_keybindings_json(path: &Path) -> io::Result<KeyBindings> {
+    let data = fs::read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Path of the main Hyprland config file
+pub fn hyprland_config_path() -> PathBuf {
+    let config_home = std::env::var("XDG_CONFIG_HOME").map_or_else(
+        |_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+            PathBuf::from(home).join(".config")
+        },
+        PathBuf::from,
+    );
+    config_home.join("hypr").join("hyprland.conf")
+}
+
+/// Path of the Sway config, or the i3 one when only that exists
+pub fn sway_config_path() -> PathBuf {
+    let home = PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".into()));
+    let config_home =
+        std::env::var("XDG_CONFIG_HOME").map_or_else(|_| home.join(".config"), PathBuf::from);
+    let candidates = [
+        config_home.join("sway").join("config"),
+        home.join(".sway").join("config"),
+        config_home.join("i3").join("config"),
+        home.join(".i3").join("config"),
+    ];
+    candidates
+        .iter()
+        .find(|p| p.exists())
+        .unwrap_or(&candidates[0])
+        .clone()
+}
+
+/// Read a Hyprland config, inlining sourced files at their `source =` line
+pub fn read_config_lines(path: &Path) -> io::Result<Vec<ConfigLine>> {
+    read_config_with(path, parse_source_directive)
+}
+
+/// Read a Sway/i3 config, inlining included files at their `include` line
+pub fn read_sway_config_lines(path: &Path) -> io::Result<Vec<ConfigLine>> {
+    read_config_with(path, parse_include_directive)
+}
+
+/// Read a config, following the files named by `directive` lines
+fn read_config_with(
+    path: &Path,
+    directive: fn(&str) -> Option<String>,
+) -> io::Result<Vec<ConfigLine>> {
+    let mut lines = Vec::new();
+    let mut visited = Vec::new();
+    read_config_file(path, 0, directive, &mut visited, &mut lines)?;
+    Ok(lines)
+}
+
+fn read_config_file(
+    path: &Path,
+    depth: usize,
+    directive: fn(&str) -> Option<String>,
+    visited: &mut Vec<PathBuf>,
+    lines: &mut Vec<ConfigLine>,
+) -> io::Result<()> {
+    let canonical = fs::canonicalize(path)?;
+    if depth > MAX_SOURCE_DEPTH || visited.contains(&canonical) {
+        return Ok(());
+    }
+    visited.push(canonical);
+
+    let contents = fs::read_to_string(path)?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    for (i, text) in contents.lines().enumerate() {
+        lines.push(ConfigLine {
+            path: path.to_path_buf(),
+            number: i + 1,
+            text: text.to_string(),
+        });
+
+        if let Some(source) = directive(text) {
+            for sourced in resolve_source_path(&source, base_dir) {
+                // Missing or unreadable sourced files are skipped like Hyprland does
+                let _ = read_config_file(&sourced, depth + 1, directive, visited, lines);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a `source =` value to files, expanding `~` and a `*` in the file name
+fn resolve_source_path(source: &str, base_dir: &Path) -> Vec<PathBuf> {
+    let expanded = source.strip_prefix("~/").map_or_else(
+        || PathBuf::from(source),
+        |rest| {
+            std::env::var("HOME").map_or_else(
+                |_| PathBuf::from(source),
+                |home| PathBuf::from(home).join(rest),
+            )
+        },
+    );
+    let path = if expanded.is_absolute() {
+        expanded
+    } else {
+        base_dir.join(expanded)
+    };
+
+    let Some(pattern) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+        return Vec::new();
+    };
+    let Some((prefix, suffix)) = pattern.split_once('*') else {
+        return vec![path];
+    };
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut matches: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name().is_some_and(|n| {
+                        let n = n.to_string_lossy();
+                        n.len() >= prefix.len() + suffix.len()
+                            && n.starts_with(prefix)
+                            && n.ends_with(suffix)
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    matches.sort();
+    matches
+}
+
+/// Copy `contents` of the config at `path` into a new file in `backup_dir`
+///
+/// Backups are named after the file and the time down to the nanosecond, with a counter
+/// added when that name is taken, and never replace an existing backup.
+fn write_backup(path: &Path, contents: &str, backup_dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(backup_dir)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let file_name = path
+        .file_name()
+        .map_or_else(|| "config".into(), |n| n.to_string_lossy());
+    let stem = format!("{file_name}.{}.{:09}", now.as_secs(), now.subsec_nanos());
+    for n in 0..1000 {
+        let backup = if n == 0 {
+            backup_dir.join(format!("{stem}.bak"))
+        } else {
+            backup_dir.join(format!("{stem}.{n}.bak"))
+        };
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&backup)
+        {
+            Ok(mut file) => {
+                file.write_all(contents.as_bytes())?;
+                file.sync_all()?;
+                return Ok(backup);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::other(format!(
+        "no free backup name for {}",
+        path.display()
+    )))
+}
+
+/// Replace the file at `path` (or the one it links to) with `contents`
+///
+/// The contents go to a temporary file in the same directory that is then renamed over
+/// the original, so a crash leaves either the old or the new file, never a truncated one.
+fn write_replacing(path: &Path, contents: &str) -> io::Result<()> {
+    let target = fs::canonicalize(path)?;
+    let dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = target
+        .file_name()
+        .map_or_else(|| "config".into(), |n| n.to_string_lossy());
+    let temp = dir.join(format!(".{file_name}.hyprbind-{}", std::process::id()));
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temp)?;
+    let written = file
+        .write_all(contents.as_bytes())
+        .and_then(|()| file.set_permissions(fs::metadata(&target)?.permissions()))
+        .and_then(|()| file.sync_all())
+        .and_then(|()| fs::rename(&temp, &target));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    written
+}
+
+/// Replace a single config line after backing up its file into `backup_dir`
+///
+/// Fails without writing if the line on disk no longer matches `line.text`.
+/// Returns the path of the backup copy.
+pub fn replace_config_line(
+    line: &ConfigLine,
+    new_text: &str,
+    backup_dir: &Path,
+) -> io::Result<PathBuf> {
+    let contents = fs::read_to_string(&line.path)?;
+    let mut lines: Vec<&str> = contents.split('\n').collect();
+
+    let index = line.number - 1;
+    let current = lines
+        .get(index)
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .ok_or_else(|| io::Error::other("line no longer exists"))?;
+    if current != line.text {
+        return Err(io::Error::other(format!(
+            "{}:{} changed on disk",
+            line.path.display(),
+            line.number
+        )));
+    }
+    let line_ending = if lines[index].ends_with('\r') {
+        "\r"
+    } else {
+        ""
+    };
+    let replacement = format!("{new_text}{line_ending}");
+    lines[index] = &replacement;
+
+    let backup = write_backup(&line.path, &contents, backup_dir)?;
+    write_replacing(&line.path, &lines.join("\n"))?;
+    Ok(backup)
+}
@@ -0,0 +1,205 @@
+use super::models::{BindFlags, ConfigLine, KeyBindEntry, ParseWarning, ParsedBinds};
+use super::parser::{expand_variables, modifiers_to_modmask, modmask_to_string};
+
+/// A `{ ... }` block open at the current line
+enum Block {
+    /// `mode "name" {`
+    Mode(String),
+    /// `bindsym [flags] {`, where every line is `<combo> <command>`
+    Binds { flags: BindFlags, code: bool },
+    /// Any other block, e.g. `bar { ... }` or `input * { ... }`
+    Other,
+}
+
+/// Extract the path of an `include <path>` line
+pub fn parse_include_directive(line: &str) -> Option<String> {
+    let path = line.trim().strip_prefix("include")?;
+    if !path.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let path = unquote(path.trim());
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Parse Sway/i3 `bindsym`/`bindcode` lines into keybindings
+///
+/// `mode` blocks become submaps and `set $var` values are expanded.
+/// `lines` is expected with included files inlined at their `include` line.
+/// Binds inside other blocks (e.g. `bar { }` mouse bindings) are ignored.
+pub fn parse_sway_lines(lines: &[ConfigLine]) -> ParsedBinds {
+    let mut parsed = ParsedBinds::default();
+    let mut variables: Vec<(String, String)> = Vec::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut pending: Option<(&ConfigLine, String)> = None;
+
+    for line in lines {
+        // A trailing backslash continues the statement on the next line
+        let (first, text) = match pending.take() {
+            Some((first, mut text)) => {
+                text.push(' ');
+                text.push_str(line.text.trim());
+                (first, text)
+            }
+            None => (line, line.text.trim().to_string()),
+        };
+        if let Some(head) = text.strip_suffix('\\') {
+            pending = Some((first, head.trim_end().to_string()));
+            continue;
+        }
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        let warning = |reason: String| ParseWarning {
+            path: (!first.path.as_os_str().is_empty()).then(|| first.path.clone()),
+            line: first.number,
+            reason,
+            raw: text.clone(),
+        };
+
+        if text == "}" {
+            blocks.pop();
+            continue;
+        }
+        let text = expand_sway_variables(&text, &variables);
+        let submap = current_mode(&blocks);
+
+        if let Some(Block::Binds { flags, code }) = blocks.last() {
+            match parse_combo_and_command(&text, *flags, *code, &submap) {
+                Ok(entry) => parsed.keybindings.add_entry(entry),
+                Err(reason) => parsed.warnings.push(warning(reason)),
+            }
+            continue;
+        }
+
+        if blocks.iter().any(|b| matches!(b, Block::Other)) {
+            if text.ends_with('{') {
+                blocks.push(Block::Other);
+            }
+            continue;
+        }
+
+        let keyword = text.split_whitespace().next().unwrap_or_default();
+        match keyword {
+            "set" => {
+                let definition = text["set".len()..].trim_start();
+                let Some((name, value)) = definition
+                    .strip_prefix('$')
+                    .map(|d| d.split_once(char::is_whitespace).unwrap_or((d, "")))
+                else {
+                    let reason = "expected `set $name value`".to_string();
+                    parsed.warnings.push(warning(reason));
+                    continue;
+                };
+                variables.retain(|(n, _)| n != name);
+                variables.push((name.to_string(), value.trim().to_string()));
+                variables.sort_by_key(|(n, _)| std::cmp::Reverse(n.len()));
+            }
+            "mode" if text.ends_with('{') => {
+                let name = text["mode".len()..text.len() - 1]
+                    .split_whitespace()
+                    .filter(|w| !w.starts_with("--"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                blocks.push(Block::Mode(unquote(&name).to_string()));
+            }
+            "bindsym" | "bindcode" => {
+                let code = keyword == "bindcode";
+                let rest = text[keyword.len()..].trim();
+                let (flags, rest) = parse_bind_options(rest);
+                if rest == "{" {
+                    blocks.push(Block::Binds { flags, code });
+                    continue;
+                }
+                match parse_combo_and_command(rest, flags, code, &submap) {
+                    Ok(entry) => parsed.keybindings.add_entry(entry),
+                    Err(reason) => parsed.warnings.push(warning(reason)),
+                }
+            }
+            _ if text.ends_with('{') => blocks.push(Block::Other),
+            _ => {}
+        }
+    }
+
+    parsed
+}
+
+fn current_mode(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .rev()
+        .find_map(|b| match b {
+            Block::Mode(name) if name != "default" => Some(name.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Expand `$var` like Sway, longest names first so `$mod` does not eat `$modAlt`
+fn expand_sway_variables(text: &str, variables: &[(String, String)]) -> String {
+    // `set` lines only expand their value
+    if let Some(rest) = text.strip_prefix("set ")
+        && let Some((name, value)) = rest.trim_start().split_once(char::is_whitespace)
+    {
+        return format!("set {name} {}", expand_variables(value.trim(), variables));
+    }
+    expand_variables(text, variables)
+}
+
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+/// Split leading `--option`s off a bind, keeping the ones the model has flags for
+fn parse_bind_options(mut rest: &str) -> (BindFlags, &str) {
+    let mut flags = BindFlags::default();
+    while let Some(option) = rest.strip_prefix("--") {
+        let (name, tail) = option
+            .split_once(char::is_whitespace)
+            .unwrap_or((option, ""));
+        match name {
+            "release" => flags.release = true,
+            "locked" => flags.locked = true,
+            // --to-code, --no-repeat, --whole-window, --input-device=... etc.
+            _ => {}
+        }
+        rest = tail.trim_start();
+    }
+    (flags, rest)
+}
+
+/// Parse `<combo> <command>`, e.g. `Mod4+Shift+q kill`
+fn parse_combo_and_command(
+    text: &str,
+    flags: BindFlags,
+    code: bool,
+    submap: &str,
+) -> Result<KeyBindEntry, String> {
+    let (combo, command) = text
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| "missing command".to_string())?;
+    let command = command.trim();
+    let mut parts: Vec<&str> = combo.split('+').collect();
+    let key = parts.pop().filter(|k| !k.is_empty()).ok_or("missing key")?;
+    let modifiers = modmask_to_string(modifiers_to_modmask(&parts.join(" ")));
+    let key = if code {
+        key.parse::<u32>()
+            .map(|n| format!("code:{n}"))
+            .map_err(|_| format!("invalid keycode '{key}'"))?
+    } else {
+        key.to_string()
+    };
+    let (dispatcher, arg) = command
+        .split_once(char::is_whitespace)
+        .map_or((command, ""), |(d, a)| (d, a.trim()));
+
+    let mut entry = KeyBindEntry::new(modifiers, key, command.to_string(), String::new());
+    entry.submap = submap.to_string();
+    entry.dispatcher = dispatcher.to_string();
+    entry.arg = arg.to_string();
+    entry.flags = flags;
+    Ok(entry)
+}
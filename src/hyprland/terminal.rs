@@ -0,0 +1,199 @@
+use super::models::{KeyBindEntry, KeyBindings};
+use crate::ui::styling::icons::{get_icon, get_key_icon};
+use std::fmt::Write;
+use unicode_width::UnicodeWidthStr;
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const CYAN: &str = "\x1b[36m";
+const YELLOW: &str = "\x1b[1;33m";
+const MAGENTA: &str = "\x1b[1;35m";
+const RESET: &str = "\x1b[0m";
+
+/// Space between columns
+const GAP: &str = "  ";
+/// Narrowest a shrunk description or command column gets
+const MIN_WIDTH: usize = 12;
+
+/// How `KeyBindings::to_table` lays out the table
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy)]
+pub struct TableStyle {
+    /// Terminal width; `None` never truncates
+    pub width: Option<usize>,
+    /// Use ANSI colours
+    pub color: bool,
+    /// Show modifiers and keys as icons instead of their names
+    pub icons: bool,
+    /// Add a category column
+    pub category: bool,
+}
+
+/// A table cell: plain text for measuring, and the same text with colours
+struct Cell {
+    plain: String,
+    painted: String,
+}
+
+impl Cell {
+    fn new(text: &str, code: Option<&str>, color: bool) -> Self {
+        Self {
+            plain: text.to_string(),
+            painted: paint(code, text, color),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.plain.width()
+    }
+
+    /// Pad to `width`, cutting off with `…` when longer; truncated cells lose inner colours
+    fn fit(&self, width: usize, code: Option<&str>, color: bool) -> String {
+        let len = self.width();
+        if len <= width {
+            return format!("{}{}", self.painted, " ".repeat(width - len));
+        }
+        let mut cut = String::new();
+        for c in self.plain.chars() {
+            if cut.width() + c.to_string().width() >= width {
+                break;
+            }
+            cut.push(c);
+        }
+        cut.push('…');
+        let pad = width.saturating_sub(cut.width());
+        format!("{}{}", paint(code, &cut, color), " ".repeat(pad))
+    }
+}
+
+fn paint(code: Option<&str>, text: &str, color: bool) -> String {
+    match code {
+        Some(code) if color && !text.is_empty() => format!("{code}{text}{RESET}"),
+        _ => text.to_string(),
+    }
+}
+
+fn key_cell(entry: &KeyBindEntry, style: &TableStyle) -> Cell {
+    let label = |name: &str| {
+        if style.icons {
+            get_icon(name).trim().to_string()
+        } else {
+            name.to_string()
+        }
+    };
+    let mut plain = Vec::new();
+    let mut painted = Vec::new();
+    for modifier in entry.modifiers.split('+').filter(|m| !m.trim().is_empty()) {
+        let text = label(modifier.trim());
+        painted.push(paint(Some(CYAN), &text, style.color));
+        plain.push(text);
+    }
+    let key = entry.normalized_key();
+    let key = if style.icons {
+        get_key_icon(key).trim().to_string()
+    } else {
+        key.label()
+    };
+    painted.push(paint(Some(YELLOW), &key, style.color));
+    plain.push(key);
+    Cell {
+        plain: plain.join(" + "),
+        painted: painted.join(" + "),
+    }
+}
+
+/// Widths for the description and command columns sharing `room`
+const fn share(room: usize, description: usize, command: usize) -> (usize, usize) {
+    if description + command <= room {
+        return (description, command);
+    }
+    let half = room / 2;
+    if description <= half {
+        (description, room - description)
+    } else if command <= half {
+        (room - command, command)
+    } else {
+        (half, room - half)
+    }
+}
+
+impl KeyBindings {
+    /// Render as aligned columns for reading in a terminal
+    ///
+    /// Binds are grouped by submap, global binds first, each group under a heading.
+    /// With a `width`, the description and command columns shrink to fit it.
+    pub fn to_table(&self, style: &TableStyle) -> String {
+        let color = style.color;
+        let mut headers = vec!["KEYS", "DESCRIPTION", "COMMAND"];
+        if style.category {
+            headers.push("CATEGORY");
+        }
+        let rows: Vec<(&str, Vec<Cell>)> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut cells = vec![
+                    key_cell(entry, style),
+                    Cell::new(&entry.description, None, color),
+                    Cell::new(&entry.command, Some(DIM), color),
+                ];
+                if style.category {
+                    cells.push(Cell::new(&entry.category, None, color));
+                }
+                (entry.submap.as_str(), cells)
+            })
+            .collect();
+
+        let mut widths: Vec<usize> = headers.iter().map(|h| h.width()).collect();
+        for (_, cells) in &rows {
+            for (width, cell) in widths.iter_mut().zip(cells) {
+                *width = (*width).max(cell.width());
+            }
+        }
+        if let Some(total) = style.width {
+            let gaps = GAP.len() * (widths.len() - 1);
+            widths[0] = widths[0].min(total / 3);
+            let fixed = widths[0] + widths.get(3).copied().unwrap_or_default() + gaps;
+            let room = total.saturating_sub(fixed).max(MIN_WIDTH * 2);
+            (widths[1], widths[2]) = share(room, widths[1], widths[2]);
+        }
+        let codes = [Some(YELLOW), None, Some(DIM), None];
+
+        let mut submaps: Vec<&str> = Vec::new();
+        for (submap, _) in &rows {
+            if !submaps.contains(submap) {
+                submaps.push(submap);
+            }
+        }
+        submaps.sort_by_key(|s| !s.is_empty());
+
+        let mut out = String::new();
+        for (i, submap) in submaps.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let heading = if submap.is_empty() {
+                "Global".to_string()
+            } else {
+                format!("Submap: {submap}")
+            };
+            let _ = writeln!(out, "{}", paint(Some(MAGENTA), &heading, color));
+            let header: Vec<String> = headers
+                .iter()
+                .zip(&widths)
+                .map(|(h, w)| Cell::new(h, Some(BOLD), color).fit(*w, Some(BOLD), color))
+                .collect();
+            let _ = writeln!(out, "{}", header.join(GAP).trim_end());
+            for (_, cells) in rows.iter().filter(|(s, _)| s == submap) {
+                let line: Vec<String> = cells
+                    .iter()
+                    .zip(&widths)
+                    .zip(codes)
+                    .map(|((cell, w), code)| cell.fit(*w, code, color))
+                    .collect();
+                let _ = writeln!(out, "{}", line.join(GAP).trim_end());
+            }
+        }
+        out
+    }
+}
@@ -10,27 +10,73 @@ mod tests;
 use app::KeybindsApp;
 use cli::CliAction;
 use eframe::egui;
-use ui::styling::fonts::setup_custom_fonts;
+use hyprland::SourceChain;
 
 fn main() -> Result<(), eframe::Error> {
+    ui::styling::icons::load_icon_map(config::load().unwrap_or_default().icon_set);
     match cli::parse_args() {
         CliAction::WriteDefaultCss { force } => {
             cli::handle_write_css(force);
             Ok(())
         }
-        CliAction::OutputJson => {
-            cli::handle_json_output();
+        CliAction::CheckTheme { path } => {
+            cli::handle_theme_check(path.as_deref());
             Ok(())
         }
-        CliAction::OutputDmenu => {
-            cli::handle_dmenu_output();
+        CliAction::ImportTheme {
+            source,
+            name,
+            select,
+        } => {
+            cli::handle_theme_import(&source, name.as_deref(), select);
             Ok(())
         }
-        CliAction::RunGui => run_gui(),
+        CliAction::Output {
+            sources,
+            output,
+            query,
+            grouped,
+        } => {
+            cli::handle_output(&sources, &output, query.as_deref(), grouped);
+            Ok(())
+        }
+        CliAction::Show {
+            sources,
+            combo,
+            submap,
+        } => {
+            cli::handle_show(&sources, &combo, submap.as_deref());
+            Ok(())
+        }
+        CliAction::Completions { shell } => {
+            cli::handle_completions(shell);
+            Ok(())
+        }
+        CliAction::Rofi {
+            sources,
+            launcher,
+            grouped,
+        } => {
+            cli::handle_rofi(&sources, &launcher, grouped);
+            Ok(())
+        }
+        CliAction::Man => {
+            cli::handle_man();
+            Ok(())
+        }
+        CliAction::Check { sources } => {
+            cli::handle_check(&sources);
+            Ok(())
+        }
+        CliAction::Diff { old, new, format } => {
+            cli::handle_diff(&old, &new, format);
+            Ok(())
+        }
+        CliAction::RunGui { sources } => run_gui(cli::bind_sources(&sources)),
     }
 }
 
-fn run_gui() -> Result<(), eframe::Error> {
+fn run_gui(sources: SourceChain) -> Result<(), eframe::Error> {
     let icon_data = load_icon();
 
     let options = eframe::NativeOptions {
@@ -45,9 +91,11 @@ fn run_gui() -> Result<(), eframe::Error> {
         "HyprBind",
         options,
         Box::new(|cc| {
-            setup_custom_fonts(&cc.egui_ctx);
+            // The app applies its theme only when it changes; keep egui from swapping in
+            // its other style when the desktop switches between light and dark
+            cc.egui_ctx.set_theme(egui::ThemePreference::Dark);
             ui::styling::css::apply_default_if_exists(&cc.egui_ctx);
-            Ok(Box::new(KeybindsApp::new()))
+            Ok(Box::new(KeybindsApp::new(&cc.egui_ctx, sources)))
         }),
     )
 }
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod annotations_tests {
+    use crate::hyprland::parser::parse_binds_output_with_warnings;
+    use crate::hyprland::{Annotation, Annotations, KeyBindEntry, KeyBindings, SearchOptions};
+
+    const SAMPLE: &str = r"bind
+	modmask: 65
+	submap:
+	key: Q
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: killactive
+	arg:
+
+bindd
+	modmask: 64
+	submap:
+	key: Return
+	keycode: 0
+	catchall: false
+	description: Terminal
+	dispatcher: exec
+	arg: kitty";
+
+    fn annotation(entry: &KeyBindEntry) -> Annotation {
+        Annotation {
+            bind: entry.bind_id(),
+            description: "Close window".to_string(),
+            notes: "Asks nothing".to_string(),
+            tags: vec!["window".to_string()],
+        }
+    }
+
+    /// Verifies bind identity ignores description and key case
+    #[test]
+    fn test_bind_id_stability() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let bind = &kb.entries[1];
+        let mut other = KeyBindEntry::new(
+            bind.modifiers.clone(),
+            "RETURN".to_string(),
+            bind.command.clone(),
+            "Something else".to_string(),
+        );
+        other.dispatcher.clone_from(&bind.dispatcher);
+        other.arg.clone_from(&bind.arg);
+
+        assert_eq!(kb.entries[1].bind_id(), other.bind_id());
+        assert_eq!(kb.entries[0].bind_id().modmask, 65);
+        assert_ne!(kb.entries[0].bind_id(), kb.entries[1].bind_id());
+    }
+
+    /// Tests merging descriptions, notes and tags into entries
+    #[test]
+    fn test_apply_annotations() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let mut annotations = Annotations::default();
+        annotations.set(annotation(&kb.entries[0]));
+
+        let merged = annotations.apply(&kb);
+        assert_eq!(merged.entries[0].description, "Close window");
+        assert_eq!(merged.entries[0].notes, "Asks nothing");
+        assert_eq!(merged.entries[0].tags, vec!["window".to_string()]);
+        assert_eq!(merged.entries[1].description, "Terminal");
+        assert_eq!(kb.entries[0].description, "");
+    }
+
+    /// Ensures an empty custom description keeps the bind's own description
+    #[test]
+    fn test_apply_keeps_own_description() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let mut annotations = Annotations::default();
+        annotations.set(Annotation {
+            bind: kb.entries[1].bind_id(),
+            notes: "Main terminal".to_string(),
+            ..Annotation::default()
+        });
+
+        let merged = annotations.apply(&kb);
+        assert_eq!(merged.entries[1].description, "Terminal");
+        assert_eq!(merged.entries[1].notes, "Main terminal");
+    }
+
+    /// Verifies setting an empty annotation removes it
+    #[test]
+    fn test_set_empty_removes() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let mut annotations = Annotations::default();
+        annotations.set(annotation(&kb.entries[0]));
+        annotations.set(Annotation {
+            bind: kb.entries[0].bind_id(),
+            ..Annotation::default()
+        });
+
+        assert_eq!(annotations.annotations, Vec::new());
+    }
+
+    /// Detects annotations whose bind disappeared after a reload
+    #[test]
+    fn test_orphans() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let mut annotations = Annotations::default();
+        annotations.set(annotation(&kb.entries[0]));
+        annotations.set(annotation(&kb.entries[1]));
+
+        let reloaded = KeyBindings {
+            entries: vec![kb.entries[1].clone()],
+        };
+        let orphans = annotations.orphans(&reloaded);
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].bind, kb.entries[0].bind_id());
+    }
+
+    /// Validates the annotation file format survives a serialization roundtrip
+    #[test]
+    fn test_annotations_roundtrip() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let mut annotations = Annotations::default();
+        annotations.set(annotation(&kb.entries[0]));
+
+        let json = serde_json::to_string_pretty(&annotations).unwrap();
+        let loaded: Annotations = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.annotations, annotations.annotations);
+    }
+
+    /// Checks notes and tags are searchable
+    #[test]
+    fn test_search_annotations() {
+        let kb = parse_binds_output_with_warnings(SAMPLE).keybindings;
+        let mut annotations = Annotations::default();
+        annotations.set(annotation(&kb.entries[0]));
+        let merged = annotations.apply(&kb);
+
+        let options = SearchOptions::default();
+        assert!(merged.entries[0].matches("asks", &options));
+        assert!(merged.entries[0].matches("WINDOW", &options));
+
+        let without = SearchOptions {
+            annotations: false,
+            description: false,
+            ..SearchOptions::default()
+        };
+        assert!(!merged.entries[0].matches("asks", &without));
+    }
+}
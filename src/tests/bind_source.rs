@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod bind_source_tests {
+    use crate::hyprland::bind_source::{
+        BindSource, ConfigSource, FileSource, HyprctlSource, SocketSource, SourceChain, SourceKind,
+        SwaySource,
+    };
+    use crate::hyprland::error::{LoadError, SourceError, SourceErrorReason};
+    use crate::hyprland::models::ParsedBinds;
+    use crate::hyprland::parser::parse_hyprctl_json;
+    use crate::hyprland::{KeyBindEntry, KeyBindings, hyprland_config_path};
+    use std::fs;
+    use tempfile::TempDir;
+
+    const HYPRCTL_JSON: &str = r#"[
+        {
+            "locked": false, "mouse": false, "release": false, "repeat": true,
+            "longPress": false, "non_consuming": false, "has_description": true,
+            "modmask": 64, "submap": "", "key": "XF86AudioRaiseVolume",
+            "keycode": 0, "catch_all": false, "description": "Volume up",
+            "dispatcher": "exec", "arg": "wpctl set-volume @DEFAULT_SINK@ 5%+"
+        },
+        {
+            "modmask": 65, "submap": "resize", "key": "l",
+            "dispatcher": "resizeactive", "arg": "10 0"
+        }
+    ]"#;
+
+    /// Test source returning a fixed result
+    struct StubSource {
+        kind: SourceKind,
+        live: bool,
+        fail: bool,
+    }
+
+    impl BindSource for StubSource {
+        fn kind(&self) -> SourceKind {
+            self.kind
+        }
+
+        fn is_live(&self) -> bool {
+            self.live
+        }
+
+        fn load(&self) -> Result<ParsedBinds, SourceErrorReason> {
+            if self.fail {
+                return Err(SourceErrorReason::Unavailable(format!(
+                    "{} is down",
+                    self.kind
+                )));
+            }
+            Ok(KeyBindings {
+                entries: vec![KeyBindEntry::new(
+                    "SUPER".to_string(),
+                    "Q".to_string(),
+                    self.kind.to_string(),
+                    String::new(),
+                )],
+            }
+            .into())
+        }
+    }
+
+    fn stub(kind: SourceKind, live: bool, fail: bool) -> Box<dyn BindSource> {
+        Box::new(StubSource { kind, live, fail })
+    }
+
+    /// Verifies `hyprctl binds -j` output is converted to entries
+    #[test]
+    fn test_parse_hyprctl_json() {
+        let kb = parse_hyprctl_json(HYPRCTL_JSON).unwrap();
+        assert_eq!(kb.entries.len(), 2);
+
+        let volume = &kb.entries[0];
+        assert_eq!(volume.modifiers, "SUPER");
+        assert_eq!(volume.key(), "XF86AudioRaiseVolume");
+        assert_eq!(volume.description, "Volume up");
+        assert_eq!(volume.dispatcher, "exec");
+        assert!(volume.flags.repeat);
+
+        let resize = &kb.entries[1];
+        assert_eq!(resize.modifiers, "SUPER+SHIFT");
+        assert_eq!(resize.submap, "resize");
+        assert_eq!(resize.arg, "10 0");
+        assert!(parse_hyprctl_json("[{").is_err());
+    }
+
+    /// Ensures the chain falls back in order and reports skipped sources
+    #[test]
+    fn test_chain_fallback() {
+        let chain = SourceChain::new(vec![
+            stub(SourceKind::Hyprctl, true, true),
+            stub(SourceKind::Socket, true, true),
+            stub(SourceKind::Config, false, false),
+            stub(SourceKind::File, false, false),
+        ]);
+        let loaded = chain.load().unwrap();
+
+        assert_eq!(loaded.source, SourceKind::Config);
+        assert!(!loaded.live);
+        assert_eq!(loaded.keybindings.entries[0].command, "config");
+        let skipped: Vec<SourceKind> = loaded.skipped.iter().map(|e| e.source).collect();
+        assert_eq!(skipped, vec![SourceKind::Hyprctl, SourceKind::Socket]);
+    }
+
+    /// Checks a chain where every source fails lists all failures in order
+    #[test]
+    fn test_chain_all_fail() {
+        let chain = SourceChain::new(vec![
+            stub(SourceKind::Hyprctl, true, true),
+            stub(SourceKind::Config, false, true),
+        ]);
+        let err = chain.load().unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "hyprctl: not available: hyprctl is down; config: not available: config is down"
+        );
+        assert_eq!(
+            SourceChain::new(Vec::new()).load().unwrap_err().to_string(),
+            LoadError::default().to_string()
+        );
+    }
+
+    /// Validates source names on the command line and in config
+    #[test]
+    fn test_source_kind_names() {
+        for kind in SourceKind::ALL {
+            assert_eq!(kind.name().parse::<SourceKind>().unwrap(), kind);
+            let json = serde_json::to_string(&kind).unwrap();
+            assert_eq!(json, format!("\"{kind}\""));
+        }
+        assert_eq!(
+            "Hyprctl-JSON".parse::<SourceKind>().unwrap(),
+            SourceKind::HyprctlJson
+        );
+        assert!("ipc".parse::<SourceKind>().unwrap_err().contains("socket"));
+    }
+
+    /// Tests the config source reads binds and uses comments as descriptions
+    #[test]
+    fn test_config_source() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("hyprland.conf");
+        fs::write(
+            &path,
+            "bind = SUPER, F, exec, thunar # Files\nbindd = SUPER, Q, Close, killactive\n",
+        )
+        .unwrap();
+
+        let source = ConfigSource { path };
+        assert!(!source.is_live());
+        let kb = source.load().unwrap().keybindings;
+        assert_eq!(kb.entries.len(), 2);
+        assert_eq!(kb.entries[0].description, "Files");
+        assert_eq!(kb.entries[1].description, "Close");
+
+        let missing = ConfigSource {
+            path: dir.path().join("missing.conf"),
+        };
+        let error = SourceError {
+            source: missing.kind(),
+            reason: missing.load().unwrap_err(),
+        };
+        assert!(matches!(error.reason, SourceErrorReason::Unavailable(_)));
+        assert!(error.to_string().starts_with("config: not available: "));
+    }
+
+    /// Ensures only binds of the current instance or a Hyprland config can be edited, in the
+    /// file they were loaded from
+    #[test]
+    fn test_config_paths() {
+        let mut hyprctl = HyprctlSource::default();
+        assert_eq!(hyprctl.config_path(), Some(hyprland_config_path()));
+        hyprctl.set_instance(Some("other_1700000000_1"));
+        assert_eq!(hyprctl.config_path(), None);
+
+        let mut socket = SocketSource::default();
+        socket.set_instance(Some("other_1700000000_1"));
+        assert_eq!(socket.config_path(), None);
+
+        let path = std::path::PathBuf::from("/tmp/other/hyprland.conf");
+        let config = ConfigSource { path: path.clone() };
+        assert_eq!(config.config_path(), Some(path.clone()));
+        assert_eq!(SwaySource { path }.config_path(), None);
+        assert_eq!(FileSource::new(None).unwrap().config_path(), None);
+    }
+}
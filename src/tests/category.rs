@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod category_tests {
+    use crate::hyprland::category::OTHER_CATEGORY;
+    use crate::hyprland::launcher::{Launcher, LauncherProfile, LauncherSettings};
+    use crate::hyprland::{Categorizer, CategoryRule, KeyBindEntry, KeyBindings};
+
+    fn entry(key: &str, command: &str) -> KeyBindEntry {
+        KeyBindEntry::new(
+            "SUPER".to_string(),
+            key.to_string(),
+            command.to_string(),
+            String::new(),
+        )
+    }
+
+    fn sample() -> KeyBindings {
+        KeyBindings {
+            entries: vec![
+                entry("Return", "exec kitty"),
+                entry("1", "workspace 1"),
+                entry("Q", "killactive"),
+                entry(
+                    "XF86AudioRaiseVolume",
+                    "exec wpctl set-volume @DEFAULT_SINK@ 5%+",
+                ),
+                entry("2", "movetoworkspace 2"),
+                entry("F1", "fakedispatcher"),
+            ],
+        }
+    }
+
+    /// Tests the built-in rules for common dispatchers and tools
+    #[test]
+    fn test_builtin_categories() {
+        let categorizer = Categorizer::default();
+        let cases = [
+            (entry("Return", "exec kitty"), "Launchers"),
+            (entry("1", "workspace 1"), "Workspaces"),
+            (entry("S", "movetoworkspacesilent special"), "Workspaces"),
+            (entry("left", "movefocus l"), "Windows"),
+            (entry("L", "resizeactive 10 0"), "Windows"),
+            (entry("mouse:272", "mouse movewindow"), "Windows"),
+            (entry("P", "exec playerctl play-pause"), "Media"),
+            (entry("XF86AudioMute", "exec ~/bin/mute.sh"), "Media"),
+            (entry("Print", "exec grim -g \"$(slurp)\""), "Screenshots"),
+            (entry("M", "exit"), "System"),
+            (entry("Escape", "exec hyprlock"), "System"),
+            (entry("F1", "fakedispatcher"), OTHER_CATEGORY),
+        ];
+        for (entry, expected) in &cases {
+            assert_eq!(
+                categorizer.categorize(entry),
+                *expected,
+                "{}",
+                entry.command
+            );
+        }
+    }
+
+    /// Ensures user rules take precedence over the built-in ones
+    #[test]
+    fn test_user_rules_first() {
+        let categorizer = Categorizer::new(vec![CategoryRule {
+            category: "Games".to_string(),
+            dispatchers: vec!["EXEC".to_string()],
+            args: vec!["Steam".to_string()],
+            ..CategoryRule::default()
+        }]);
+
+        assert_eq!(categorizer.categorize(&entry("G", "exec steam")), "Games");
+        assert_eq!(
+            categorizer.categorize(&entry("T", "exec kitty")),
+            "Launchers"
+        );
+    }
+
+    /// Ensures rules without criteria or category never match
+    #[test]
+    fn test_empty_rules_ignored() {
+        let categorizer = Categorizer::new(vec![
+            CategoryRule {
+                category: "Everything".to_string(),
+                ..CategoryRule::default()
+            },
+            CategoryRule {
+                category: " ".to_string(),
+                dispatchers: vec!["exec".to_string()],
+                ..CategoryRule::default()
+            },
+        ]);
+
+        assert_eq!(
+            categorizer.categorize(&entry("T", "exec kitty")),
+            "Launchers"
+        );
+    }
+
+    /// Verifies grouping follows rule order and keeps order within groups
+    #[test]
+    fn test_group_order() {
+        let categorizer = Categorizer::default();
+        let mut kb = sample();
+        categorizer.apply(&mut kb);
+        categorizer.group(&mut kb.entries);
+
+        let order: Vec<(&str, &str)> = kb
+            .entries
+            .iter()
+            .map(|e| (e.category.as_str(), e.key()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("Media", "XF86AudioRaiseVolume"),
+                ("Workspaces", "1"),
+                ("Workspaces", "2"),
+                ("Windows", "Q"),
+                ("Launchers", "Return"),
+                (OTHER_CATEGORY, "F1"),
+            ]
+        );
+    }
+
+    /// Tests category grouping in the dmenu and hyprland.conf exports
+    #[test]
+    fn test_grouped_exports() {
+        let categorizer = Categorizer::default();
+        let mut kb = sample();
+        categorizer.apply(&mut kb);
+        categorizer.group(&mut kb.entries);
+
+        let mut profile = LauncherProfile::new(Launcher::Dmenu, &LauncherSettings::default());
+        assert!(!kb.to_launcher(&profile).contains("[Media]"));
+        profile.grouped = true;
+        let dmenu = kb.to_launcher(&profile);
+        assert!(dmenu.lines().next().unwrap().starts_with("[Media] "));
+
+        let conf = kb.to_hyprconf(None, true);
+        let headers: Vec<&str> = conf
+            .lines()
+            .filter(|l| l.starts_with("# ") && !l.starts_with("# Generated"))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                "# Media",
+                "# Workspaces",
+                "# Windows",
+                "# Launchers",
+                "# Other"
+            ]
+        );
+        assert!(conf.contains("# Workspaces\nbind = SUPER, 1, workspace, 1\nbind = SUPER, 2"));
+    }
+
+    /// Checks the category is exported to JSON and optional when reading it back
+    #[test]
+    fn test_category_json() {
+        let categorizer = Categorizer::default();
+        let mut kb = sample();
+        categorizer.apply(&mut kb);
+
+        let json = kb.to_json().unwrap();
+        assert!(json.contains("\"category\": \"Launchers\""));
+
+        let plain: KeyBindings = serde_json::from_str(
+            r#"{"entries":[{"modifiers":"SUPER","key":"Q","command":"killactive","description":""}]}"#,
+        )
+        .unwrap();
+        assert_eq!(plain.entries[0].category, "");
+    }
+}
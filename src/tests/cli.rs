@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod cli_tests {
+    use crate::cli::{CliAction, OutputFormat, action_from};
+    use crate::hyprland::SourceKind;
+    use crate::hyprland::models::{ConfigLine, KeyBindings};
+    use crate::hyprland::parser::{parse_combo, parse_config_lines};
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    fn action(args: &[&str]) -> CliAction {
+        let args = std::iter::once("hyprbind").chain(args.iter().copied());
+        action_from(crate::cli::Cli::try_parse_from(args).unwrap())
+    }
+
+    fn config_binds(text: &str) -> KeyBindings {
+        let lines: Vec<ConfigLine> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| ConfigLine {
+                path: PathBuf::new(),
+                number: i + 1,
+                text: line.to_string(),
+            })
+            .collect();
+        KeyBindings {
+            entries: parse_config_lines(&lines)
+                .into_iter()
+                .map(|bind| bind.entry)
+                .collect(),
+        }
+    }
+
+    /// Verifies the bare command runs the GUI with global source options
+    #[test]
+    fn test_bare_command_runs_gui() {
+        let CliAction::RunGui { sources } = action(&["--source", "sway,config"]) else {
+            panic!("expected the GUI");
+        };
+        assert_eq!(sources.source, vec![SourceKind::Sway, SourceKind::Config]);
+    }
+
+    /// Checks subcommands map to their actions, with global options after them
+    #[test]
+    fn test_subcommands() {
+        let CliAction::Output {
+            sources,
+            output,
+            query,
+            grouped,
+        } = action(&[
+            "search",
+            "firefox",
+            "--format",
+            "json",
+            "--group-by-category",
+            "--strict",
+        ])
+        else {
+            panic!("expected output");
+        };
+        assert_eq!(query.as_deref(), Some("firefox"));
+        assert!(matches!(output.format, OutputFormat::Json));
+        assert!(grouped && sources.strict);
+
+        assert!(matches!(
+            action(&["list"]),
+            CliAction::Output { query: None, output, .. } if matches!(output.format, OutputFormat::Table)
+        ));
+        assert!(matches!(
+            action(&["show", "super", "shift", "q", "--submap", "resize"]),
+            CliAction::Show { combo, submap, .. }
+                if combo == "super shift q" && submap.as_deref() == Some("resize")
+        ));
+        assert!(matches!(action(&["check"]), CliAction::Check { .. }));
+        assert!(matches!(
+            action(&["theme", "write-default", "--force"]),
+            CliAction::WriteDefaultCss { force: true }
+        ));
+        assert!(matches!(
+            action(&["theme", "check", "my.css"]),
+            CliAction::CheckTheme { path: Some(path) } if path.to_str() == Some("my.css")
+        ));
+        assert!(matches!(
+            action(&["theme", "check"]),
+            CliAction::CheckTheme { path: None }
+        ));
+        assert!(matches!(
+            action(&["theme", "import", "pywal", "--name", "wal", "--select"]),
+            CliAction::ImportTheme { source, name: Some(name), select: true }
+                if source == "pywal" && name == "wal"
+        ));
+        assert!(crate::cli::Cli::try_parse_from(["hyprbind", "export"]).is_err());
+    }
+
+    /// Ensures the old flags still work as aliases
+    #[test]
+    fn test_deprecated_flags() {
+        assert!(matches!(
+            action(&["--json"]),
+            CliAction::Output { output, .. } if matches!(output.format, OutputFormat::Json)
+        ));
+        assert!(matches!(
+            action(&["--format", "hyprconf", "--main-mod", "SUPER"]),
+            CliAction::Output { output, .. }
+                if matches!(output.format, OutputFormat::Hyprconf)
+                    && output.main_mod.as_deref() == Some("SUPER")
+        ));
+        assert!(matches!(
+            action(&["--write-default-css", "--force"]),
+            CliAction::WriteDefaultCss { force: true }
+        ));
+    }
+
+    /// Tests combos are matched against binds and duplicates are found
+    #[test]
+    fn test_combo_and_duplicates() {
+        let kb: KeyBindings = config_binds(
+            "bind = SUPER SHIFT, Q, killactive
+bind = SUPER, Q, exec, kitty
+bind = SUPER, q, exec, foot
+submap = resize
+bind = SUPER, Q, submap, reset",
+        );
+        let (modmask, key) = parse_combo("SUPER + SHIFT + q", &[]).unwrap();
+        let bound: Vec<&str> = kb
+            .entries
+            .iter()
+            .filter(|e| e.has_combo(modmask, &key))
+            .map(|e| e.command.as_str())
+            .collect();
+        assert_eq!(bound, vec!["killactive"]);
+        assert!(parse_combo("SUPER+", &[]).is_err());
+
+        let duplicates = kb.duplicate_combos();
+        assert_eq!(duplicates.len(), 1);
+        let commands: Vec<&str> = duplicates[0].iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["exec kitty", "exec foot"]);
+        assert_eq!(duplicates[0][0].combo_label(), "SUPER+Q");
+    }
+}
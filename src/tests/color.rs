@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod color_tests {
+    use crate::ui::styling::color::parse_color;
+    use crate::ui::styling::css::{CssTheme, Element};
+    use eframe::egui::Color32;
+
+    fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Color32 {
+        Color32::from_rgba_unmultiplied(red, green, blue, alpha)
+    }
+
+    /// Checks every supported colour syntax against the colour it stands for
+    #[test]
+    fn test_color_table() {
+        let cases = [
+            // Hex
+            ("#fff", Some(Color32::WHITE)),
+            ("#f00a", Some(rgba(255, 0, 0, 170))),
+            ("#7aa2f7", Some(Color32::from_rgb(122, 162, 247))),
+            ("#7AA2F780", Some(rgba(122, 162, 247, 128))),
+            // rgb() and rgba()
+            ("rgb(122 162 247)", Some(Color32::from_rgb(122, 162, 247))),
+            ("rgb(122, 162, 247)", Some(Color32::from_rgb(122, 162, 247))),
+            ("rgba(122,162,247,0.5)", Some(rgba(122, 162, 247, 128))),
+            ("rgb(100% 0% 0% / 50%)", Some(rgba(255, 0, 0, 128))),
+            ("rgb(none 255 0)", Some(Color32::from_rgb(0, 255, 0))),
+            ("rgb(300 -5 0)", Some(Color32::from_rgb(255, 0, 0))),
+            ("RGBA(0 0 255/0.25)", Some(rgba(0, 0, 255, 64))),
+            // hsl() and hsla()
+            ("hsl(0 100% 50%)", Some(Color32::from_rgb(255, 0, 0))),
+            ("hsl(120, 100%, 25%)", Some(Color32::from_rgb(0, 128, 0))),
+            ("hsla(240deg 100% 50% / 0.25)", Some(rgba(0, 0, 255, 64))),
+            (
+                "hsl(0.5turn 100% 50%)",
+                Some(Color32::from_rgb(0, 255, 255)),
+            ),
+            ("hsl(-120 100% 50%)", Some(Color32::from_rgb(0, 0, 255))),
+            ("hsl(0 0% 100%)", Some(Color32::WHITE)),
+            // Named colours
+            ("rebeccapurple", Some(Color32::from_rgb(102, 51, 153))),
+            ("Red", Some(Color32::from_rgb(255, 0, 0))),
+            ("transparent", Some(Color32::TRANSPARENT)),
+            // color-mix()
+            (
+                "color-mix(in srgb, red, blue)",
+                Some(Color32::from_rgb(128, 0, 128)),
+            ),
+            (
+                "color-mix(in srgb, red 25%, blue)",
+                Some(Color32::from_rgb(64, 0, 191)),
+            ),
+            (
+                "color-mix(in srgb, 25% red, blue 75%)",
+                Some(Color32::from_rgb(64, 0, 191)),
+            ),
+            (
+                "color-mix(in srgb, #fff, transparent)",
+                Some(rgba(255, 255, 255, 128)),
+            ),
+            (
+                "color-mix(in srgb, red 20%, blue 20%)",
+                Some(rgba(128, 0, 128, 102)),
+            ),
+            (
+                "color-mix(in srgb, rgb(255 0 0), hsl(240 100% 50%))",
+                Some(Color32::from_rgb(128, 0, 128)),
+            ),
+            // Invalid
+            ("#ff", None),
+            ("#ggg", None),
+            ("rgb(1 2)", None),
+            ("rgb(1, 2 3)", None),
+            ("hsl(a b c)", None),
+            ("notacolor", None),
+            ("color-mix(in lab, red, blue)", None),
+            ("color-mix(in srgb, red 0%, blue 0%)", None),
+            ("color-mix(in srgb, red 120%, blue)", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_color(input), expected, "{input}");
+        }
+    }
+
+    /// Tests mixing in linear light lands brighter than the sRGB midpoint
+    #[test]
+    fn test_color_mix_linear() {
+        let mixed = parse_color("color-mix(in srgb-linear, black, white)").unwrap();
+        assert!((187..=188).contains(&mixed.r()), "{mixed:?}");
+        assert_eq!(mixed.r(), mixed.g());
+    }
+
+    fn theme() -> CssTheme {
+        CssTheme::check(
+            ":root {
+                --accent: #7aa2f7;
+                --fg: var(--accent);
+                --a: var(--b);
+                --b: var(--a);
+                --c: var(--missing, red);
+                --d: var(--a, blue);
+                --e: var(--missing, var(--fg));
+                --mix: color-mix(in srgb, var(--accent), white);
+            }
+            .keycap { color: var(--fg); background: var(--later); padding: var(--pad) 4px }
+            .stats { color: var(--a) }
+            :root { --later: #010203; --pad: 2px }",
+        )
+        .0
+    }
+
+    /// Verifies `var()` references, fallbacks and cycles
+    #[test]
+    fn test_var_resolution() {
+        let theme = theme();
+        assert_eq!(theme.var("fg").as_deref(), Some("#7aa2f7"));
+        assert_eq!(theme.var("c").as_deref(), Some("red"));
+        assert_eq!(theme.var("d").as_deref(), Some("blue"));
+        assert_eq!(theme.var("e").as_deref(), Some("#7aa2f7"));
+        assert_eq!(
+            theme.var("mix").as_deref(),
+            Some("color-mix(in srgb, #7aa2f7, white)")
+        );
+        assert_eq!(
+            theme.resolve("1px var(--missing, 2px)").as_deref(),
+            Ok("1px 2px")
+        );
+        assert_eq!(
+            theme.resolve("var(--nope)"),
+            Err("unknown variable '--nope'".to_string())
+        );
+        assert_eq!(
+            theme.resolve("var(--a)"),
+            Err("variable cycle: --a -> --b -> --a".to_string())
+        );
+        assert!(theme.resolve("var(accent)").is_err());
+        assert!(theme.resolve("var(--accent").is_err());
+    }
+
+    /// Ensures element properties resolve variables, including ones defined later
+    #[test]
+    fn test_element_vars() {
+        let theme = theme();
+        let keycap = theme.style(Element::Keycap);
+        assert_eq!(keycap.color, Some(Color32::from_rgb(122, 162, 247)));
+        assert_eq!(keycap.background, Some(Color32::from_rgb(1, 2, 3)));
+        assert_eq!(keycap.padding, Some(eframe::egui::Margin::symmetric(4, 2)));
+        assert_eq!(theme.style(Element::Stats).color, None);
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod combo_tests {
+    use crate::hyprland::models::ConfigLine;
+    use crate::hyprland::parser::{config_variables, parse_combo};
+    use std::path::PathBuf;
+
+    fn config(text: &str) -> Vec<ConfigLine> {
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| ConfigLine {
+                path: PathBuf::new(),
+                number: i + 1,
+                text: line.to_string(),
+            })
+            .collect()
+    }
+
+    /// Checks the accepted ways of writing a combo give the same modmask and key
+    #[test]
+    fn test_parse_combo_forms() {
+        let expected = Ok((0x41, "Q".to_string()));
+        assert_eq!(parse_combo("SUPER+SHIFT+Q", &[]), expected);
+        assert_eq!(parse_combo("$mainMod SHIFT, Q", &[]), expected);
+        assert_eq!(parse_combo("  Shift + Super +Q ", &[]), expected);
+
+        let (modmask, key) = parse_combo("super shift q", &[]).unwrap();
+        assert_eq!((modmask, key.as_str()), (0x41, "q"));
+        assert_eq!(parse_combo("Mod4+Shift+q", &[]).unwrap().0, 0x41);
+        assert_eq!(parse_combo("F1", &[]), Ok((0, "F1".to_string())));
+        assert_eq!(
+            parse_combo("ctrl alt del", &[]),
+            Ok((0x0c, "Delete".to_string()))
+        );
+    }
+
+    /// Verifies variables from the config are expanded, overriding the defaults
+    #[test]
+    fn test_parse_combo_variables() {
+        let variables = config_variables(&config(
+            "$mainMod = ALT # not SUPER
+$hyper = $mainMod CTRL SHIFT
+bind = $mainMod, Q, killactive",
+        ));
+        assert_eq!(
+            parse_combo("$mainMod, Q", &variables),
+            Ok((0x08, "Q".to_string()))
+        );
+        assert_eq!(parse_combo("$hyper+Q", &variables).unwrap().0, 0x0d);
+        assert_eq!(parse_combo("$mod+Q", &variables).unwrap().0, 0x40);
+    }
+
+    /// Tests invalid combos are rejected with a reason
+    #[test]
+    fn test_parse_combo_errors() {
+        assert_eq!(parse_combo(" + ", &[]), Err("missing key".to_string()));
+        assert_eq!(
+            parse_combo("$mainMod,", &[]),
+            Err("missing key".to_string())
+        );
+        assert_eq!(
+            parse_combo("SUPER+X+Q", &[]),
+            Err("'X' is not a modifier".to_string())
+        );
+        assert_eq!(
+            parse_combo("$hyper, Q", &[]),
+            Err("unknown variable '$hyper'".to_string())
+        );
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod completions_tests {
+    use crate::cli::{CompletionShell, command, write_completions};
+    use clap_complete::engine::complete;
+
+    fn script(shell: CompletionShell) -> String {
+        let mut buf = Vec::new();
+        write_completions(shell, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Verifies the clap definition is consistent
+    #[test]
+    fn test_command_is_valid() {
+        command().debug_assert();
+    }
+
+    /// Checks each shell gets a script for the hyprbind binary
+    #[test]
+    fn test_completion_scripts() {
+        for shell in [
+            CompletionShell::Bash,
+            CompletionShell::Zsh,
+            CompletionShell::Fish,
+        ] {
+            let script = script(shell);
+            assert!(script.contains("COMPLETE="), "{script}");
+            assert!(script.contains("hyprbind"));
+        }
+
+        let nushell = script(CompletionShell::Nushell);
+        assert!(nushell.contains(r#"export extern "hyprbind show""#));
+        assert!(nushell.contains(r#"--submap: string@"nu-complete hyprbind submaps""#));
+    }
+
+    /// Tests export formats are completed
+    #[test]
+    fn test_complete_export_formats() {
+        let args = ["hyprbind", "export", "--format", ""]
+            .map(Into::into)
+            .to_vec();
+        let candidates = complete(&mut command(), args, 3, None).unwrap();
+        let values: Vec<_> = candidates
+            .iter()
+            .map(|c| c.get_value().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                "table", "json", "dmenu", "rofi", "fuzzel", "wofi", "hyprconf"
+            ]
+        );
+    }
+}
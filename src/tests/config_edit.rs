@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod config_edit_tests {
+    use crate::hyprland::parser::{parse_config_lines, rewrite_bind_description};
+    use crate::hyprland::source::{read_config_lines, replace_config_line};
+    use crate::hyprland::{ConfigLine, DescriptionStyle};
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn lines(contents: &str) -> Vec<ConfigLine> {
+        contents
+            .lines()
+            .enumerate()
+            .map(|(i, text)| ConfigLine {
+                path: PathBuf::from("hyprland.conf"),
+                number: i + 1,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    /// Tests converting `bind` to `bindd` while keeping variables and spacing
+    #[test]
+    fn test_rewrite_bind_to_bindd() {
+        let line = "  bind = $mainMod SHIFT, Q, killactive, # old";
+        let rewritten = rewrite_bind_description(line, "Close window", DescriptionStyle::Bindd);
+        assert_eq!(
+            rewritten.as_deref(),
+            Some("  bindd = $mainMod SHIFT, Q, Close window, killactive, # old")
+        );
+    }
+
+    /// Tests replacing and removing an existing `bindd` description
+    #[test]
+    fn test_rewrite_existing_bindd() {
+        let line = "bindde = SUPER, L, Grow, resizeactive, 10 0";
+        assert_eq!(
+            rewrite_bind_description(line, "Wider", DescriptionStyle::Bindd).as_deref(),
+            Some("bindde = SUPER, L, Wider, resizeactive, 10 0")
+        );
+        assert_eq!(
+            rewrite_bind_description(line, "", DescriptionStyle::Bindd).as_deref(),
+            Some("binde = SUPER, L, resizeactive, 10 0")
+        );
+    }
+
+    /// Tests writing descriptions as trailing comments
+    #[test]
+    fn test_rewrite_comment_style() {
+        let line = "bind = SUPER, F, exec, thunar # files";
+        assert_eq!(
+            rewrite_bind_description(line, "File #1", DescriptionStyle::Comment).as_deref(),
+            Some("bind = SUPER, F, exec, thunar # File #1")
+        );
+        assert_eq!(
+            rewrite_bind_description(line, "", DescriptionStyle::Comment).as_deref(),
+            Some("bind = SUPER, F, exec, thunar")
+        );
+    }
+
+    /// Ensures lines that cannot carry the description are refused
+    #[test]
+    fn test_rewrite_refuses() {
+        assert!(rewrite_bind_description("$mod = SUPER", "x", DescriptionStyle::Bindd).is_none());
+        assert!(rewrite_bind_description("bind = SUPER", "x", DescriptionStyle::Bindd).is_none());
+        assert!(
+            rewrite_bind_description("bind = SUPER, Q, exit", "a, b", DescriptionStyle::Bindd)
+                .is_none()
+        );
+    }
+
+    /// Verifies binds keep their line index, submap and trailing comment
+    #[test]
+    fn test_parse_config_lines_locations() {
+        let conf = lines(
+            "$mod = SUPER\nbind = $mod, Q, killactive # Close\nsubmap = resize\nbinde = , h, resizeactive, -10 0\nsubmap = reset",
+        );
+        let binds = parse_config_lines(&conf);
+        assert_eq!(binds.len(), 2);
+        assert_eq!(binds[0].line, 1);
+        assert_eq!(binds[0].comment, "Close");
+        assert_eq!(binds[0].entry.modifiers, "SUPER");
+        assert_eq!(binds[1].line, 3);
+        assert_eq!(binds[1].entry.submap, "resize");
+        assert!(binds[1].entry.flags.repeat);
+    }
+
+    /// Verifies sourced files are inlined at their `source =` line
+    #[test]
+    fn test_read_config_lines_follows_source() {
+        let dir = TempDir::new().unwrap();
+        let main = dir.path().join("hyprland.conf");
+        fs::write(
+            &main,
+            "$mod = SUPER\nsource = binds.conf\nbind = $mod, B, exec, b\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("binds.conf"), "bind = $mod, A, exec, a\n").unwrap();
+
+        let conf = read_config_lines(&main).unwrap();
+        let binds = parse_config_lines(&conf);
+        assert_eq!(binds.len(), 2);
+        assert_eq!(binds[0].entry.key(), "A");
+        assert_eq!(binds[0].entry.modifiers, "SUPER");
+        assert_eq!(conf[binds[0].line].path, dir.path().join("binds.conf"));
+        assert_eq!(conf[binds[0].line].number, 1);
+        assert_eq!(binds[1].entry.key(), "B");
+    }
+
+    /// Ensures a line that changed on disk is never overwritten
+    #[test]
+    fn test_replace_config_line_refuses_stale_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("hyprland.conf");
+        fs::write(&path, "bind = SUPER, Q, exit\n").unwrap();
+        let stale = ConfigLine {
+            path: path.clone(),
+            number: 1,
+            text: "bind = SUPER, Q, killactive".to_string(),
+        };
+
+        let result = replace_config_line(&stale, "bindd = SUPER, Q, X, killactive", dir.path());
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "bind = SUPER, Q, exit\n"
+        );
+    }
+
+    /// Verifies the rewrite is written and a backup copy of the file is kept
+    #[test]
+    fn test_replace_config_line_writes_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("hyprland.conf");
+        fs::write(&path, "# binds\nbind = SUPER, Q, exit\n").unwrap();
+        let line = ConfigLine {
+            path: path.clone(),
+            number: 2,
+            text: "bind = SUPER, Q, exit".to_string(),
+        };
+
+        let backup = replace_config_line(
+            &line,
+            "bindd = SUPER, Q, Quit, exit",
+            &dir.path().join("bak"),
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# binds\nbindd = SUPER, Q, Quit, exit\n"
+        );
+        assert_eq!(
+            fs::read_to_string(backup).unwrap(),
+            "# binds\nbind = SUPER, Q, exit\n"
+        );
+    }
+
+    /// Checks repeated saves and same-named files never share a backup, and no temporary
+    /// file is left next to the config
+    #[test]
+    fn test_replace_config_line_keeps_every_backup() {
+        let dir = TempDir::new().unwrap();
+        let backups = dir.path().join("bak");
+        let path = dir.path().join("hyprland.conf");
+        fs::write(&path, "bind = SUPER, Q, exit\n").unwrap();
+        let first = replace_config_line(
+            &ConfigLine {
+                path: path.clone(),
+                number: 1,
+                text: "bind = SUPER, Q, exit".to_string(),
+            },
+            "bindd = SUPER, Q, Quit, exit",
+            &backups,
+        )
+        .unwrap();
+        let second = replace_config_line(
+            &ConfigLine {
+                path,
+                number: 1,
+                text: "bindd = SUPER, Q, Quit, exit".to_string(),
+            },
+            "bindd = SUPER, Q, Leave, exit",
+            &backups,
+        )
+        .unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            fs::read_to_string(&first).unwrap(),
+            "bind = SUPER, Q, exit\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&second).unwrap(),
+            "bindd = SUPER, Q, Quit, exit\n"
+        );
+
+        let nested = dir.path().join("conf.d");
+        fs::create_dir(&nested).unwrap();
+        let other = nested.join("hyprland.conf");
+        fs::write(&other, "bind = SUPER, E, exec, thunar\n").unwrap();
+        let third = replace_config_line(
+            &ConfigLine {
+                path: other,
+                number: 1,
+                text: "bind = SUPER, E, exec, thunar".to_string(),
+            },
+            "bindd = SUPER, E, Files, exec, thunar",
+            &backups,
+        )
+        .unwrap();
+        assert!(third != first && third != second);
+        assert_eq!(fs::read_dir(&backups).unwrap().count(), 3);
+
+        let names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert!(names.iter().all(|n| !n.contains("hyprbind-")), "{names:?}");
+    }
+}
@@ -1,14 +1,17 @@
 #[cfg(test)]
 mod config_roundtrip_tests {
     use crate::config::user::UserConfig;
-    use crate::hyprland::SearchOptions;
+    use crate::hyprland::launcher::{Launcher, LauncherSettings};
+    use crate::hyprland::{CategoryRule, DescriptionStyle, SearchOptions, SourceKind};
+    use crate::ui::styling::fonts::FontSettings;
+    use crate::ui::styling::icons::IconSet;
     use crate::ui::types::{ColumnVisibility, Theme};
+    use std::collections::HashMap;
     use std::fs;
     use tempfile::TempDir;
 
     fn setup_temp_config(temp_dir: &TempDir) -> std::path::PathBuf {
-        let config_path = temp_dir.path().join("hyprbind_test.json");
-        config_path
+        temp_dir.path().join("hyprbind_test.json")
     }
 
     /// Ensures default config can be serialized and deserialized without data loss
@@ -42,13 +45,40 @@ mod config_roundtrip_tests {
                 keybind: false,
                 command: true,
                 description: false,
+                category: true,
             },
             search_options: SearchOptions {
                 keybind: true,
                 command: true,
                 description: false,
+                annotations: false,
             },
             zen_mode: true,
+            description_style: DescriptionStyle::Comment,
+            group_by_category: true,
+            category_rules: vec![CategoryRule {
+                category: "Games".to_string(),
+                args: vec!["steam".to_string()],
+                ..CategoryRule::default()
+            }],
+            sources: vec![SourceKind::Socket, SourceKind::Config],
+            launchers: HashMap::from([(
+                Launcher::Rofi,
+                LauncherSettings {
+                    template: Some("{keys}\\t{description}".to_string()),
+                    plain: true,
+                    index: false,
+                },
+            )]),
+            css_theme: Some("tokyo-night".to_string()),
+            pywal_sync: true,
+            fonts: FontSettings {
+                family: Some("Inter".to_string()),
+                weight: 500,
+                monospace: Some("JetBrains Mono".to_string()),
+                scale: 1.3,
+            },
+            icon_set: IconSet::Ascii,
         };
 
         let json = serde_json::to_string_pretty(&original).unwrap();
@@ -73,9 +103,18 @@ mod config_roundtrip_tests {
         assert!(json.contains("\"column_visibility\""));
         assert!(json.contains("\"search_options\""));
         assert!(json.contains("\"zen_mode\""));
+        assert!(json.contains("\"description_style\""));
+        assert!(json.contains("\"group_by_category\""));
+        assert!(json.contains("\"category_rules\""));
+        assert!(json.contains("\"sources\""));
+        assert!(json.contains("\"launchers\""));
+        assert!(json.contains("\"css_theme\""));
+        assert!(json.contains("\"pywal_sync\""));
+        assert!(json.contains("\"fonts\""));
+        assert!(json.contains("\"icon_set\""));
     }
 
-    /// Verifies default UserConfig values match specification
+    /// Verifies default `UserConfig` values match specification
     #[test]
     fn test_default_values() {
         let config = UserConfig::default();
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod css_tests {
+    use crate::ui::styling::css::{CssTheme, Element};
+    use crate::ui::styling::stylesheet::{Diagnostic, parse_stylesheet};
+    use eframe::egui::{Color32, Margin};
+
+    /// Checks comments spanning lines are skipped and positions are kept
+    #[test]
+    fn test_multiline_comments() {
+        let sheet = parse_stylesheet(
+            "/* first\n   .header { color: #ff0000; }\n*/\n.stats { color: #00ff00; /* inline */ }\n",
+        );
+        assert_eq!(sheet.rules.len(), 1);
+        let rule = &sheet.rules[0];
+        assert_eq!(rule.selectors, vec![".stats"]);
+        assert_eq!((rule.line, rule.column), (4, 1));
+        assert_eq!(rule.declarations[0].name, "color");
+        assert_eq!(rule.declarations[0].value, "#00ff00");
+        assert_eq!(
+            (rule.declarations[0].line, rule.declarations[0].column),
+            (4, 10)
+        );
+    }
+
+    /// Tests several declarations on one line and selector lists
+    #[test]
+    fn test_declarations_and_selector_lists() {
+        let sheet = parse_stylesheet(
+            ".description,\n.command   { color: #112233; font-size: 13px;padding: 2px }",
+        );
+        let rule = &sheet.rules[0];
+        assert_eq!(rule.selectors, vec![".description", ".command"]);
+        let names: Vec<&str> = rule.declarations.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["color", "font-size", "padding"]);
+    }
+
+    /// Ensures selectors map to elements, whitespace collapsed
+    #[test]
+    fn test_element_selectors() {
+        let sheet = parse_stylesheet(".table   row:nth-child(even) {}");
+        assert_eq!(sheet.rules[0].selectors, vec![".table row:nth-child(even)"]);
+        assert_eq!(
+            Element::from_selector(".table row:nth-child(even)"),
+            Some(Element::EvenRow)
+        );
+        assert_eq!(
+            Element::from_selector(".table row:nth-child(2n+1)"),
+            Some(Element::OddRow)
+        );
+        assert_eq!(
+            Element::from_selector(".modifier.keycap"),
+            Some(Element::Modifier)
+        );
+        assert_eq!(Element::from_selector(".unknown"), None);
+        for element in Element::ALL {
+            assert_eq!(Element::from_selector(element.selector()), Some(element));
+        }
+    }
+
+    /// Verifies modifiers build on key caps and even rows on rows
+    #[test]
+    fn test_cascade() {
+        let theme = CssTheme::check(
+            ".keycap { color: #ffffff; background: #000000 }
+             .keycap.modifier { background: #ff0000 }
+             .table row { color: #010203 }
+             .table row:nth-child(even) { background: #0a0b0c }",
+        )
+        .0;
+        let modifier = theme.style(Element::Modifier);
+        assert_eq!(modifier.color, Some(Color32::WHITE));
+        assert_eq!(modifier.background, Some(Color32::RED));
+        let even = theme.style(Element::EvenRow);
+        assert_eq!(even.color, Some(Color32::from_rgb(1, 2, 3)));
+        assert_eq!(even.background, Some(Color32::from_rgb(10, 11, 12)));
+        assert_eq!(theme.style(Element::OddRow).background, None);
+    }
+
+    /// Tests lengths and the one to four value forms of padding
+    #[test]
+    fn test_lengths_and_padding() {
+        let theme = CssTheme::check(
+            ".keycap { border-radius: 4px; font-size: 14; padding: 2px 6px }
+             .header { padding: 1 2 3 4 }
+             .search { padding: 5px 6px 7px }
+             .stats { padding: 3px; font-size: big }",
+        )
+        .0;
+        let keycap = theme.style(Element::Keycap);
+        assert_eq!(keycap.border_radius, Some(4.0));
+        assert_eq!(keycap.font_size, Some(14.0));
+        assert_eq!(keycap.padding, Some(Margin::symmetric(6, 2)));
+        let header = theme.style(Element::Header).padding;
+        assert_eq!(
+            header,
+            Some(Margin {
+                left: 4,
+                right: 2,
+                top: 1,
+                bottom: 3
+            })
+        );
+        let search = theme.style(Element::Search).padding;
+        assert_eq!(
+            search,
+            Some(Margin {
+                left: 6,
+                right: 6,
+                top: 5,
+                bottom: 7
+            })
+        );
+        let stats = theme.style(Element::Stats);
+        assert_eq!(stats.padding, Some(Margin::same(3)));
+        assert_eq!(stats.font_size, None);
+    }
+
+    /// Ensures `:root` variables still load next to element rules
+    #[test]
+    fn test_root_variables() {
+        let theme = CssTheme::check(
+            ":root {\n  --bg: #0f1117;\n  --accent: #7aa2f7; --radius: 6;\n}\n.header { color: #ffffff }",
+        ).0;
+        assert_eq!(theme.vars.get("bg").map(String::as_str), Some("#0f1117"));
+        assert_eq!(
+            theme.vars.get("accent").map(String::as_str),
+            Some("#7aa2f7")
+        );
+        assert_eq!(theme.vars.get("radius").map(String::as_str), Some("6"));
+        assert_eq!(theme.style(Element::Header).color, Some(Color32::WHITE));
+    }
+
+    /// Checks syntax errors are reported where they are and the rest still parses
+    #[test]
+    fn test_syntax_diagnostics() {
+        let sheet = parse_stylesheet(
+            "@import url(a.css);\n.stats { color red; font-size: ; color: #fff }\n.header { color: #000",
+        );
+        assert_eq!(
+            sheet.diagnostics,
+            vec![
+                Diagnostic::warning("@import is not supported", 1, 1),
+                Diagnostic::error("expected 'name: value', found 'color red'", 2, 10),
+                Diagnostic::error("missing value for 'font-size'", 2, 21),
+                Diagnostic::error("'.header' is missing its closing '}'", 3, 1),
+            ]
+        );
+        assert_eq!(sheet.rules.len(), 2);
+        assert_eq!(sheet.rules[0].declarations.len(), 1);
+        assert_eq!(
+            parse_stylesheet("/* open").diagnostics,
+            vec![Diagnostic::error("unclosed comment", 1, 1)]
+        );
+    }
+
+    /// Tests unknown variables, properties, selectors and bad values are reported
+    #[test]
+    fn test_theme_diagnostics() {
+        let (theme, diagnostics) = CssTheme::check(
+            ":root { --bg: #zzz; --accent: var(--nope); --spacing: wide }
+.header { colr: red; color: var(--missing); background: #111 }
+.keycap, .foo { padding: 1 2 3 4 5 }",
+        );
+        let messages: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "1:9: error: --bg must be a colour, got '#zzz'",
+                "1:21: error: unknown variable '--nope'",
+                "1:44: error: --spacing must be a number, got 'wide'",
+                "2:11: error: unknown property 'colr'",
+                "2:22: error: unknown variable '--missing'",
+                "3:1: warning: unknown selector '.foo', its properties are ignored",
+                "3:17: error: invalid value '1 2 3 4 5' for 'padding'",
+            ]
+        );
+        // Valid declarations next to broken ones still apply
+        assert_eq!(
+            theme.style(Element::Header).background,
+            Some(Color32::from_rgb(17, 17, 17))
+        );
+    }
+
+    /// Ensures the bundled sample theme is free of problems
+    #[test]
+    fn test_sample_has_no_diagnostics() {
+        let (_, diagnostics) = CssTheme::check(include_str!("../../hyprbind-theme.sample.css"));
+        assert_eq!(diagnostics, Vec::new());
+    }
+}
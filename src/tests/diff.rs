@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod diff_tests {
+    use crate::config::history::save_snapshot_in;
+    use crate::hyprland::{BindDiff, KeyBindEntry, KeyBindings, read_keybindings_json};
+    use crate::ui::diff::snapshot_label;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn entry(modifiers: &str, key: &str, command: &str, description: &str) -> KeyBindEntry {
+        KeyBindEntry::new(
+            modifiers.to_string(),
+            key.to_string(),
+            command.to_string(),
+            description.to_string(),
+        )
+    }
+
+    fn old_binds() -> KeyBindings {
+        KeyBindings {
+            entries: vec![
+                entry("SUPER", "Q", "killactive", ""),
+                entry("SUPER", "Return", "exec kitty", "Terminal"),
+                entry("SUPER", "E", "exec thunar", ""),
+            ],
+        }
+    }
+
+    fn new_binds() -> KeyBindings {
+        KeyBindings {
+            entries: vec![
+                entry("SUPER", "Return", "exec foot", "Terminal"),
+                entry("SUPER", "q", "killactive", ""),
+                entry("SUPER+SHIFT", "E", "exit", ""),
+            ],
+        }
+    }
+
+    /// Tests added, removed and changed binds matched by combo
+    #[test]
+    fn test_diff_kinds() {
+        let diff = BindDiff::new(&old_binds(), &new_binds());
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].modifiers, "SUPER+SHIFT");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].command, "exec thunar");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].before.command, "exec kitty");
+        assert_eq!(diff.changed[0].after.command, "exec foot");
+    }
+
+    /// Ensures identical sets and reordered modifiers produce no differences
+    #[test]
+    fn test_diff_identical() {
+        let old = KeyBindings {
+            entries: vec![entry("SUPER+SHIFT", "Q", "exit", "")],
+        };
+        let new = KeyBindings {
+            entries: vec![entry("SHIFT+SUPER", "Q", "exit", "")],
+        };
+        assert!(BindDiff::new(&old, &new).is_empty());
+        assert!(BindDiff::new(&old_binds(), &old_binds()).is_empty());
+    }
+
+    /// Verifies submaps and description changes are taken into account
+    #[test]
+    fn test_diff_submap_and_description() {
+        let mut resize = entry("", "h", "resizeactive -10 0", "");
+        resize.submap = "resize".to_string();
+        let old = KeyBindings {
+            entries: vec![entry("", "h", "resizeactive -10 0", ""), resize.clone()],
+        };
+        resize.description = "Shrink".to_string();
+        let new = KeyBindings {
+            entries: vec![entry("", "h", "resizeactive -10 0", ""), resize],
+        };
+
+        let diff = BindDiff::new(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].after.submap, "resize");
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+    }
+
+    /// Ensures a combo bound twice only reports the bind that differs
+    #[test]
+    fn test_diff_duplicate_combo() {
+        let old = KeyBindings {
+            entries: vec![
+                entry("SUPER", "V", "togglefloating", ""),
+                entry("SUPER", "V", "centerwindow", ""),
+            ],
+        };
+        let new = KeyBindings {
+            entries: vec![entry("SUPER", "V", "centerwindow", "")],
+        };
+
+        let diff = BindDiff::new(&old, &new);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].command, "togglefloating");
+        assert!(diff.changed.is_empty());
+    }
+
+    /// Tests the text and JSON diff formats
+    #[test]
+    fn test_diff_formats() {
+        let diff = BindDiff::new(&old_binds(), &new_binds());
+
+        let plain = diff.to_text(false);
+        assert!(plain.contains("+ SUPER+SHIFT+E: exit\n"));
+        assert!(plain.contains("- SUPER+E: exec thunar\n"));
+        assert!(
+            plain.contains(
+                "~ SUPER+Return\n    - exec kitty (Terminal)\n    + exec foot (Terminal)\n"
+            )
+        );
+        assert!(plain.ends_with("1 added, 1 removed, 1 changed\n"));
+        assert!(!plain.contains('\x1b'));
+
+        let colored = diff.to_text(true);
+        assert!(colored.contains("\x1b[32m+ SUPER+SHIFT+E: exit\x1b[0m"));
+
+        let json: serde_json::Value = serde_json::from_str(&diff.to_json().unwrap()).unwrap();
+        assert_eq!(json["added"][0]["command"], "exit");
+        assert_eq!(json["changed"][0]["before"]["command"], "exec kitty");
+    }
+
+    /// Verifies snapshots are only written when the keybindings changed
+    #[test]
+    fn test_snapshot_dedup() {
+        let dir = TempDir::new().unwrap();
+
+        let first = save_snapshot_in(dir.path(), &old_binds()).unwrap().unwrap();
+        assert!(
+            save_snapshot_in(dir.path(), &old_binds())
+                .unwrap()
+                .is_none()
+        );
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = save_snapshot_in(dir.path(), &new_binds()).unwrap().unwrap();
+
+        assert_ne!(first, second);
+        let loaded = read_keybindings_json(&second).unwrap();
+        assert_eq!(loaded.entries[0].command, "exec foot");
+        assert!(BindDiff::new(&read_keybindings_json(&first).unwrap(), &old_binds()).is_empty());
+    }
+
+    /// Tests the readable snapshot time shown in the history window
+    #[test]
+    fn test_snapshot_label() {
+        assert_eq!(
+            snapshot_label(Path::new("/h/snapshot_001700000000000.json")),
+            "2023-11-14 22:13:20 UTC"
+        );
+        assert_eq!(snapshot_label(Path::new("other.json")), "other");
+    }
+}
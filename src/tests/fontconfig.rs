@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod fontconfig_tests {
+    use crate::ui::styling::fontconfig::{
+        FontFace, families, find_face, parse_config, read_faces, scan_faces,
+    };
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    const FIRPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/Firple-Bold.ttf");
+
+    fn face(family: &str, weight: u16, italic: bool) -> FontFace {
+        FontFace {
+            path: PathBuf::from(format!("/fonts/{family}-{weight}.ttf")),
+            index: 0,
+            family: family.to_string(),
+            weight,
+            italic,
+        }
+    }
+
+    /// Tests directories and includes are read, skipping comments and similar tags
+    #[test]
+    fn test_parse_config() {
+        let conf = r#"<?xml version="1.0"?>
+<!DOCTYPE fontconfig SYSTEM "urn:fontconfig:fonts.dtd">
+<fontconfig>
+    <!-- <dir>/commented/out</dir> -->
+    <dir>/usr/share/fonts</dir>
+    <dir prefix="xdg">fonts</dir>
+    <dir>extra</dir>
+    <cachedir>/var/cache/fontconfig</cachedir>
+    <include ignore_missing="yes">conf.d</include>
+    <selectfont><rejectfont><glob>*.pcf</glob></rejectfont></selectfont>
+</fontconfig>"#;
+        let (dirs, includes) = parse_config(conf, Path::new("/etc/fonts"));
+        assert_eq!(dirs.len(), 3);
+        assert_eq!(dirs[0], PathBuf::from("/usr/share/fonts"));
+        assert!(dirs[1].ends_with("fonts") && dirs[1].is_absolute());
+        assert_eq!(dirs[2], PathBuf::from("/etc/fonts/extra"));
+        assert_eq!(includes, vec![PathBuf::from("/etc/fonts/conf.d")]);
+    }
+
+    /// Checks the bundled font is read from its tables and found by a directory scan
+    #[test]
+    fn test_read_bundled_font() {
+        let faces = read_faces(Path::new(FIRPLE));
+        assert_eq!(faces.len(), 1);
+        assert_ne!(faces[0].family, "");
+        assert!((100..=900).contains(&faces[0].weight));
+        assert!(!faces[0].italic);
+
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("truetype").join("firple");
+        fs::create_dir_all(&nested).unwrap();
+        fs::copy(FIRPLE, nested.join("Firple-Bold.TTF")).unwrap();
+        fs::write(nested.join("broken.ttf"), "not a font").unwrap();
+        fs::write(nested.join("README"), "fonts").unwrap();
+        let scanned = scan_faces(&[dir.path().to_path_buf(), dir.path().join("missing")]);
+        assert_eq!(scanned.len(), 1);
+        assert_eq!(scanned[0].family, faces[0].family);
+    }
+
+    /// Verifies the upright face closest to the weight is picked, ignoring case
+    #[test]
+    fn test_find_face() {
+        let faces = vec![
+            face("Inter", 300, false),
+            face("Inter", 400, true),
+            face("Inter", 600, false),
+            face("Noto Sans", 400, false),
+        ];
+        assert_eq!(families(&faces), vec!["Inter", "Noto Sans"]);
+        assert_eq!(find_face(&faces, "inter", 400).unwrap().weight, 300);
+        assert_eq!(find_face(&faces, "Inter", 700).unwrap().weight, 600);
+        assert_eq!(find_face(&faces, "Noto Sans", 900).unwrap().weight, 400);
+        assert_eq!(find_face(&faces, "Roboto", 400), None);
+    }
+}
@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod hyprconf_tests {
+    use crate::hyprland::models::ConfigLine;
+    use crate::hyprland::parser::{
+        modifiers_to_modmask, modmask_to_string, parse_binds_output_with_warnings,
+        parse_config_lines,
+    };
+    use crate::hyprland::{KeyBindEntry, KeyBindings};
+    use std::path::PathBuf;
+
+    const HYPRCTL_SAMPLE: &str = r"bindd
+	modmask: 64
+	submap:
+	key: Return
+	keycode: 0
+	catchall: false
+	description: Terminal
+	dispatcher: exec
+	arg: kitty
+
+bind
+	modmask: 65
+	submap:
+	key: Q
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: killactive
+	arg:
+
+bindle
+	modmask: 0
+	submap:
+	key: XF86AudioRaiseVolume
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: exec
+	arg: wpctl set-volume -l 1 @DEFAULT_AUDIO_SINK@ 5%+
+
+bindm
+	modmask: 64
+	submap:
+	key: mouse:272
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: mouse
+	arg: movewindow
+
+binde
+	modmask: 0
+	submap: resize
+	key: right
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: resizeactive
+	arg: 10 0
+
+bind
+	modmask: 0
+	submap: resize
+	key: escape
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: submap
+	arg: reset";
+
+    fn config_binds(text: &str) -> KeyBindings {
+        let lines: Vec<ConfigLine> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| ConfigLine {
+                path: PathBuf::new(),
+                number: i + 1,
+                text: line.to_string(),
+            })
+            .collect();
+        KeyBindings {
+            entries: parse_config_lines(&lines)
+                .into_iter()
+                .map(|bind| bind.entry)
+                .collect(),
+        }
+    }
+
+    fn assert_same_binds(a: &KeyBindings, b: &KeyBindings) {
+        assert_eq!(a.entries.len(), b.entries.len());
+        for (x, y) in a.entries.iter().zip(&b.entries) {
+            assert_eq!(x.modifiers, y.modifiers);
+            assert_eq!(x.key(), y.key());
+            assert_eq!(x.command, y.command);
+            assert_eq!(x.description, y.description);
+            assert_eq!(x.submap, y.submap);
+            assert_eq!(x.dispatcher, y.dispatcher);
+            assert_eq!(x.arg, y.arg);
+            assert_eq!(x.flags, y.flags);
+        }
+    }
+
+    /// Verifies hyprctl output survives a trip through hyprland.conf syntax
+    #[test]
+    fn test_roundtrip_from_hyprctl() {
+        let original = parse_binds_output_with_warnings(HYPRCTL_SAMPLE).keybindings;
+        assert_eq!(original.entries.len(), 6);
+
+        let conf = original.to_hyprconf(None, false);
+        let reparsed = config_binds(&conf);
+
+        assert_same_binds(&original, &reparsed);
+    }
+
+    /// Ensures every modifier bit survives naming, the written config and combo lookup
+    #[test]
+    fn test_modmask_roundtrip() {
+        for modmask in 0..=0xff {
+            let modifiers = modmask_to_string(modmask);
+            assert_eq!(modifiers_to_modmask(&modifiers), modmask, "{modifiers}");
+
+            let entry = KeyBindEntry::new(
+                modifiers,
+                "x".to_string(),
+                "exec true".to_string(),
+                String::new(),
+            );
+            assert!(entry.has_combo(modmask, "X"));
+            let kb = KeyBindings {
+                entries: vec![entry],
+            };
+            let reparsed = config_binds(&kb.to_hyprconf(None, false));
+            assert_eq!(
+                modifiers_to_modmask(&reparsed.entries[0].modifiers),
+                modmask
+            );
+        }
+    }
+
+    /// Verifies `$mainMod` substitution round-trips through variable expansion
+    #[test]
+    fn test_roundtrip_with_main_mod() {
+        let original = parse_binds_output_with_warnings(HYPRCTL_SAMPLE).keybindings;
+
+        let conf = original.to_hyprconf(Some("super"), false);
+        assert!(conf.contains("$mainMod = SUPER\n"));
+        assert!(conf.contains("bind = $mainMod SHIFT, Q, killactive\n"));
+        assert!(!conf.contains("SUPER,"));
+
+        let reparsed = config_binds(&conf);
+        assert_same_binds(&original, &reparsed);
+    }
+
+    /// Checks keyword flags and submap block layout of the generated config
+    #[test]
+    fn test_hyprconf_lines() {
+        let kb = parse_binds_output_with_warnings(HYPRCTL_SAMPLE).keybindings;
+        let conf = kb.to_hyprconf(None, false);
+        let lines: Vec<&str> = conf.lines().collect();
+
+        assert!(lines.contains(&"bindd = SUPER, Return, Terminal, exec, kitty"));
+        assert!(lines.contains(
+            &"bindle = , XF86AudioRaiseVolume, exec, wpctl set-volume -l 1 @DEFAULT_AUDIO_SINK@ 5%+"
+        ));
+        assert!(lines.contains(&"bindm = SUPER, mouse:272, movewindow"));
+
+        let start = lines
+            .iter()
+            .position(|l| *l == "submap = resize")
+            .expect("submap block");
+        assert_eq!(lines[start + 1], "binde = , right, resizeactive, 10 0");
+        assert_eq!(lines[start + 2], "bind = , escape, submap, reset");
+        assert_eq!(lines[start + 3], "submap = reset");
+    }
+
+    /// Ensures `#` and commas are written in a form hyprland.conf accepts
+    #[test]
+    fn test_hyprconf_escaping() {
+        let entry = KeyBindEntry::new(
+            "SUPER".to_string(),
+            "N".to_string(),
+            "exec notify-send #1, hi".to_string(),
+            "Notify #1, loudly".to_string(),
+        );
+        let kb = KeyBindings {
+            entries: vec![entry],
+        };
+
+        let conf = kb.to_hyprconf(None, false);
+        assert!(conf.contains("bindd = SUPER, N, Notify ##1; loudly, exec, notify-send ##1, hi"));
+
+        let reparsed = config_binds(&conf);
+        assert_eq!(reparsed.entries.len(), 1);
+        assert_eq!(reparsed.entries[0].description, "Notify #1; loudly");
+        assert_eq!(reparsed.entries[0].command, "exec notify-send #1, hi");
+    }
+
+    /// Validates config parsing of variables, comments and modifier spellings
+    #[test]
+    fn test_parse_config_lines() {
+        let conf = r"
+# comment line
+$mainMod = SUPER
+$terminal = kitty # trailing comment
+bind = $mainMod, Return, exec, $terminal
+bind = SUPER_SHIFT, Q, killactive,
+bindd = MOD4 CTRL, E, Files, exec, thunar
+bind = ALT, Tab, cyclenext
+general {
+    gaps_in = 5
+}
+binds:scroll_event_delay = 0
+";
+        let kb = config_binds(conf);
+        assert_eq!(kb.entries.len(), 4);
+        assert_eq!(kb.entries[0].modifiers, "SUPER");
+        assert_eq!(kb.entries[0].command, "exec kitty");
+        assert_eq!(kb.entries[1].modifiers, "SUPER+SHIFT");
+        assert_eq!(kb.entries[1].command, "killactive");
+        assert_eq!(kb.entries[2].modifiers, "SUPER+CTRL");
+        assert_eq!(kb.entries[2].description, "Files");
+        assert_eq!(kb.entries[3].modifiers, "ALT");
+        assert_eq!(kb.entries[3].dispatcher, "cyclenext");
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod icons_tests {
-    use crate::ui::styling::icons::get_icon;
+    use crate::ui::styling::icons::{IconMap, IconSet, get_icon, read_overrides};
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
 
     /// Tests icon mapping for various keys and modifiers
     #[test]
@@ -40,4 +43,49 @@ mod icons_tests {
             assert_eq!(get_icon(input), *expected);
         }
     }
+
+    /// Checks the Unicode and ASCII sets, and keys added beyond the original table
+    #[test]
+    fn test_icon_sets() {
+        let unicode = IconMap::new(IconSet::Unicode, HashMap::new());
+        let ascii = IconMap::new(IconSet::Ascii, HashMap::new());
+        let nerd = IconMap::default();
+        assert_eq!(unicode.icon("SUPER"), "⌘");
+        assert_eq!(unicode.icon("shift"), "⇧");
+        assert_eq!(unicode.icon("Return"), "⏎");
+        assert_eq!(ascii.icon("SUPER"), "Super");
+        assert_eq!(ascii.icon("XF86AudioRaiseVolume"), "Vol+");
+        assert_eq!(ascii.icon("mouse:272"), "LMB");
+        // No Nerd Font glyph: the key name stays
+        assert_eq!(nerd.icon("CTRL"), "CTRL");
+
+        for map in [&nerd, &unicode, &ascii] {
+            assert_eq!(map.icon("bracketleft"), "[");
+            assert_eq!(map.icon("comma"), ",");
+            assert_eq!(map.icon("f12"), "F12");
+            assert_eq!(map.icon("KP_Add"), "KP +");
+            assert_eq!(map.icon("KP_7"), "KP 7");
+            assert_eq!(map.icon("XF86Launch1"), "Launch 1");
+            assert_eq!(map.icon("fn"), "fn");
+        }
+        assert_eq!(ascii.icon("KP_Enter"), "KP Enter");
+        assert_eq!(nerd.icon("XF86TouchpadToggle"), "Touchpad Toggle");
+    }
+
+    /// Tests `icons.json` labels replace those of the set, matching keys in any case
+    #[test]
+    fn test_icon_overrides() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("icons.json");
+        assert_eq!(read_overrides(&path), Ok(HashMap::new()));
+
+        fs::write(&path, r#"{"Super": "Win", "XF86Tools": "⚙"}"#).unwrap();
+        let map = IconMap::new(IconSet::Unicode, read_overrides(&path).unwrap());
+        assert_eq!(map.icon("SUPER"), "Win");
+        assert_eq!(map.icon("xf86tools"), "⚙");
+        assert_eq!(map.icon("shift"), "⇧");
+
+        fs::write(&path, r#"["super"]"#).unwrap();
+        assert!(read_overrides(&path).is_err());
+    }
 }
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod input_tests {
+    use crate::hyprland::bind_source::{BindSource, FileSource};
+    use crate::hyprland::error::SourceErrorReason;
+    use crate::hyprland::parser::{parse_bind_input, parse_binds_output_with_warnings};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const HYPRCTL: &str = r"bind
+	modmask: 64
+	submap:
+	key: Q
+	keycode: 0
+	catchall: false
+	description:
+	dispatcher: killactive
+	arg:";
+
+    /// Verifies JSON produced by `to_json` is read back unchanged
+    #[test]
+    fn test_parse_json_input() {
+        let original = parse_binds_output_with_warnings(HYPRCTL).keybindings;
+        let json = format!("\n  {}", original.to_json().unwrap());
+
+        let parsed = parse_bind_input(&json).unwrap().keybindings;
+        assert_eq!(parsed.to_json().unwrap(), original.to_json().unwrap());
+    }
+
+    /// Verifies hyprctl text is detected and parsed
+    #[test]
+    fn test_parse_hyprctl_input() {
+        let parsed = parse_bind_input(HYPRCTL).unwrap().keybindings;
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.entries[0].key(), "Q");
+        assert_eq!(parsed.entries[0].modifiers, "SUPER");
+    }
+
+    /// Ensures broken JSON is reported instead of yielding no binds
+    #[test]
+    fn test_parse_invalid_json_input() {
+        assert!(parse_bind_input("{\"entries\": [").is_err());
+    }
+
+    /// Tests file input is re-read on every load
+    #[test]
+    fn test_file_input_reload() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("binds.txt");
+        fs::write(&path, HYPRCTL).unwrap();
+
+        let input = FileSource::new(Some(&path)).unwrap();
+        assert!(!input.is_live());
+        assert_eq!(input.load().unwrap().keybindings.entries.len(), 1);
+
+        fs::write(&path, "").unwrap();
+        assert!(input.load().unwrap().keybindings.entries.is_empty());
+    }
+
+    /// Checks missing files and invalid contents are load errors
+    #[test]
+    fn test_input_errors() {
+        let missing = FileSource::new(Some(Path::new("/nonexistent/binds.json"))).unwrap();
+        assert!(matches!(missing.load(), Err(SourceErrorReason::Io(_))));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("binds.json");
+        fs::write(&path, "{ not json").unwrap();
+        let invalid = FileSource::new(Some(&path)).unwrap();
+        assert!(matches!(
+            invalid.load(),
+            Err(SourceErrorReason::InvalidData(_))
+        ));
+
+        let none = FileSource::new(None).unwrap();
+        assert!(matches!(
+            none.load(),
+            Err(SourceErrorReason::Unavailable(_))
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod instances_tests {
+    use crate::hyprland::bind_source::{
+        BindSource, HyprctlSource, SocketSource, SourceChain, SourceKind,
+    };
+    use crate::hyprland::diff::BindDiff;
+    use crate::hyprland::models::{ConfigLine, KeyBindings};
+    use crate::hyprland::parser::parse_config_lines;
+    use crate::hyprland::source::list_instances_in;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn config_binds(text: &str) -> KeyBindings {
+        let lines: Vec<ConfigLine> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| ConfigLine {
+                path: PathBuf::new(),
+                number: i + 1,
+                text: line.to_string(),
+            })
+            .collect();
+        KeyBindings {
+            entries: parse_config_lines(&lines)
+                .into_iter()
+                .map(|bind| bind.entry)
+                .collect(),
+        }
+    }
+
+    /// Verifies instances are found by their socket and read from the lock file
+    #[test]
+    fn test_list_instances() {
+        let dir = TempDir::new().unwrap();
+        for (signature, lock) in [
+            ("b_1700000002_2", Some("4242\nwayland-2\n")),
+            ("a_1700000001_1", None),
+        ] {
+            let instance = dir.path().join(signature);
+            fs::create_dir(&instance).unwrap();
+            fs::write(instance.join(".socket.sock"), "").unwrap();
+            if let Some(lock) = lock {
+                fs::write(instance.join("hyprland.lock"), lock).unwrap();
+            }
+        }
+        fs::create_dir(dir.path().join("stale")).unwrap();
+
+        let instances = list_instances_in(dir.path());
+        let signatures: Vec<&str> = instances.iter().map(|i| i.signature.as_str()).collect();
+        assert_eq!(signatures, vec!["a_1700000001_1", "b_1700000002_2"]);
+
+        assert_eq!(instances[0].pid, None);
+        assert_eq!(instances[0].label(), "a_1700000001_1");
+        assert_eq!(instances[1].pid, Some(4242));
+        assert_eq!(instances[1].label(), "wayland-2 (b_1700000002_2)");
+
+        assert_eq!(list_instances_in(&dir.path().join("missing")), Vec::new());
+    }
+
+    /// Ensures the chosen instance is kept by the chain and used by live sources
+    #[test]
+    fn test_instance_chain() {
+        let mut chain = SourceChain::from_kinds(&SourceChain::DEFAULT, None, None).unwrap();
+        assert_eq!(chain.instance(), None);
+        chain.set_instance(Some("no_such_instance"));
+        assert_eq!(chain.instance(), Some("no_such_instance"));
+
+        let mut hyprctl = HyprctlSource::default();
+        assert!(hyprctl.is_live());
+        hyprctl.set_instance(Some("no_such_instance"));
+        assert!(!hyprctl.is_live());
+        let mut socket = SocketSource::default();
+        socket.set_instance(Some("no_such_instance"));
+        assert!(!socket.is_live());
+
+        let chain = SourceChain::for_instance("no_such_instance");
+        let err = chain.load().unwrap_err();
+        let kinds: Vec<SourceKind> = err.errors.iter().map(|e| e.source).collect();
+        assert_eq!(kinds, vec![SourceKind::Hyprctl, SourceKind::Socket]);
+        assert!(err.errors[1].to_string().contains("no_such_instance"));
+    }
+
+    /// Ensures the local config never stands in for another instance's binds
+    #[test]
+    fn test_instance_skips_local_config() {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("hyprland.conf");
+        fs::write(&config, "bind = SUPER, Q, killactive\n").unwrap();
+        let mut chain =
+            SourceChain::from_kinds(&[SourceKind::Config], None, Some(&config)).unwrap();
+        assert!(chain.load().is_ok());
+
+        chain.set_instance(Some("no_such_instance"));
+        let err = chain.load().unwrap_err();
+        assert_eq!(err.errors.len(), 1);
+        assert_eq!(err.errors[0].source, SourceKind::Config);
+        assert!(err.errors[0].to_string().contains("no_such_instance"));
+    }
+
+    /// Checks binds of two instances are lined up by combo
+    #[test]
+    fn test_side_by_side() {
+        let left = config_binds(
+            "bind = SUPER, Q, killactive
+bind = SUPER, Return, exec, kitty
+bind = SUPER, F, exec, thunar",
+        );
+        let right = config_binds(
+            "bind = SUPER, Return, exec, foot
+bind = SUPER, Q, killactive
+bind = SUPER, B, exec, firefox",
+        );
+        let pairs = BindDiff::side_by_side(&left, &right);
+
+        let rows: Vec<(Option<&str>, Option<&str>, bool)> = pairs
+            .iter()
+            .map(|p| {
+                (
+                    p.left.as_ref().map(|e| e.command.as_str()),
+                    p.right.as_ref().map(|e| e.command.as_str()),
+                    p.is_same(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("killactive"), Some("killactive"), true),
+                (Some("exec kitty"), Some("exec foot"), false),
+                (Some("exec thunar"), None, false),
+                (None, Some("exec firefox"), false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod key_tests {
+    use crate::hyprland::key::{Key, Keymap, Wheel};
+    use crate::hyprland::parser::{parse_binds_output_with_warnings, parse_hyprctl_json};
+    use crate::hyprland::source::option_from_json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// XKB data with a few keys of the US layout, laid out like `/usr/share/X11/xkb`
+    fn xkb_root() -> TempDir {
+        let root = TempDir::new().unwrap();
+        let keycodes = root.path().join("keycodes");
+        let symbols = root.path().join("symbols");
+        fs::create_dir_all(&keycodes).unwrap();
+        fs::create_dir_all(&symbols).unwrap();
+        fs::write(
+            keycodes.join("evdev"),
+            r#"// evdev keycodes
+default xkb_keycodes "evdev" {
+    minimum = 8;
+    <ESC> = 9;
+    <AE01> = 10;
+    <AD01> = 24;
+    <AD11> = 34;
+    <RTRN> = 36;
+    <VOL+> = 123;
+    # <AD02> = 25;
+    alias <LatQ> = <AD01>;
+};
+"#,
+        )
+        .unwrap();
+        fs::write(
+            symbols.join("pc"),
+            r#"default partial alphanumeric_keys modifier_keys
+xkb_symbols "pc105" {
+    key <ESC>  {	[ Escape		]	};
+    key  <RTRN> {	[ Return		]	};
+};
+"#,
+        )
+        .unwrap();
+        fs::write(
+            symbols.join("us"),
+            r#"default partial alphanumeric_keys
+xkb_symbols "basic" {
+    name[Group1]= "English (US)";
+    key <AE01> {	[	  1,	exclam 		]	};
+    key <LatQ> {	[	  q,	Q 		]	};
+    key <AD11> {	[ bracketleft,	braceleft	]	};
+};
+
+partial alphanumeric_keys
+xkb_symbols "dvorak" {
+    include "us(basic)"
+    key <AD01> {	[  apostrophe,	quotedbl	]	};
+    key <AD11> { type[Group1] = "TWO_LEVEL", symbols[Group1] = [ slash, question ] };
+};
+"#,
+        )
+        .unwrap();
+        fs::write(
+            symbols.join("inet"),
+            r#"partial alphanumeric_keys
+xkb_symbols "evdev" {
+    key <VOL+>   {       [ XF86AudioRaiseVolume  ]       };
+};
+"#,
+        )
+        .unwrap();
+        root
+    }
+
+    /// Tests keycodes are resolved through the keycodes, layout and its includes
+    #[test]
+    fn test_keymap_from_xkb() {
+        let root = xkb_root();
+        let us = Keymap::from_xkb(root.path(), "us", None);
+        assert_eq!(us.sym(9), Some("Escape"));
+        assert_eq!(us.sym(10), Some("1"));
+        assert_eq!(us.sym(24), Some("q"));
+        assert_eq!(us.sym(34), Some("bracketleft"));
+        assert_eq!(us.sym(36), Some("Return"));
+        assert_eq!(us.sym(123), Some("XF86AudioRaiseVolume"));
+        assert_eq!(us.sym(25), None);
+
+        let dvorak = Keymap::from_xkb(root.path(), "us", Some("dvorak"));
+        assert_eq!(dvorak.sym(10), Some("1"));
+        assert_eq!(dvorak.sym(24), Some("apostrophe"));
+        assert_eq!(dvorak.sym(34), Some("slash"));
+
+        let key = Key::parse_with("code:24", &us);
+        assert_eq!(key.name(), "q");
+        assert_eq!(key.label(), "Q");
+        assert!(key.same_key(&Key::parse("Q")));
+        assert!(!key.same_key(&Key::parse_with("code:24", &dvorak)));
+    }
+
+    /// Checks a compiled keymap file is read, following its includes
+    #[test]
+    fn test_keymap_from_keymap_file() {
+        let root = xkb_root();
+        let keymap = Keymap::from_keymap(
+            r#"xkb_keymap {
+    xkb_keycodes "custom" { <AD01> = 24; <AB01> = 52; alias <LatQ> = <AD01>; };
+    xkb_types "complete" { include "complete" };
+    xkb_symbols "custom" {
+        include "pc+us"
+        key <AB01> { [ z, Z ] };
+    };
+};"#,
+            root.path(),
+        );
+        assert_eq!(keymap.sym(24), Some("q"));
+        assert_eq!(keymap.sym(52), Some("z"));
+        assert_eq!(keymap.sym(36), None);
+    }
+
+    /// Verifies names are normalised and labelled, whatever case a bind uses
+    #[test]
+    fn test_names_and_labels() {
+        let keymap = Keymap::default();
+        let cases = [
+            ("q", "q", "Q"),
+            ("RETURN", "Return", "Return"),
+            ("bracketleft", "bracketleft", "["),
+            ("f5", "F5", "F5"),
+            ("kp_add", "KP_Add", "KP +"),
+            ("xf86audiomute", "XF86audiomute", "XF86audiomute"),
+            ("mouse:272", "mouse:272", "Mouse Left"),
+            ("mouse:280", "mouse:280", "Mouse 280"),
+            ("mouse_down", "mouse_down", "Wheel Down"),
+            ("code:999", "code:999", "code:999"),
+        ];
+        for (raw, name, label) in cases {
+            let key = Key::parse_with(raw, &keymap);
+            assert_eq!(key.name(), name, "{raw}");
+            assert_eq!(key.label(), label, "{raw}");
+        }
+        assert_eq!(Key::parse("mouse_up"), Key::Wheel(Wheel::Up));
+        assert_eq!(Key::parse("XF86AudioMute").name(), "XF86AudioMute");
+    }
+
+    /// Ensures keys sort letters, digits and F-keys first and mouse and keycodes last
+    #[test]
+    fn test_sort_order() {
+        let keymap = Keymap::default();
+        let mut keys: Vec<Key> = [
+            "code:999",
+            "mouse_up",
+            "mouse:273",
+            "mouse:272",
+            "XF86AudioMute",
+            "KP_1",
+            "comma",
+            "Escape",
+            "Return",
+            "F10",
+            "F2",
+            "2",
+            "1",
+            "b",
+            "A",
+        ]
+        .iter()
+        .map(|raw| Key::parse_with(raw, &keymap))
+        .collect();
+        keys.sort_by_key(Key::sort_key);
+        let labels: Vec<String> = keys.iter().map(Key::label).collect();
+        assert_eq!(
+            labels,
+            [
+                "A",
+                "B",
+                "1",
+                "2",
+                "F2",
+                "F10",
+                "Return",
+                "Escape",
+                ",",
+                "KP 1",
+                "XF86AudioMute",
+                "Mouse Left",
+                "Mouse Right",
+                "Wheel Up",
+                "code:999",
+            ]
+        );
+    }
+
+    /// Tests `hyprctl` binds by keycode keep the keycode as `code:N`
+    #[test]
+    fn test_hyprctl_keycodes() {
+        let json = r#"[
+            {"modmask": 64, "key": "", "keycode": 24, "dispatcher": "killactive", "arg": ""},
+            {"modmask": 64, "key": "Q", "keycode": 0, "dispatcher": "exec", "arg": "kitty"}
+        ]"#;
+        let kb = parse_hyprctl_json(json).unwrap();
+        assert_eq!(kb.entries[0].key(), "code:24");
+        assert_eq!(kb.entries[1].key(), "Q");
+
+        let text = "bind\n\tmodmask: 64\n\tsubmap: \n\tkey: \n\tkeycode: 10\n\tdispatcher: workspace\n\targ: 1\n\n";
+        let kb = parse_binds_output_with_warnings(text).keybindings;
+        assert_eq!(kb.entries[0].key(), "code:10");
+    }
+
+    /// Ensures options `hyprctl getoption` reports as unset don't become a layout or variant
+    #[test]
+    fn test_unset_layout_options() {
+        let unset = r#"{"option": "input:kb_variant", "str": "[[EMPTY]]", "set": false}"#;
+        assert_eq!(option_from_json(unset), None);
+        let empty = r#"{"option": "input:kb_file", "str": "", "set": false}"#;
+        assert_eq!(option_from_json(empty), None);
+        let layout = r#"{"option": "input:kb_layout", "str": "us,de", "set": true}"#;
+        assert_eq!(option_from_json(layout), Some("us,de".to_string()));
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod launcher_tests {
+    use crate::hyprland::launcher::{
+        Launcher, LauncherProfile, LauncherSettings, exec_icon, render_template,
+    };
+    use crate::hyprland::models::{KeyBindEntry, KeyBindings};
+
+    fn entry(modifiers: &str, key: &str, command: &str, description: &str) -> KeyBindEntry {
+        let (dispatcher, arg) = command.split_once(' ').unwrap_or((command, ""));
+        let mut entry = KeyBindEntry::new(
+            modifiers.to_string(),
+            key.to_string(),
+            command.to_string(),
+            description.to_string(),
+        );
+        entry.dispatcher = dispatcher.to_string();
+        entry.arg = arg.to_string();
+        entry
+    }
+
+    fn keybindings() -> KeyBindings {
+        KeyBindings {
+            entries: vec![
+                entry("SUPER", "Return", "exec kitty", "Terminal"),
+                entry("SUPER+SHIFT", "Q", "killactive", ""),
+            ],
+        }
+    }
+
+    fn profile(launcher: Launcher, settings: &LauncherSettings) -> LauncherProfile {
+        LauncherProfile::new(launcher, settings)
+    }
+
+    /// Checks templates fill placeholders and escapes
+    #[test]
+    fn test_render_template() {
+        let bind = entry("SUPER", "Q", "exec firefox -P work", "");
+        let line = render_template(
+            "{keys}\\t{text}|{description}|{dispatcher}|{arg}|{index}|{unknown}",
+            &bind,
+            "SUPER+Q",
+            3,
+        );
+        assert_eq!(
+            line,
+            "SUPER+Q\texec firefox -P work||exec|firefox -P work|3|{unknown}"
+        );
+    }
+
+    /// Ensures placeholders inside substituted values are not expanded again
+    #[test]
+    fn test_render_template_single_pass() {
+        let bind = entry(
+            "SUPER",
+            "Q",
+            "exec notify-send {index}",
+            "Show {command} \\t",
+        );
+        let line = render_template("{description}|{command}\\n", &bind, "SUPER+Q", 3);
+        assert_eq!(line, "Show {command} \\t|exec notify-send {index}\n");
+    }
+
+    /// Verifies plain mode, index suffix and custom templates
+    #[test]
+    fn test_plain_and_index() {
+        let settings = LauncherSettings {
+            template: Some("{keys}\\t{command}".to_string()),
+            plain: true,
+            index: true,
+        };
+        let out = keybindings().to_launcher(&profile(Launcher::Wofi, &settings));
+        assert_eq!(
+            out,
+            "SUPER+Return\texec kitty #0\nSUPER+SHIFT+Q\tkillactive #1"
+        );
+    }
+
+    /// Tests rofi and fuzzel row options
+    #[test]
+    fn test_row_options() {
+        let settings = LauncherSettings {
+            plain: true,
+            ..LauncherSettings::default()
+        };
+        let rofi = keybindings().to_launcher(&profile(Launcher::Rofi, &settings));
+        let lines: Vec<&str> = rofi.lines().collect();
+        assert_eq!(
+            lines[0],
+            "SUPER+Return  Terminal\0icon\x1fkitty\x1fmeta\x1fexec kitty\x1finfo\x1f0"
+        );
+        assert_eq!(
+            lines[1],
+            "SUPER+SHIFT+Q  killactive\0meta\x1fkillactive\x1finfo\x1f1"
+        );
+
+        let fuzzel = keybindings().to_launcher(&profile(Launcher::Fuzzel, &settings));
+        assert_eq!(
+            fuzzel,
+            "SUPER+Return  Terminal\0icon\x1fkitty\nSUPER+SHIFT+Q  killactive"
+        );
+        let dmenu = keybindings().to_launcher(&profile(Launcher::Dmenu, &settings));
+        assert!(!dmenu.contains('\0'));
+    }
+
+    /// Ensures icon names come from the program an exec bind starts
+    #[test]
+    fn test_exec_icon() {
+        let icon = |command: &str| exec_icon(&entry("", "A", command, ""));
+        assert_eq!(icon("exec firefox"), Some("firefox".to_string()));
+        assert_eq!(
+            icon("exec [workspace 2 silent] /usr/bin/thunar ~"),
+            Some("thunar".to_string())
+        );
+        assert_eq!(icon("exec GDK_SCALE=2 steam"), Some("steam".to_string()));
+        assert_eq!(icon("killactive"), None);
+    }
+}
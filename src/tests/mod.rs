@@ -1,7 +1,30 @@
+mod annotations;
+mod bind_source;
+mod category;
+mod cli;
+mod color;
+mod combo;
+mod completions;
+mod config_edit;
 mod config_roundtrip;
+mod css;
+mod diff;
+mod fontconfig;
+mod hyprconf;
 mod icons;
+mod input;
+mod instances;
+mod key;
+mod launcher;
 mod models;
+mod parse_warnings;
 mod parser;
 mod parser_edge;
 mod source_error;
+mod sway;
+mod system_theme;
 mod table;
+mod terminal;
+mod theme_import;
+mod themes;
+mod watch;
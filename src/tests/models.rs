@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod models_tests {
+    use crate::hyprland::launcher::{Launcher, LauncherProfile, LauncherSettings};
     use crate::hyprland::{KeyBindEntry, KeyBindings};
 
     /// Validates dmenu format export with icon mapping
@@ -38,7 +39,10 @@ mod models_tests {
             entries: vec![entry1, entry2, entry3, entry4],
         };
 
-        let dmenu = kb.to_dmenu();
+        let dmenu = kb.to_launcher(&LauncherProfile::new(
+            Launcher::Dmenu,
+            &LauncherSettings::default(),
+        ));
         let lines: Vec<&str> = dmenu.lines().collect();
 
         // 1. No modifier, icon only
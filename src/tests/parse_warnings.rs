@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod parse_warnings_tests {
+    use crate::hyprland::bind_source::{BindSource, ConfigSource};
+    use crate::hyprland::models::ConfigLine;
+    use crate::hyprland::parser::{
+        parse_binds_output_with_warnings, parse_config_lines_with_warnings,
+    };
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    const HYPRCTL: &str = "bind
+\tmodmask: 64
+\tkey: Q
+\tdispatcher: killactive
+
+bind
+\tmodmask: super
+\tkey: W
+\tdispatcher: exec
+
+bind
+\tmodmask: 64
+\tdispatcher: exec
+
+bind
+\tmodmask: 0
+\tkey: F1
+\tdispatcher: exec
+";
+
+    fn lines(text: &str) -> Vec<ConfigLine> {
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| ConfigLine {
+                path: PathBuf::from("binds.conf"),
+                number: i + 1,
+                text: line.to_string(),
+            })
+            .collect()
+    }
+
+    /// Verifies skipped hyprctl blocks are reported with line, reason and text
+    #[test]
+    fn test_hyprctl_block_warnings() {
+        let parsed = parse_binds_output_with_warnings(HYPRCTL);
+        assert_eq!(parsed.keybindings.entries.len(), 2);
+
+        let warnings: Vec<(usize, &str)> = parsed
+            .warnings
+            .iter()
+            .map(|w| (w.line, w.reason.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![(6, "invalid modmask 'super'"), (11, "missing key")]
+        );
+        assert!(parsed.warnings[0].raw.starts_with("bind\n"));
+        assert!(parsed.warnings[0].raw.contains("key: W"));
+        assert_eq!(parsed.warnings[1].to_string(), "line 11: missing key");
+    }
+
+    /// Ensures valid output yields no warnings
+    #[test]
+    fn test_no_warnings() {
+        let parsed = parse_binds_output_with_warnings(
+            "\n\nbind\n\tmodmask: 0\n\tkey: A\n\tdispatcher: exec\n\n",
+        );
+        assert_eq!(parsed.keybindings.entries.len(), 1);
+        assert_eq!(parsed.warnings, Vec::new());
+    }
+
+    /// Checks config lines that look like binds but cannot be parsed are reported
+    #[test]
+    fn test_config_warnings() {
+        let conf = lines(
+            "bind = SUPER, Q, killactive
+bindx = SUPER, W, exec, foo
+bindd = SUPER, E, exec
+bind = SUPER
+binds:scroll_event_delay = 0
+bind = SUPER, , exec, foo",
+        );
+        let (binds, warnings) = parse_config_lines_with_warnings(&conf);
+        assert_eq!(binds.len(), 1);
+
+        let found: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            vec![
+                "binds.conf:2: unknown bind flags 'x'",
+                "binds.conf:3: missing dispatcher",
+                "binds.conf:4: missing key",
+                "binds.conf:6: empty key",
+            ]
+        );
+        assert_eq!(warnings[1].raw, "bindd = SUPER, E, exec");
+    }
+
+    /// Tests the config source passes parser warnings on
+    #[test]
+    fn test_config_source_warnings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("hyprland.conf");
+        fs::write(&path, "bind = SUPER, Q, killactive\nbind = SUPER\n").unwrap();
+
+        let parsed = ConfigSource { path: path.clone() }.load().unwrap();
+        assert_eq!(parsed.keybindings.entries.len(), 1);
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].path.as_deref(), Some(path.as_path()));
+        assert_eq!(parsed.warnings[0].line, 2);
+    }
+}
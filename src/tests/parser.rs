@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod parser_tests {
-    use crate::hyprland::parser::parse_binds_output;
+    use crate::hyprland::parser::parse_binds_output_with_warnings;
 
     /// Tests modmask bitmask to human-readable string conversion
     #[test]
@@ -15,7 +15,7 @@ mod parser_tests {
 	dispatcher: exec
 	arg: echo super";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
         assert_eq!(kb.entries[0].modifiers, "SUPER");
     }
@@ -33,7 +33,7 @@ mod parser_tests {
 	dispatcher: killactive
 	arg: ";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
         assert_eq!(kb.entries[0].modifiers, "SUPER+SHIFT");
         assert_eq!(kb.entries[0].description, "Kill window");
@@ -52,10 +52,10 @@ mod parser_tests {
 	dispatcher: exec
 	arg: kitty";
 
-        let kb = parse_binds_output(block);
+        let kb = parse_binds_output_with_warnings(block).keybindings;
         assert_eq!(kb.entries.len(), 1);
         assert_eq!(kb.entries[0].modifiers, "SUPER");
-        assert_eq!(kb.entries[0].key, "Return");
+        assert_eq!(kb.entries[0].key(), "Return");
         assert_eq!(kb.entries[0].command, "exec kitty");
         assert_eq!(kb.entries[0].description, "Terminal");
     }
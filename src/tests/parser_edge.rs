@@ -1,11 +1,11 @@
 #[cfg(test)]
 mod parser_edge_tests {
-    use crate::hyprland::parser::parse_binds_output;
+    use crate::hyprland::parser::parse_binds_output_with_warnings;
 
     /// Ensures parser handles empty input without crashing
     #[test]
     fn test_empty_input() {
-        let kb = parse_binds_output("");
+        let kb = parse_binds_output_with_warnings("").keybindings;
         assert_eq!(kb.entries.len(), 0);
     }
 
@@ -33,10 +33,10 @@ bind
 	dispatcher: killactive
 	arg: ";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 2);
-        assert_eq!(kb.entries[0].key, "A");
-        assert_eq!(kb.entries[1].key, "B");
+        assert_eq!(kb.entries[0].key(), "A");
+        assert_eq!(kb.entries[1].key(), "B");
     }
 
     /// Verifies parser rejects bind blocks missing required fields (modmask, dispatcher)
@@ -47,7 +47,7 @@ bind
 	key: A
 	description: Missing modmask";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 0);
     }
 
@@ -64,7 +64,7 @@ bind
 	dispatcher: exec
 	arg: test";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 0);
     }
 
@@ -83,9 +83,9 @@ bind
 	unknown_field: should be ignored
 	another_unknown: also ignored";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
-        assert_eq!(kb.entries[0].key, "A");
+        assert_eq!(kb.entries[0].key(), "A");
     }
 
     /// Validates parser against actual hyprctl binds output format
@@ -121,7 +121,7 @@ bindd
 	dispatcher: exec
 	arg: kitty --config ~/.config/kitty/dev.conf";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 3);
         assert_eq!(kb.entries[0].modifiers, "SUPER");
         assert_eq!(kb.entries[0].command, "exec kitty");
@@ -142,7 +142,7 @@ bindd
 	dispatcher: exec
 	arg: test";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
     }
 
@@ -159,9 +159,9 @@ bindd
 	dispatcher: 
 	arg: ";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
-        assert!(kb.entries[0].command.is_empty());
+        assert_eq!(kb.entries[0].command, "");
     }
 
     /// Verifies parser preserves special characters in description fields
@@ -177,7 +177,7 @@ bindd
 	dispatcher: exec
 	arg: test"#;
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
         assert!(kb.entries[0].description.contains("quotes"));
     }
@@ -187,7 +187,7 @@ bindd
     fn test_whitespace_handling() {
         let sample = "bind\n\tmodmask: 64\n\tsubmap: \n\tkey: A\n\tkeycode: 0\n\tcatchall: false\n\tdescription:    Spaces   \n\tdispatcher: exec\n\targ:   test  ";
 
-        let kb = parse_binds_output(sample);
+        let kb = parse_binds_output_with_warnings(sample).keybindings;
         assert_eq!(kb.entries.len(), 1);
         assert_eq!(kb.entries[0].description.trim(), "Spaces");
     }
@@ -2,14 +2,10 @@
 mod source_error_tests {
     use crate::hyprland::source::fetch_hyprctl_binds;
 
-    /// Validates hyprctl command execution returns string output
+    /// Validates hyprctl command execution returns a result instead of panicking,
+    /// whether or not Hyprland is running
     #[test]
     fn test_hyprctl_success_returns_string() {
-        match fetch_hyprctl_binds() {
-            Ok(output) => {
-                assert!(!output.is_empty() || output.is_empty());
-            }
-            Err(_) => {}
-        }
+        let _ = fetch_hyprctl_binds(None);
     }
 }
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod sway_tests {
+    use crate::hyprland::bind_source::{BindSource, SourceChain, SourceKind, SwaySource};
+    use crate::hyprland::models::ConfigLine;
+    use crate::hyprland::source::read_sway_config_lines;
+    use crate::hyprland::sway::{parse_include_directive, parse_sway_lines};
+    use std::path::{Path, PathBuf};
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/tests/fixtures")
+            .join(name)
+    }
+
+    fn lines(text: &str) -> Vec<ConfigLine> {
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| ConfigLine {
+                path: PathBuf::new(),
+                number: i + 1,
+                text: line.to_string(),
+            })
+            .collect()
+    }
+
+    /// Verifies the Sway fixture yields every bind with modes, variables and includes
+    #[test]
+    fn test_sway_fixture() {
+        let parsed = SwaySource {
+            path: fixture("sway/config"),
+        }
+        .load()
+        .unwrap();
+
+        let binds: Vec<(&str, &str, &str, &str)> = parsed
+            .keybindings
+            .entries
+            .iter()
+            .map(|e| {
+                (
+                    e.submap.as_str(),
+                    e.modifiers.as_str(),
+                    e.key(),
+                    e.command.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            binds,
+            vec![
+                ("", "SUPER", "Return", "exec foot"),
+                ("", "SUPER+SHIFT", "q", "kill"),
+                ("", "ALT", "Tab", "focus next"),
+                ("", "SUPER", "code:36", "exec notify-send \"keycode 36\""),
+                ("", "SUPER", "r", "mode \"resize\""),
+                ("", "SUPER", "f", "fullscreen toggle"),
+                ("", "SUPER+SHIFT", "space", "floating toggle"),
+                ("resize", "", "h", "resize shrink width 10px"),
+                ("resize", "", "Return", "mode \"default\""),
+                (
+                    "",
+                    "",
+                    "XF86AudioMute",
+                    "exec pactl set-sink-mute @DEFAULT_SINK@ toggle"
+                ),
+            ]
+        );
+
+        let kill = &parsed.keybindings.entries[1];
+        assert!(kill.flags.release && kill.flags.locked);
+        assert_eq!(kill.dispatcher, "kill");
+        assert_eq!(parsed.keybindings.entries[0].arg, "foot");
+
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].reason, "missing command");
+        assert_eq!(parsed.warnings[0].raw, "bindsym $mod+x");
+        assert_eq!(
+            parsed.warnings[0].path.as_deref(),
+            Some(fixture("sway/config").as_path())
+        );
+    }
+
+    /// Checks an i3 config with quoted mode names and bindcode
+    #[test]
+    fn test_i3_fixture() {
+        let chain = SourceChain::from_kinds(&[SourceKind::Sway], None, Some(&fixture("i3/config")))
+            .unwrap();
+        let loaded = chain.load().unwrap();
+        assert_eq!(loaded.source, SourceKind::Sway);
+        assert!(!loaded.live);
+
+        let entries = &loaded.keybindings.entries;
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0].modifiers, "ALT");
+        assert_eq!(entries[0].arg, "--no-startup-id dmenu_run");
+        assert_eq!(entries[2].submap, "Mod1+r: resize");
+        assert_eq!(entries[3].key(), "Escape");
+        assert_eq!(entries[4].key(), "code:133");
+        assert_eq!(entries[4].submap, "");
+    }
+
+    /// Ensures included files are inlined at their include line
+    #[test]
+    fn test_sway_include() {
+        let lines = read_sway_config_lines(&fixture("sway/config")).unwrap();
+        let included = lines
+            .iter()
+            .position(|l| l.text.starts_with("bindsym XF86AudioMute"))
+            .unwrap();
+        assert!(lines[included].path.ends_with("extra/media.conf"));
+        assert_eq!(lines[included + 1].text, "");
+        assert_eq!(lines.last().unwrap().text, "bindsym $mod+x");
+
+        assert_eq!(
+            parse_include_directive("include \"~/.config/sway/a b\""),
+            Some("~/.config/sway/a b".to_string())
+        );
+        assert_eq!(parse_include_directive("includes foo"), None);
+        assert_eq!(parse_include_directive("include"), None);
+    }
+
+    /// Tests variable redefinition, bad keycodes and unclosed continuation
+    #[test]
+    fn test_sway_edge_cases() {
+        let parsed = parse_sway_lines(&lines(
+            "set $mod Mod4
+set $mod Mod1
+bindsym $mod+a exec a
+bindcode $mod+abc exec b
+set nothing
+mode default {
+bindsym b exec c
+}",
+        ));
+        let entries = &parsed.keybindings.entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].modifiers, "ALT");
+        assert_eq!(entries[1].submap, "");
+
+        let reasons: Vec<&str> = parsed.warnings.iter().map(|w| w.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec!["invalid keycode 'abc'", "expected `set $name value`"]
+        );
+        assert_eq!(parsed.warnings[0].line, 4);
+    }
+}
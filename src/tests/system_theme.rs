@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod system_theme_tests {
+    use crate::ui::system_theme::{
+        ColorScheme, SettingValue, SettingsSource, affects_scheme, detect, scheme_from_gtk_theme,
+    };
+    use std::collections::HashMap;
+
+    /// Settings source answering from a map, in place of the portal
+    #[derive(Default)]
+    struct MockSettings {
+        settings: HashMap<(&'static str, &'static str), SettingValue>,
+    }
+
+    impl MockSettings {
+        fn with(mut self, namespace: &'static str, key: &'static str, value: SettingValue) -> Self {
+            self.settings.insert((namespace, key), value);
+            self
+        }
+    }
+
+    impl SettingsSource for MockSettings {
+        fn read(&self, namespace: &str, key: &str) -> Option<SettingValue> {
+            self.settings
+                .iter()
+                .find(|((n, k), _)| *n == namespace && *k == key)
+                .map(|(_, v)| v.clone())
+        }
+    }
+
+    fn color_scheme(value: u32) -> MockSettings {
+        MockSettings::default().with(
+            "org.freedesktop.appearance",
+            "color-scheme",
+            SettingValue::U32(value),
+        )
+    }
+
+    /// Checks the portal's colour scheme wins over GTK themes
+    #[test]
+    fn test_portal_color_scheme() {
+        let dark = color_scheme(1);
+        let light = color_scheme(2).with(
+            "org.gnome.desktop.interface",
+            "gtk-theme",
+            SettingValue::Str("Adwaita-dark".to_string()),
+        );
+        assert_eq!(
+            detect(Some(&dark), Some("Adwaita")),
+            Some(ColorScheme::Dark)
+        );
+        assert_eq!(
+            detect(Some(&light), Some("Adwaita:dark")),
+            Some(ColorScheme::Light)
+        );
+    }
+
+    /// Tests the GTK theme decides when there is no preference
+    #[test]
+    fn test_gtk_theme_fallback() {
+        let no_preference = color_scheme(0).with(
+            "org.gnome.desktop.interface",
+            "gtk-theme",
+            SettingValue::Str("Yaru-dark".to_string()),
+        );
+        assert_eq!(
+            detect(Some(&no_preference), Some("Adwaita")),
+            Some(ColorScheme::Dark)
+        );
+
+        let empty = MockSettings::default();
+        assert_eq!(
+            detect(Some(&empty), Some("Adwaita:dark")),
+            Some(ColorScheme::Dark)
+        );
+        assert_eq!(detect(None, Some("Breeze")), Some(ColorScheme::Light));
+        assert_eq!(detect(None, Some("")), None);
+        assert_eq!(detect(Some(&empty), None), None);
+    }
+
+    /// Verifies GTK theme names and changed settings are recognised
+    #[test]
+    fn test_theme_names_and_changes() {
+        assert_eq!(scheme_from_gtk_theme("Adwaita:dark"), ColorScheme::Dark);
+        assert_eq!(
+            scheme_from_gtk_theme("Catppuccin-Mocha-Dark"),
+            ColorScheme::Dark
+        );
+        assert_eq!(scheme_from_gtk_theme("Adwaita"), ColorScheme::Light);
+        assert!(affects_scheme("org.freedesktop.appearance", "color-scheme"));
+        assert!(affects_scheme("org.gnome.desktop.interface", "gtk-theme"));
+        assert!(!affects_scheme(
+            "org.freedesktop.appearance",
+            "accent-color"
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod terminal_tests {
+    use crate::hyprland::models::{KeyBindEntry, KeyBindings};
+    use crate::hyprland::terminal::TableStyle;
+    use unicode_width::UnicodeWidthStr;
+
+    const PLAIN: TableStyle = TableStyle {
+        width: None,
+        color: false,
+        icons: false,
+        category: false,
+    };
+
+    fn keybindings() -> KeyBindings {
+        let mut kb = KeyBindings::new();
+        let mut reset = KeyBindEntry::new(
+            String::new(),
+            "escape".to_string(),
+            "submap reset".to_string(),
+            String::new(),
+        );
+        reset.submap = "resize".to_string();
+        kb.add_entry(reset);
+        kb.add_entry(KeyBindEntry::new(
+            "SUPER".to_string(),
+            "Return".to_string(),
+            "exec kitty --single-instance".to_string(),
+            "Terminal".to_string(),
+        ));
+        kb.add_entry(KeyBindEntry::new(
+            "SUPER+SHIFT".to_string(),
+            "Q".to_string(),
+            "killactive".to_string(),
+            String::new(),
+        ));
+        kb
+    }
+
+    /// Checks binds are aligned in columns and grouped by submap, global first
+    #[test]
+    fn test_table_layout() {
+        let table = keybindings().to_table(&PLAIN);
+        assert_eq!(
+            table,
+            "Global
+KEYS               DESCRIPTION  COMMAND
+SUPER + Return     Terminal     exec kitty --single-instance
+SUPER + SHIFT + Q               killactive
+
+Submap: resize
+KEYS               DESCRIPTION  COMMAND
+Escape                          submap reset
+"
+        );
+    }
+
+    /// Ensures rows are cut to the terminal width
+    #[test]
+    fn test_table_fits_width() {
+        let style = TableStyle {
+            width: Some(44),
+            ..PLAIN
+        };
+        let table = keybindings().to_table(&style);
+        assert!(table.lines().all(|line| line.width() <= 44), "{table}");
+        assert!(table.contains("SUPER + Return  Terminal     exec kitty --s…"));
+    }
+
+    /// Tests colours are only added when asked for
+    #[test]
+    fn test_table_colors() {
+        assert!(!keybindings().to_table(&PLAIN).contains('\x1b'));
+
+        let style = TableStyle {
+            color: true,
+            ..PLAIN
+        };
+        let table = keybindings().to_table(&style);
+        assert!(table.contains("\x1b[36mSUPER\x1b[0m + \x1b[1;33mReturn\x1b[0m"));
+        assert!(table.contains("\x1b[1;35mSubmap: resize\x1b[0m"));
+    }
+
+    /// Verifies the icon and category options
+    #[test]
+    fn test_table_icons_and_category() {
+        let mut kb = keybindings();
+        kb.entries[1].category = "Launchers".to_string();
+        let style = TableStyle {
+            icons: true,
+            category: true,
+            ..PLAIN
+        };
+        let table = kb.to_table(&style);
+        assert!(table.contains(" + 󰌑"));
+        assert!(table.contains(" + 󰘶 + Q"));
+        assert!(table.contains("CATEGORY"));
+        assert!(table.contains("Launchers"));
+    }
+}
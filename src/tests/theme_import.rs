@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod theme_import_tests {
+    use crate::hyprland::ConfigLine;
+    use crate::hyprland::parser::config_option;
+    use crate::hyprland::source::option_from_json;
+    use crate::ui::styling::css::CssTheme;
+    use crate::ui::styling::import::{
+        ImportSource, Palette, import_theme_into, palette_from_base16, palette_from_hyprland,
+        palette_from_pywal, parse_hyprland_color,
+    };
+    use crate::ui::styling::themes::{ThemeSource, find_theme_in};
+    use eframe::egui::Color32;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    const PYWAL: &str = r##"{
+        "wallpaper": "/home/me/wall.png",
+        "alpha": "100",
+        "special": {"background": "#1a1b26", "foreground": "#c0caf5", "cursor": "#c0caf5"},
+        "colors": {
+            "color0": "#1a1b26", "color1": "#f7768e", "color2": "#9ece6a", "color3": "#e0af68",
+            "color4": "#7aa2f7", "color5": "#bb9af7", "color6": "#7dcfff", "color7": "#a9b1d6",
+            "color8": "#414868", "color9": "#f7768e", "color10": "#9ece6a", "color11": "#e0af68",
+            "color12": "#7aa2f7", "color13": "#bb9af7", "color14": "#7dcfff", "color15": "#c0caf5"
+        }
+    }"##;
+
+    const BASE16_CLASSIC: &str = r#"scheme: "Tomorrow Night"
+author: "Chris Kempson"
+base00: "1d1f21"
+base01: "282a2e"
+base02: "373b41"
+base03: "969896"
+base04: "b4b7b4"
+base05: "c5c8c6"
+base06: "e0e0e0"
+base07: "ffffff"
+base08: "cc6666"
+base09: "de935f"
+base0A: "f0c674"
+base0B: "b5bd68"
+base0C: "8abeb7"
+base0D: "81a2be"
+base0E: "b294bb"
+base0F: "a3685a"
+"#;
+
+    const BASE16_TINTED: &str = "system: \"base16\"
+name: \"Nord\"
+variant: \"dark\"
+palette:
+  base00: \"#2E3440\" # polar night
+  base01: \"#3B4252\"
+  base02: \"#434C5E\"
+  base03: \"#4C566A\"
+  base04: \"#D8DEE9\"
+  base05: \"#E5E9F0\"
+  base06: \"#ECEFF4\"
+  base07: \"#8FBCBB\"
+  base08: \"#BF616A\"
+  base09: \"#D08770\"
+  base0A: \"#EBCB8B\"
+  base0B: \"#A3BE8C\"
+  base0C: \"#88C0D0\"
+  base0D: \"#81A1C1\"
+  base0E: \"#B48EAD\"
+  base0F: \"#5E81AC\"
+";
+
+    fn lines(text: &str) -> Vec<ConfigLine> {
+        text.lines()
+            .enumerate()
+            .map(|(i, text)| ConfigLine {
+                path: PathBuf::from("hyprland.conf"),
+                number: i + 1,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    /// Checks a theme written from a palette is valid CSS setting every colour
+    fn assert_valid_theme(palette: Palette) {
+        let (theme, diagnostics) = CssTheme::check(&palette.to_css("test"));
+        assert_eq!(diagnostics, Vec::new());
+        for var in ["bg", "fg", "panel", "accent", "stroke", "selection"] {
+            assert!(theme.var(var).is_some(), "missing --{var}");
+        }
+    }
+
+    /// Tests pywal colours map to the theme variables
+    #[test]
+    fn test_pywal_palette() {
+        let palette = palette_from_pywal(PYWAL).unwrap();
+        assert_eq!(palette.panel, Color32::from_rgb(0x1a, 0x1b, 0x26));
+        assert_eq!(palette.fg, Color32::from_rgb(0xc0, 0xca, 0xf5));
+        assert_eq!(palette.accent, Color32::from_rgb(0x7a, 0xa2, 0xf7));
+        assert_eq!(palette.stroke, Color32::from_rgb(0x41, 0x48, 0x68));
+        assert_valid_theme(palette);
+
+        assert_eq!(
+            palette_from_pywal(r#"{"special": {}}"#).unwrap_err(),
+            "colors.json has no special.background"
+        );
+        assert!(palette_from_pywal("not json").is_err());
+    }
+
+    /// Verifies both base16 layouts give the same slots
+    #[test]
+    fn test_base16_palette() {
+        let classic = palette_from_base16(BASE16_CLASSIC).unwrap();
+        assert_eq!(classic.bg, Color32::from_rgb(0x1d, 0x1f, 0x21));
+        assert_eq!(classic.fg, Color32::from_rgb(0xc5, 0xc8, 0xc6));
+        assert_eq!(classic.accent, Color32::from_rgb(0x81, 0xa2, 0xbe));
+        assert_eq!(classic.selection, Color32::from_rgb(0x37, 0x3b, 0x41));
+        assert_valid_theme(classic);
+
+        let tinted = palette_from_base16(BASE16_TINTED).unwrap();
+        assert_eq!(tinted.bg, Color32::from_rgb(0x2e, 0x34, 0x40));
+        assert_eq!(tinted.panel, Color32::from_rgb(0x3b, 0x42, 0x52));
+        assert_eq!(tinted.stroke, Color32::from_rgb(0x4c, 0x56, 0x6a));
+
+        assert_eq!(
+            palette_from_base16("base00: \"1d1f21\"").unwrap_err(),
+            "scheme has no base05"
+        );
+        assert_eq!(
+            palette_from_base16("base00: \"nothex\"").unwrap_err(),
+            "invalid colour 'nothex' for base00"
+        );
+    }
+
+    /// Checks Hyprland colour syntaxes, taking the first colour of a gradient
+    #[test]
+    fn test_hyprland_colors() {
+        let cyan = Some(Color32::from_rgba_unmultiplied(0x33, 0xcc, 0xff, 0xee));
+        assert_eq!(parse_hyprland_color("rgba(33ccffee)"), cyan);
+        assert_eq!(parse_hyprland_color("0xee33ccff"), cyan);
+        assert_eq!(parse_hyprland_color("ee33ccff ee00ff99 45deg"), cyan);
+        assert_eq!(
+            parse_hyprland_color("rgba(33ccffee) rgba(00ff99ee) 45deg"),
+            cyan
+        );
+        assert_eq!(
+            parse_hyprland_color("rgb(33ccff)"),
+            Some(Color32::from_rgb(0x33, 0xcc, 0xff))
+        );
+        assert_eq!(
+            parse_hyprland_color("rgba(51, 204, 255, 1.0)"),
+            Some(Color32::from_rgb(0x33, 0xcc, 0xff))
+        );
+        assert_eq!(parse_hyprland_color("45deg"), None);
+
+        assert_eq!(
+            option_from_json(
+                r#"{"option": "general:col.active_border", "custom": "ee33ccff ee00ff99 45deg", "set": true}"#
+            ),
+            Some("ee33ccff ee00ff99 45deg".to_string())
+        );
+        assert_eq!(
+            option_from_json(
+                r#"{"option": "decoration:shadow:color", "int": 4279834905, "set": false}"#
+            ),
+            Some("0xff191919".to_string())
+        );
+
+        let palette = palette_from_hyprland(
+            "rgba(33ccffee) rgba(00ff99ee) 45deg",
+            Some("rgba(595959aa)"),
+            Some("rgba(1a1a1aee)"),
+        )
+        .unwrap();
+        assert_eq!(palette.accent, Color32::from_rgb(0x33, 0xcc, 0xff));
+        assert_eq!(palette.stroke, Color32::from_rgb(0x59, 0x59, 0x59));
+        assert_eq!(palette.panel, Color32::from_rgb(0x1a, 0x1a, 0x1a));
+        assert_valid_theme(palette);
+        assert!(palette_from_hyprland("45deg", None, None).is_err());
+    }
+
+    /// Tests options are found in blocks and in `section:option` form, with variables
+    #[test]
+    fn test_config_option() {
+        let config = lines(
+            "$accent = rgba(33ccffee)
+general {
+    gaps_in = 5
+    col.active_border = $accent rgba(00ff99ee) 45deg # gradient
+    col.inactive_border = rgba(595959aa)
+}
+decoration {
+    shadow {
+        color = rgba(1a1a1aee)
+    }
+}
+general:col.inactive_border = rgba(222222aa)",
+        );
+        assert_eq!(
+            config_option(&config, "general:col.active_border").as_deref(),
+            Some("rgba(33ccffee) rgba(00ff99ee) 45deg")
+        );
+        assert_eq!(
+            config_option(&config, "general:col.inactive_border").as_deref(),
+            Some("rgba(222222aa)")
+        );
+        assert_eq!(
+            config_option(&config, "decoration:shadow:color").as_deref(),
+            Some("rgba(1a1a1aee)")
+        );
+        assert_eq!(config_option(&config, "col.active_border"), None);
+    }
+
+    /// Checks sources, theme names, and that an import is found as a named theme
+    #[test]
+    fn test_import_into_themes_dir() {
+        assert_eq!(ImportSource::parse("hyprland"), Ok(ImportSource::Hyprland));
+        assert!(matches!(
+            ImportSource::parse("wallust"),
+            Ok(ImportSource::Pywal(_))
+        ));
+        assert!(ImportSource::parse("theme.toml").is_err());
+        let base16 = ImportSource::parse("schemes/tomorrow-night.yaml").unwrap();
+        assert_eq!(base16.default_name(), "base16-tomorrow-night");
+
+        let dir = TempDir::new().unwrap();
+        let scheme = dir.path().join("tomorrow-night.yaml");
+        fs::write(&scheme, BASE16_CLASSIC).unwrap();
+        let themes = dir.path().join("themes");
+        let (name, path) =
+            import_theme_into(&themes, &ImportSource::Base16(scheme.clone()), None).unwrap();
+        assert_eq!(name, "base16-tomorrow-night");
+        let theme = find_theme_in(&themes, &name).unwrap();
+        assert_eq!(theme.source, ThemeSource::File(path));
+
+        let colors = dir.path().join("colors.json");
+        fs::write(&colors, PYWAL).unwrap();
+        let (name, _) =
+            import_theme_into(&themes, &ImportSource::Pywal(colors), Some("wal")).unwrap();
+        assert_eq!(name, "wal");
+        assert!(themes.join("wal.css").is_file());
+
+        assert!(import_theme_into(&themes, &ImportSource::Base16(scheme), Some("../x")).is_err());
+    }
+}
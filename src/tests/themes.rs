@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod themes_tests {
+    use crate::ui::styling::css::CssTheme;
+    use crate::ui::styling::themes::{PRESETS, ThemeSource, find_theme_in, list_themes_in};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Checks every bundled preset parses without problems and sets the window colours
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in &PRESETS {
+            let (theme, diagnostics) = CssTheme::check(preset.css);
+            assert_eq!(diagnostics, Vec::new(), "{}", preset.name);
+            for var in ["bg", "fg", "panel", "accent", "stroke", "selection"] {
+                assert!(theme.var(var).is_some(), "{} lacks --{var}", preset.name);
+            }
+        }
+    }
+
+    /// Tests user themes are listed after the presets and replace presets of the same name
+    #[test]
+    fn test_list_themes() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("nord.css"), ":root { --bg: #000000; }").unwrap();
+        fs::write(dir.path().join("zebra.css"), "").unwrap();
+        fs::write(dir.path().join("mine.css"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let themes = list_themes_in(dir.path());
+        let names: Vec<&str> = themes.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "catppuccin-mocha",
+                "tokyo-night",
+                "gruvbox-dark",
+                "nord",
+                "rose-pine",
+                "mine",
+                "zebra",
+            ]
+        );
+        assert_eq!(
+            themes[3].source,
+            ThemeSource::File(dir.path().join("nord.css"))
+        );
+        assert_eq!(themes[4].title, "Rosé Pine");
+        assert_eq!(themes.len(), PRESETS.len() + 2);
+    }
+
+    /// Ensures themes are found by name, files first
+    #[test]
+    fn test_find_theme() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("nord.css"), ":root { --bg: #000000; }").unwrap();
+
+        let nord = find_theme_in(dir.path(), "nord").unwrap();
+        assert_eq!(nord.css().unwrap(), ":root { --bg: #000000; }");
+        assert_eq!(nord.source, ThemeSource::File(dir.path().join("nord.css")));
+
+        let tokyo = find_theme_in(dir.path(), "tokyo-night").unwrap();
+        assert!(matches!(tokyo.source, ThemeSource::Preset(_)));
+        assert!(find_theme_in(dir.path(), "missing").is_none());
+    }
+}
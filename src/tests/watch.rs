@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod watch_tests {
+    use crate::config::watch::{Changes, ConfigWatcher, WatchDirs};
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    /// Longer than the debounce, so a batch of events has been reported
+    const SETTLE: Duration = Duration::from_millis(600);
+
+    fn dirs(root: &Path) -> WatchDirs {
+        WatchDirs {
+            config: root.join("hyprbind"),
+            themes: root.join("hyprbind").join("themes"),
+            pywal: root.join("wal"),
+        }
+    }
+
+    /// Start a watcher on `dirs`, counting the reports
+    fn start(dirs: WatchDirs) -> (ConfigWatcher, Arc<AtomicUsize>) {
+        let reports = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&reports);
+        let watcher = ConfigWatcher::start(dirs, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        (watcher, reports)
+    }
+
+    /// Changes reported within a few seconds
+    fn wait_for_changes(watcher: &ConfigWatcher) -> Changes {
+        let start = Instant::now();
+        let mut changes = Changes::default();
+        while changes.is_empty() && start.elapsed() < Duration::from_secs(5) {
+            sleep(Duration::from_millis(20));
+            changes = watcher.take();
+        }
+        changes
+    }
+
+    /// Save like editors that write a temporary file and rename it over the original
+    fn save_by_rename(path: &Path, contents: &str) {
+        let temp = path.with_extension("tmp~");
+        fs::write(&temp, contents).unwrap();
+        fs::rename(&temp, path).unwrap();
+    }
+
+    /// Checks config and CSS edits are reported once, after the burst of events settles
+    #[test]
+    fn test_reports_debounced_changes() {
+        let root = TempDir::new().unwrap();
+        let dirs = dirs(root.path());
+        fs::create_dir_all(&dirs.config).unwrap();
+        let (watcher, reports) = start(dirs.clone());
+
+        save_by_rename(&dirs.config.join("config.json"), "{}");
+        let changes = wait_for_changes(&watcher);
+        assert!(changes.config && !changes.css && !changes.pywal);
+
+        sleep(SETTLE);
+        let before = reports.load(Ordering::SeqCst);
+        let css = dirs.config.join("hyprbind-theme.css");
+        for i in 0..5 {
+            fs::write(&css, format!(":root {{ --radius: {i}; }}")).unwrap();
+        }
+        save_by_rename(&css, ":root { --radius: 6; }");
+        let changes = wait_for_changes(&watcher);
+        assert!(changes.css && !changes.config);
+        sleep(SETTLE);
+        assert_eq!(reports.load(Ordering::SeqCst), before + 1);
+    }
+
+    /// Tests the themes directory is watched once created, and unrelated files are ignored
+    #[test]
+    fn test_themes_dir_created_later() {
+        let root = TempDir::new().unwrap();
+        let dirs = dirs(root.path());
+        fs::create_dir_all(&dirs.config).unwrap();
+        fs::create_dir_all(&dirs.pywal).unwrap();
+        let (watcher, _) = start(dirs.clone());
+
+        fs::write(dirs.config.join("notes.txt"), "hello").unwrap();
+        sleep(SETTLE);
+        assert_eq!(watcher.take(), Changes::default());
+
+        fs::create_dir_all(&dirs.themes).unwrap();
+        assert!(wait_for_changes(&watcher).css);
+        sleep(SETTLE);
+        let _ = watcher.take();
+
+        fs::write(dirs.themes.join("mine.css"), ":root {}").unwrap();
+        assert!(wait_for_changes(&watcher).css);
+
+        save_by_rename(&dirs.pywal.join("colors.json"), "{}");
+        let changes = wait_for_changes(&watcher);
+        assert!(changes.pywal && !changes.css);
+    }
+}
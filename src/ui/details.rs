@@ -0,0 +1,202 @@
+use crate::hyprland::{Annotation, BindId, KeyBindEntry};
+use eframe::egui;
+
+/// Editable copy of a bind's annotation
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationDraft {
+    pub bind: BindId,
+    pub description: String,
+    pub notes: String,
+    /// Comma-separated tags
+    pub tags: String,
+}
+
+impl AnnotationDraft {
+    pub fn new(bind: BindId, annotation: Option<&Annotation>) -> Self {
+        let mut draft = Self {
+            bind,
+            ..Self::default()
+        };
+        if let Some(a) = annotation {
+            draft.description.clone_from(&a.description);
+            draft.notes.clone_from(&a.notes);
+            draft.tags = a.tags.join(", ");
+        }
+        draft
+    }
+
+    pub fn to_annotation(&self) -> Annotation {
+        Annotation {
+            bind: self.bind.clone(),
+            description: self.description.trim().to_string(),
+            notes: self.notes.trim().to_string(),
+            tags: self
+                .tags
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// User choice in the detail panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetailAction {
+    Save,
+    Revert,
+    DeleteOrphan(BindId),
+}
+
+fn render_bind_summary(ui: &mut egui::Ui, entry: &KeyBindEntry) {
+    egui::Grid::new("detail_summary")
+        .num_columns(2)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            let key = entry.normalized_key();
+            let keybind = if entry.modifiers.is_empty() {
+                key.label()
+            } else {
+                format!("{} + {key}", entry.modifiers.replace('+', " + "))
+            };
+            ui.label(egui::RichText::new("\u{ea65}  Keybind").weak());
+            ui.label(keybind);
+            ui.end_row();
+
+            ui.label(egui::RichText::new("\u{ebc4}  Command").weak());
+            ui.label(egui::RichText::new(&entry.command).monospace());
+            ui.end_row();
+
+            if !entry.submap.is_empty() {
+                ui.label(egui::RichText::new("\u{f0db}  Submap").weak());
+                ui.label(&entry.submap);
+                ui.end_row();
+            }
+
+            let flags = entry.flags.letters();
+            if !flags.is_empty() {
+                ui.label(egui::RichText::new("\u{f024}  Flags").weak());
+                ui.monospace(flags);
+                ui.end_row();
+            }
+        });
+}
+
+fn render_annotation_editor(
+    ui: &mut egui::Ui,
+    draft: &mut AnnotationDraft,
+) -> Option<DetailAction> {
+    let mut action = None;
+
+    ui.heading("\u{f044}  Annotation");
+    ui.add_space(5.0);
+    ui.label("Description");
+    ui.add(
+        egui::TextEdit::singleline(&mut draft.description)
+            .hint_text("Overrides the bind's description")
+            .desired_width(f32::INFINITY),
+    );
+    ui.add_space(5.0);
+    ui.label("Notes");
+    ui.add(
+        egui::TextEdit::multiline(&mut draft.notes)
+            .desired_rows(4)
+            .desired_width(f32::INFINITY),
+    );
+    ui.add_space(5.0);
+    ui.label("Tags");
+    ui.add(
+        egui::TextEdit::singleline(&mut draft.tags)
+            .hint_text("comma, separated")
+            .desired_width(f32::INFINITY),
+    );
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        if ui.button("Save").clicked() {
+            action = Some(DetailAction::Save);
+        }
+        if ui.button("Revert").clicked() {
+            action = Some(DetailAction::Revert);
+        }
+    });
+
+    action
+}
+
+fn render_orphans(ui: &mut egui::Ui, orphans: &[&Annotation]) -> Option<DetailAction> {
+    let mut action = None;
+
+    egui::CollapsingHeader::new(format!(
+        "\u{f071}  Orphaned annotations ({})",
+        orphans.len()
+    ))
+    .id_salt("orphaned_annotations")
+    .show(ui, |ui| {
+        ui.label(
+            egui::RichText::new("These binds no longer exist in the loaded config.")
+                .weak()
+                .size(12.0),
+        );
+        for orphan in orphans {
+            ui.add_space(4.0);
+            ui.horizontal_wrapped(|ui| {
+                ui.label(egui::RichText::new(orphan.bind.to_string()).monospace());
+                if ui.small_button("Delete").clicked() {
+                    action = Some(DetailAction::DeleteOrphan(orphan.bind.clone()));
+                }
+            });
+            let summary = if orphan.description.is_empty() {
+                &orphan.notes
+            } else {
+                &orphan.description
+            };
+            if !summary.is_empty() {
+                ui.label(egui::RichText::new(summary).weak().size(12.0));
+            }
+        }
+    });
+
+    action
+}
+
+pub fn render_detail_panel(
+    ctx: &egui::Context,
+    entry: Option<&KeyBindEntry>,
+    draft: Option<&mut AnnotationDraft>,
+    orphans: &[&Annotation],
+) -> Option<DetailAction> {
+    let mut action = None;
+
+    egui::SidePanel::right("detail_panel")
+        .resizable(true)
+        .default_width(300.0)
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                match (entry, draft) {
+                    (Some(entry), Some(draft)) => {
+                        render_bind_summary(ui, entry);
+                        ui.add_space(10.0);
+                        ui.separator();
+                        ui.add_space(10.0);
+                        action = render_annotation_editor(ui, draft);
+                    }
+                    _ => {
+                        ui.label(egui::RichText::new("Select a bind to see its details.").weak());
+                    }
+                }
+
+                if !orphans.is_empty() {
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    if let Some(orphan_action) = render_orphans(ui, orphans) {
+                        action = Some(orphan_action);
+                    }
+                }
+            });
+        });
+
+    action
+}
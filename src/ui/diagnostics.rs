@@ -0,0 +1,98 @@
+use crate::hyprland::{ParseWarning, SourceError, SourceKind};
+use eframe::egui;
+
+const LOADED: egui::Color32 = egui::Color32::from_rgb(80, 200, 120);
+const FAILED: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+const WARNING: egui::Color32 = egui::Color32::from_rgb(230, 180, 60);
+
+/// Problems found during the last load
+pub struct Diagnostics<'a> {
+    pub loaded_from: Option<SourceKind>,
+    pub source_errors: &'a [SourceError],
+    pub warnings: &'a [ParseWarning],
+    pub error_message: Option<&'a str>,
+}
+
+impl Diagnostics<'_> {
+    const fn is_empty(&self) -> bool {
+        self.source_errors.is_empty() && self.warnings.is_empty() && self.error_message.is_none()
+    }
+}
+
+fn render_sources(ui: &mut egui::Ui, diagnostics: &Diagnostics) {
+    ui.label(egui::RichText::new("Sources").strong());
+    for error in diagnostics.source_errors {
+        ui.horizontal_wrapped(|ui| {
+            ui.colored_label(FAILED, "\u{f00d}");
+            ui.label(egui::RichText::new(error.source.name()).strong());
+            ui.label(error.reason.to_string());
+        });
+    }
+    match diagnostics.loaded_from {
+        Some(source) => {
+            ui.horizontal(|ui| {
+                ui.colored_label(LOADED, "\u{f00c}");
+                ui.label(egui::RichText::new(source.name()).strong());
+                ui.label("loaded");
+            });
+        }
+        None => {
+            ui.colored_label(FAILED, "No source could provide keybindings.");
+        }
+    }
+}
+
+fn render_warning(ui: &mut egui::Ui, index: usize, warning: &ParseWarning) {
+    ui.horizontal_wrapped(|ui| {
+        ui.colored_label(WARNING, "\u{f071}");
+        ui.label(egui::RichText::new(warning.to_string()).strong());
+    });
+    egui::CollapsingHeader::new("Raw text")
+        .id_salt(("parse_warning", index))
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(&warning.raw).monospace().weak());
+        });
+}
+
+/// Render the diagnostics window; returns true if a reload was requested
+pub fn render_diagnostics_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    diagnostics: &Diagnostics,
+) -> bool {
+    let mut reload = false;
+    egui::Window::new("\u{f0ae}  Diagnostics")
+        .open(open)
+        .collapsible(false)
+        .default_size([560.0, 400.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                reload = ui.button("\u{f021}  Reload").clicked();
+            });
+            ui.separator();
+            if diagnostics.is_empty() {
+                ui.label("No problems found during the last load.");
+            }
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                render_sources(ui, diagnostics);
+                if let Some(error) = diagnostics.error_message {
+                    ui.add_space(8.0);
+                    ui.colored_label(FAILED, format!("⚠ {error}"));
+                }
+                if !diagnostics.warnings.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Skipped binds ({})",
+                            diagnostics.warnings.len()
+                        ))
+                        .strong(),
+                    );
+                    for (i, warning) in diagnostics.warnings.iter().enumerate() {
+                        render_warning(ui, i, warning);
+                    }
+                }
+            });
+        });
+    reload
+}
@@ -0,0 +1,196 @@
+use crate::hyprland::diff::ChangedBind;
+use crate::hyprland::{BindDiff, KeyBindEntry};
+use eframe::egui;
+use std::path::Path;
+
+/// Bind sets compared in the history window; `None` is the current keybindings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffSelection {
+    pub from: Option<usize>,
+    pub to: Option<usize>,
+}
+
+const ADDED: egui::Color32 = egui::Color32::from_rgb(80, 200, 120);
+const REMOVED: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+const CHANGED: egui::Color32 = egui::Color32::from_rgb(230, 180, 60);
+
+/// Readable UTC time of a `snapshot_<millis>.json` file
+pub fn snapshot_label(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let Some(millis) = stem
+        .strip_prefix("snapshot_")
+        .and_then(|m| m.parse::<i64>().ok())
+    else {
+        return stem.to_string();
+    };
+
+    let secs = millis / 1000;
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+fn source_label(index: Option<usize>, snapshots: &[String]) -> String {
+    index
+        .and_then(|i| snapshots.get(i))
+        .map_or_else(|| "Current".to_string(), Clone::clone)
+}
+
+fn render_source_picker(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &mut Option<usize>,
+    snapshots: &[String],
+) -> bool {
+    let before = *selected;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(source_label(*selected, snapshots))
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, "Current");
+            for (i, label) in snapshots.iter().enumerate() {
+                ui.selectable_value(selected, Some(i), label);
+            }
+        });
+    *selected != before
+}
+
+/// Combo and submap of a bind, e.g. `SUPER + Q  [resize]`
+pub fn bind_label(entry: &KeyBindEntry) -> String {
+    let combo = if entry.modifiers.is_empty() {
+        entry.normalized_key().label()
+    } else {
+        format!(
+            "{} + {}",
+            entry.modifiers.replace('+', " + "),
+            entry.normalized_key()
+        )
+    };
+    if entry.submap.is_empty() {
+        combo
+    } else {
+        format!("{combo}  [{}]", entry.submap)
+    }
+}
+
+/// Command of a bind, followed by its description if any
+pub fn action_text(entry: &KeyBindEntry) -> String {
+    if entry.description.is_empty() {
+        entry.command.clone()
+    } else {
+        format!("{}  ({})", entry.command, entry.description)
+    }
+}
+
+fn render_entry(ui: &mut egui::Ui, sign: &str, color: egui::Color32, entry: &KeyBindEntry) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new(sign).monospace().strong().color(color));
+        ui.label(egui::RichText::new(bind_label(entry)).strong());
+        ui.label(
+            egui::RichText::new(action_text(entry))
+                .monospace()
+                .color(color),
+        );
+    });
+}
+
+fn render_change(ui: &mut egui::Ui, change: &ChangedBind) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new("~").monospace().strong().color(CHANGED));
+        ui.label(egui::RichText::new(bind_label(&change.after)).strong());
+    });
+    ui.indent("change", |ui| {
+        ui.label(
+            egui::RichText::new(format!("- {}", action_text(&change.before)))
+                .monospace()
+                .color(REMOVED),
+        );
+        ui.label(
+            egui::RichText::new(format!("+ {}", action_text(&change.after)))
+                .monospace()
+                .color(ADDED),
+        );
+    });
+}
+
+fn render_diff(ui: &mut egui::Ui, diff: &BindDiff) {
+    ui.label(
+        egui::RichText::new(format!(
+            "{} added, {} removed, {} changed",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len()
+        ))
+        .weak(),
+    );
+    ui.add_space(6.0);
+    if diff.is_empty() {
+        ui.label("No differences.");
+        return;
+    }
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for entry in &diff.added {
+            render_entry(ui, "+", ADDED, entry);
+        }
+        for entry in &diff.removed {
+            render_entry(ui, "-", REMOVED, entry);
+        }
+        for change in &diff.changed {
+            render_change(ui, change);
+        }
+    });
+}
+
+/// Render the snapshot history window; returns true if the selection changed
+pub fn render_diff_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    snapshots: &[String],
+    selection: &mut DiffSelection,
+    diff: Option<&Result<BindDiff, String>>,
+) -> bool {
+    let mut changed = false;
+    egui::Window::new("\u{f1da}  History")
+        .open(open)
+        .collapsible(false)
+        .default_size([560.0, 420.0])
+        .show(ctx, |ui| {
+            if snapshots.is_empty() {
+                ui.label("No snapshots yet. One is saved every time keybindings are loaded.");
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.label("From");
+                changed |= render_source_picker(ui, "diff_from", &mut selection.from, snapshots);
+                ui.label("To");
+                changed |= render_source_picker(ui, "diff_to", &mut selection.to, snapshots);
+            });
+            ui.separator();
+            match diff {
+                Some(Ok(diff)) => render_diff(ui, diff),
+                Some(Err(e)) => {
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {e}"));
+                }
+                None => {}
+            }
+        });
+    changed
+}
@@ -0,0 +1,104 @@
+use crate::hyprland::DescriptionStyle;
+use eframe::egui;
+
+/// Contents of the save dialog
+pub enum SaveDialog<'a> {
+    Preview {
+        location: String,
+        old_line: &'a str,
+        new_line: &'a str,
+        style: DescriptionStyle,
+    },
+    Failed(&'a str),
+    Done(&'a str),
+}
+
+/// User choice in the save dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveDialogAction {
+    Confirm,
+    Close,
+    SetStyle(DescriptionStyle),
+}
+
+fn render_diff(ui: &mut egui::Ui, old_line: &str, new_line: &str) {
+    let removed = egui::Color32::from_rgb(0xf7, 0x76, 0x8e);
+    let added = egui::Color32::from_rgb(0x9e, 0xce, 0x6a);
+
+    egui::Frame::new()
+        .inner_margin(egui::Margin::same(8))
+        .corner_radius(6.0_f32)
+        .fill(ui.visuals().extreme_bg_color)
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(format!("- {old_line}"))
+                    .monospace()
+                    .color(removed),
+            );
+            ui.label(
+                egui::RichText::new(format!("+ {new_line}"))
+                    .monospace()
+                    .color(added),
+            );
+        });
+}
+
+pub fn render_save_dialog(ctx: &egui::Context, dialog: &SaveDialog) -> Option<SaveDialogAction> {
+    let mut action = None;
+
+    egui::Window::new("Save description")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| match dialog {
+            SaveDialog::Preview {
+                location,
+                old_line,
+                new_line,
+                style,
+            } => {
+                ui.label("Write as:");
+                ui.horizontal(|ui| {
+                    let mut selected = *style;
+                    ui.radio_value(&mut selected, DescriptionStyle::Bindd, "bindd");
+                    ui.radio_value(&mut selected, DescriptionStyle::Comment, "# comment");
+                    if selected != *style {
+                        action = Some(SaveDialogAction::SetStyle(selected));
+                    }
+                });
+                ui.add_space(8.0);
+                ui.monospace(location);
+                render_diff(ui, old_line, new_line);
+                ui.add_space(8.0);
+                ui.label(egui::RichText::new("A backup is kept and Hyprland is reloaded.").weak());
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        action = Some(SaveDialogAction::Confirm);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        action = Some(SaveDialogAction::Close);
+                    }
+                });
+            }
+            SaveDialog::Failed(message) => {
+                ui.colored_label(egui::Color32::RED, format!("⚠ {message}"));
+                ui.add_space(10.0);
+                if ui.button("OK").clicked() {
+                    action = Some(SaveDialogAction::Close);
+                }
+            }
+            SaveDialog::Done(message) => {
+                ui.label(*message);
+                ui.add_space(10.0);
+                if ui.button("OK").clicked() {
+                    action = Some(SaveDialogAction::Close);
+                }
+            }
+        });
+
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        action = Some(SaveDialogAction::Close);
+    }
+    action
+}
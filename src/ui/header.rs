@@ -1,8 +1,15 @@
+use crate::hyprland::{SourceError, SourceKind};
+use crate::ui::styling::css::{Element, element_style};
 use eframe::egui;
 
-fn render_gradient_text(ui: &mut egui::Ui, text: &str, font_size: f32) {
-    // Gradient colors derived from theme accent
-    let accent = ui.visuals().hyperlink_color;
+fn render_gradient_text(
+    ui: &mut egui::Ui,
+    text: &str,
+    font_size: f32,
+    color: Option<egui::Color32>,
+) {
+    // Gradient colors derived from the header colour or theme accent
+    let accent = color.unwrap_or_else(|| ui.visuals().hyperlink_color);
     let start_color = accent; // use accent as start
     let end_color = egui::Color32::from_rgb(
         u8::try_from(u16::midpoint(u16::from(accent.r()), 255)).unwrap_or(255),
@@ -48,20 +55,40 @@ fn render_gradient_text(ui: &mut egui::Ui, text: &str, font_size: f32) {
 pub fn render_header(
     ui: &mut egui::Ui,
     show_options_window: &mut bool,
+    show_detail_panel: &mut bool,
+    show_diff_window: &mut bool,
+    show_instances_window: &mut bool,
     error_message: Option<&String>,
     logo_texture: Option<&egui::TextureHandle>,
 ) {
+    let style = element_style(ui.ctx(), Element::Header);
+    let padding = style.padding.unwrap_or(egui::Margin {
+        left: 20,
+        right: 20,
+        top: 15,
+        bottom: 12,
+    });
+
     // Modern header with background
     let header_rect =
         egui::Rect::from_min_size(ui.min_rect().min, egui::vec2(ui.available_width(), 120.0));
-    let header_bg = ui.visuals().panel_fill;
-    ui.painter().rect_filled(header_rect, 0.0, header_bg);
+    let header_bg = style.background.unwrap_or_else(|| ui.visuals().panel_fill);
+    let radius = style.border_radius.unwrap_or_default();
+    ui.painter().rect_filled(header_rect, radius, header_bg);
+    if let Some(border) = style.border_color {
+        ui.painter().rect_stroke(
+            header_rect,
+            radius,
+            egui::Stroke::new(1.0_f32, border),
+            egui::StrokeKind::Inside,
+        );
+    }
 
-    ui.add_space(15.0);
+    ui.add_space(f32::from(padding.top));
 
     // Title section
     ui.horizontal(|ui| {
-        ui.add_space(20.0);
+        ui.add_space(f32::from(padding.left));
 
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = 8.0;
@@ -84,22 +111,47 @@ pub fn render_header(
             // Render gradient text "HyprBind" tinted by accent color
             ui.vertical(|ui| {
                 ui.add_space(-1.0); // Negative space to move text up
-                render_gradient_text(ui, "HyprBind", 24.0);
+                render_gradient_text(ui, "HyprBind", style.font_size.unwrap_or(24.0), style.color);
             });
         });
 
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            ui.add_space(20.0);
+            ui.add_space(f32::from(padding.right));
             let options_button = egui::Button::new(egui::RichText::new("").size(18.0))
                 .fill(egui::Color32::TRANSPARENT)
                 .stroke(egui::Stroke::NONE);
             if ui.add(options_button).on_hover_text("Options").clicked() {
                 *show_options_window = !*show_options_window;
             }
+            let details_button = egui::Button::new(egui::RichText::new("\u{f05a}").size(18.0))
+                .fill(egui::Color32::TRANSPARENT)
+                .stroke(egui::Stroke::NONE)
+                .selected(*show_detail_panel);
+            if ui.add(details_button).on_hover_text("Details").clicked() {
+                *show_detail_panel = !*show_detail_panel;
+            }
+            let history_button = egui::Button::new(egui::RichText::new("\u{f1da}").size(18.0))
+                .fill(egui::Color32::TRANSPARENT)
+                .stroke(egui::Stroke::NONE)
+                .selected(*show_diff_window);
+            if ui.add(history_button).on_hover_text("History").clicked() {
+                *show_diff_window = !*show_diff_window;
+            }
+            let instances_button = egui::Button::new(egui::RichText::new("\u{f108}").size(18.0))
+                .fill(egui::Color32::TRANSPARENT)
+                .stroke(egui::Stroke::NONE)
+                .selected(*show_instances_window);
+            if ui
+                .add(instances_button)
+                .on_hover_text("Hyprland instances")
+                .clicked()
+            {
+                *show_instances_window = !*show_instances_window;
+            }
         });
     });
 
-    ui.add_space(12.0);
+    ui.add_space(f32::from(padding.bottom));
 
     // Display error message if any
     if let Some(error) = error_message {
@@ -111,7 +163,51 @@ pub fn render_header(
     }
 }
 
+/// Summarise load problems above the table, with a button opening the diagnostics window
+///
+/// Shows which source the binds came from when others failed, or why nothing loaded.
+pub fn render_source_status(
+    ui: &mut egui::Ui,
+    loaded_from: Option<SourceKind>,
+    errors: &[SourceError],
+    warning_count: usize,
+    show_diagnostics_window: &mut bool,
+) {
+    let mut lines = Vec::new();
+    let color = if let Some(source) = loaded_from {
+        if !errors.is_empty() {
+            lines.push(format!("⚠ Loaded from {source} after other sources failed"));
+        }
+        egui::Color32::YELLOW
+    } else {
+        lines.push("⚠ Failed to load keybindings".to_string());
+        egui::Color32::RED
+    };
+    if warning_count > 0 {
+        lines.push(format!("⚠ {warning_count} bind(s) could not be parsed"));
+    }
+    if lines.is_empty() {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.add_space(20.0);
+        ui.vertical(|ui| {
+            for line in lines {
+                ui.colored_label(color, line);
+            }
+            for error in errors {
+                ui.label(egui::RichText::new(format!("  {error}")).weak().size(12.0));
+            }
+            if ui.small_button("\u{f0ae}  Diagnostics").clicked() {
+                *show_diagnostics_window = true;
+            }
+        });
+    });
+    ui.add_space(8.0);
+}
+
 pub fn render_search_bar(ui: &mut egui::Ui, search_query: &mut String) {
+    let style = element_style(ui.ctx(), Element::Search);
     ui.horizontal(|ui| {
         ui.add_space(20.0);
 
@@ -119,11 +215,41 @@ pub fn render_search_bar(ui: &mut egui::Ui, search_query: &mut String) {
         ui.label(egui::RichText::new("").size(16.0).weak());
         ui.add_space(5.0);
 
-        let search_bar = egui::TextEdit::singleline(search_query)
+        let mut search_bar = egui::TextEdit::singleline(search_query)
             .id(egui::Id::new("search_bar"))
             .hint_text("\u{e68f}  Search keybinds... ( / )")
             .desired_width(ui.available_width() - 140.0);
-        ui.add(search_bar);
+        if let Some(color) = style.color {
+            search_bar = search_bar.text_color(color);
+        }
+        if let Some(background) = style.background {
+            search_bar = search_bar.background_color(background);
+        }
+        if let Some(size) = style.font_size {
+            search_bar = search_bar.font(egui::FontId::proportional(size));
+        }
+        if let Some(padding) = style.padding {
+            search_bar = search_bar.margin(padding);
+        }
+        ui.scope(|ui| {
+            let visuals = ui.visuals_mut();
+            for state in [
+                &mut visuals.widgets.inactive,
+                &mut visuals.widgets.hovered,
+                &mut visuals.widgets.active,
+            ] {
+                if let Some(border) = style.border_color {
+                    state.bg_stroke.color = border;
+                }
+                if let Some(radius) = style.border_radius {
+                    state.corner_radius = egui::CornerRadius::from(radius);
+                }
+            }
+            if let Some(border) = style.border_color {
+                visuals.selection.stroke.color = border;
+            }
+            ui.add(search_bar);
+        });
 
         ui.add_space(1.0);
 
@@ -139,18 +265,23 @@ pub fn render_search_bar(ui: &mut egui::Ui, search_query: &mut String) {
 }
 
 pub fn render_stats_bar(ui: &mut egui::Ui, total: usize, showing: usize) {
+    let style = element_style(ui.ctx(), Element::Stats);
     ui.horizontal(|ui| {
         ui.add_space(20.0);
         ui.label(
-            egui::RichText::new(format!(" Total: {total}"))
-                .weak()
-                .size(12.0),
+            style.text(
+                egui::RichText::new(format!(" Total: {total}"))
+                    .weak()
+                    .size(12.0),
+            ),
         );
         ui.add_space(10.0);
         ui.label(
-            egui::RichText::new(format!(" Showing: {showing}"))
-                .weak()
-                .size(12.0),
+            style.text(
+                egui::RichText::new(format!(" Showing: {showing}"))
+                    .weak()
+                    .size(12.0),
+            ),
         );
     });
     ui.add_space(8.0);
@@ -0,0 +1,169 @@
+use super::diff::{action_text, bind_label};
+use crate::hyprland::HyprlandInstance;
+use crate::hyprland::diff::BindPair;
+use eframe::egui;
+
+const MISSING: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+const CHANGED: egui::Color32 = egui::Color32::from_rgb(230, 180, 60);
+
+/// Instances compared side by side in the instances window
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceSelection {
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub only_differences: bool,
+}
+
+pub enum InstanceAction {
+    /// Show binds of this instance in the main table; `None` is the current session
+    View(Option<String>),
+    /// Compare the selected instances again
+    Compare,
+    /// Scan for instances again
+    Refresh,
+}
+
+fn instance_label(signature: Option<&str>, instances: &[HyprlandInstance]) -> String {
+    signature.map_or_else(
+        || "Select an instance".to_string(),
+        |sig| {
+            instances
+                .iter()
+                .find(|i| i.signature == sig)
+                .map_or_else(|| sig.to_string(), HyprlandInstance::label)
+        },
+    )
+}
+
+fn render_instance_picker(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &mut Option<String>,
+    instances: &[HyprlandInstance],
+) -> bool {
+    let before = selected.clone();
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(instance_label(selected.as_deref(), instances))
+        .width(240.0)
+        .show_ui(ui, |ui| {
+            for instance in instances {
+                ui.selectable_value(selected, Some(instance.signature.clone()), instance.label());
+            }
+        });
+    *selected != before
+}
+
+fn render_side(ui: &mut egui::Ui, entry: Option<&egui::RichText>) {
+    match entry {
+        Some(text) => ui.label(text.clone()),
+        None => ui.label(egui::RichText::new("—").color(MISSING)),
+    };
+}
+
+fn render_pairs(ui: &mut egui::Ui, pairs: &[BindPair], only_differences: bool) {
+    let differences = pairs.iter().filter(|p| !p.is_same()).count();
+    ui.label(egui::RichText::new(format!("{differences} of {} binds differ", pairs.len())).weak());
+    ui.add_space(6.0);
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("instance_compare")
+            .num_columns(3)
+            .striped(true)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Keybind").strong());
+                ui.label(egui::RichText::new("Left").strong());
+                ui.label(egui::RichText::new("Right").strong());
+                ui.end_row();
+
+                for pair in pairs {
+                    let same = pair.is_same();
+                    if only_differences && same {
+                        continue;
+                    }
+                    let Some(bind) = pair.left.as_ref().or(pair.right.as_ref()) else {
+                        continue;
+                    };
+                    ui.label(egui::RichText::new(bind_label(bind)).strong());
+                    let text = |entry: &crate::hyprland::KeyBindEntry| {
+                        let text = egui::RichText::new(action_text(entry)).monospace();
+                        if same { text } else { text.color(CHANGED) }
+                    };
+                    render_side(ui, pair.left.as_ref().map(text).as_ref());
+                    render_side(ui, pair.right.as_ref().map(text).as_ref());
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+/// Render the Hyprland instances window
+pub fn render_instances_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    instances: &[HyprlandInstance],
+    viewing: Option<&str>,
+    selection: &mut InstanceSelection,
+    pairs: Option<&Result<Vec<BindPair>, String>>,
+) -> Option<InstanceAction> {
+    let mut action = None;
+    egui::Window::new("\u{f108}  Hyprland instances")
+        .open(open)
+        .collapsible(false)
+        .default_size([720.0, 460.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Viewing");
+                let mut view = viewing.map(String::from);
+                egui::ComboBox::from_id_salt("instance_view")
+                    .selected_text(viewing.map_or_else(
+                        || "Current session".to_string(),
+                        |sig| instance_label(Some(sig), instances),
+                    ))
+                    .width(240.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut view, None, "Current session");
+                        for instance in instances {
+                            ui.selectable_value(
+                                &mut view,
+                                Some(instance.signature.clone()),
+                                instance.label(),
+                            );
+                        }
+                    });
+                if view.as_deref() != viewing {
+                    action = Some(InstanceAction::View(view));
+                }
+                if ui.button("\u{f021}  Rescan").clicked() {
+                    action = Some(InstanceAction::Refresh);
+                }
+            });
+            if instances.is_empty() {
+                ui.label("No Hyprland instances found in $XDG_RUNTIME_DIR/hypr/.");
+                return;
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                let mut changed =
+                    render_instance_picker(ui, "instance_left", &mut selection.left, instances);
+                ui.label("vs");
+                changed |=
+                    render_instance_picker(ui, "instance_right", &mut selection.right, instances);
+                ui.checkbox(&mut selection.only_differences, "Only differences");
+                if changed {
+                    action = Some(InstanceAction::Compare);
+                }
+            });
+            ui.separator();
+            match pairs {
+                Some(Ok(pairs)) => render_pairs(ui, pairs, selection.only_differences),
+                Some(Err(e)) => {
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {e}"));
+                }
+                None => {
+                    ui.label("Pick two instances to compare their binds.");
+                }
+            }
+        });
+    action
+}
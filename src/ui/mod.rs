@@ -1,6 +1,12 @@
+pub mod details;
+pub mod diagnostics;
+pub mod diff;
+pub mod edit;
 pub mod header;
+pub mod instances;
 pub mod options;
 pub mod styling;
+pub mod system_theme;
 pub mod table;
 pub mod types;
 pub mod zen;
@@ -1,12 +1,23 @@
 use super::types::{ColumnVisibility, Theme};
 use crate::hyprland::SearchOptions;
+use crate::ui::styling::fonts::{FontSettings, SCALE_RANGE};
+use crate::ui::styling::icons::{self, IconSet, get_icon};
+use crate::ui::styling::import::{ImportSource, import_theme};
+use crate::ui::styling::stylesheet::Severity;
+use crate::ui::styling::{css, fontconfig, themes};
 use eframe::egui;
 
 pub struct OptionsState<'a> {
     pub theme: &'a mut Theme,
+    pub css_theme: &'a mut Option<String>,
+    pub theme_preview: &'a mut Option<String>,
+    pub pywal_sync: &'a mut bool,
+    pub fonts: &'a mut FontSettings,
+    pub icon_set: &'a mut IconSet,
     pub column_visibility: &'a mut ColumnVisibility,
     pub search_options: &'a mut SearchOptions,
     pub zen_mode: &'a mut bool,
+    pub group_by_category: &'a mut bool,
     pub show_zen_info_modal: &'a mut bool,
     pub export_request: &'a mut bool,
 }
@@ -17,17 +28,45 @@ fn save_config(
     search_options: &SearchOptions,
     zen_mode: bool,
 ) {
-    let cfg = crate::config::UserConfig {
-        theme,
-        column_visibility: column_visibility.clone(),
-        search_options: search_options.clone(),
-        zen_mode,
-    };
+    let mut cfg = crate::config::load().unwrap_or_default();
+    cfg.theme = theme;
+    cfg.column_visibility = column_visibility.clone();
+    cfg.search_options = search_options.clone();
+    cfg.zen_mode = zen_mode;
+    let _ = crate::config::save(&cfg);
+}
+
+fn save_css_theme(css_theme: Option<String>) {
+    let mut cfg = crate::config::load().unwrap_or_default();
+    cfg.css_theme = css_theme;
+    let _ = crate::config::save(&cfg);
+}
+
+fn save_pywal_sync(pywal_sync: bool) {
+    let mut cfg = crate::config::load().unwrap_or_default();
+    cfg.pywal_sync = pywal_sync;
+    let _ = crate::config::save(&cfg);
+}
+
+fn save_fonts(fonts: &FontSettings) {
+    let mut cfg = crate::config::load().unwrap_or_default();
+    cfg.fonts = fonts.clone();
+    let _ = crate::config::save(&cfg);
+}
+
+fn save_icon_set(icon_set: IconSet) {
+    let mut cfg = crate::config::load().unwrap_or_default();
+    cfg.icon_set = icon_set;
+    let _ = crate::config::save(&cfg);
+}
+
+fn save_group_by_category(group_by_category: bool) {
+    let mut cfg = crate::config::load().unwrap_or_default();
+    cfg.group_by_category = group_by_category;
     let _ = crate::config::save(&cfg);
 }
 
 fn render_theme_section(
-    ctx: &egui::Context,
     ui: &mut egui::Ui,
     theme: &mut Theme,
     column_visibility: &ColumnVisibility,
@@ -43,10 +82,20 @@ fn render_theme_section(
 
         ui.add_space(8.0);
 
-        // Toggle switch (smaller)
-        let mut is_light = matches!(theme, Theme::Light);
+        // Toggle switch (smaller); shows the system's choice while following it
+        let follows_system = matches!(theme, Theme::System);
+        let mut is_light = match theme {
+            Theme::Light => true,
+            Theme::Dark => false,
+            Theme::System => !ui.visuals().dark_mode,
+        };
         let desired_size = egui::vec2(36.0, 18.0);
-        let (rect, response) = ui.allocate_exact_size(desired_size, egui::Sense::click());
+        let sense = if follows_system {
+            egui::Sense::hover()
+        } else {
+            egui::Sense::click()
+        };
+        let (rect, response) = ui.allocate_exact_size(desired_size, sense);
 
         if response.clicked() {
             is_light = !is_light;
@@ -55,11 +104,14 @@ fn render_theme_section(
             save_config(*theme, column_visibility, search_options, zen_mode);
         }
 
-        let bg_color = if is_light {
+        let mut bg_color = if is_light {
             egui::Color32::from_rgb(100, 149, 237)
         } else {
             egui::Color32::from_rgb(60, 60, 60)
         };
+        if follows_system {
+            bg_color = bg_color.gamma_multiply(0.5);
+        }
 
         let knob_offset = if is_light {
             rect.width() - rect.height()
@@ -82,24 +134,222 @@ fn render_theme_section(
         // Light label with icon
         let light_text = egui::RichText::new("\u{e30d} Light").size(13.0);
         ui.label(light_text);
+
+        ui.add_space(12.0);
+        let mut follow = follows_system;
+        if ui
+            .checkbox(&mut follow, "Follow system")
+            .on_hover_text("Use the desktop's light or dark preference")
+            .changed()
+        {
+            *theme = if follow {
+                Theme::System
+            } else if ui.visuals().dark_mode {
+                Theme::Dark
+            } else {
+                Theme::Light
+            };
+            save_config(*theme, column_visibility, search_options, zen_mode);
+        }
     });
     ui.add_space(8.0);
-    let tip = format!(
-        "Re-apply {}",
-        crate::ui::styling::css::default_css_path().to_string_lossy()
+}
+
+/// Picker of named CSS themes, previewing the hovered one, and the CSS reload button
+fn render_css_theme_section(
+    ctx: &egui::Context,
+    ui: &mut egui::Ui,
+    css_theme: &mut Option<String>,
+    theme_preview: &mut Option<String>,
+    pywal_sync: &mut bool,
+) {
+    let default_title = if css::has_custom_theme() {
+        "hyprbind-theme.css"
+    } else {
+        "Built-in"
+    };
+    let selected = css_theme.as_deref().and_then(themes::find_theme);
+    let selected_title = selected
+        .as_ref()
+        .map_or(default_title, |theme| theme.title.as_str());
+    ui.horizontal(|ui| {
+        ui.label("\u{f03d8}  CSS theme");
+        let previous = css_theme.clone();
+        egui::ComboBox::from_id_salt("css_theme")
+            .selected_text(selected_title)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(css_theme, None, default_title);
+                for theme in themes::list_themes() {
+                    let response =
+                        ui.selectable_value(css_theme, Some(theme.name.clone()), &theme.title);
+                    if response.hovered() {
+                        *theme_preview = Some(theme.name);
+                    }
+                }
+            });
+        if *css_theme != previous {
+            *theme_preview = None;
+            save_css_theme(css_theme.clone());
+        }
+    });
+    ui.add_space(4.0);
+
+    let tip = selected.as_ref().map_or_else(
+        || format!("Re-apply {}", css::default_css_path().to_string_lossy()),
+        |theme| format!("Re-apply {}", theme.title),
     );
-    if ui.button("Reload CSS").on_hover_text(tip).clicked() {
-        crate::ui::styling::css::apply_default_if_exists(ctx);
-    }
+    ui.horizontal(|ui| {
+        if ui.button("Reload CSS").on_hover_text(tip).clicked() {
+            match &selected {
+                Some(theme) => {
+                    let _ = theme.apply(ctx);
+                }
+                None => css::apply_default_if_exists(ctx),
+            }
+        }
+        render_css_summary(ctx, ui);
+    });
+    render_css_diagnostics(ctx, ui);
+    ui.add_space(4.0);
+    render_import_section(ui, css_theme, pywal_sync);
     ui.add_space(10.0);
 }
 
+/// Import colours from pywal, Hyprland or a base16 scheme as a named theme, and pick it
+fn render_import_section(ui: &mut egui::Ui, css_theme: &mut Option<String>, pywal_sync: &mut bool) {
+    let path_id = egui::Id::new("base16_path");
+    let result_id = egui::Id::new("theme_import_result");
+    ui.collapsing("\u{f019}  Import colours", |ui| {
+        let mut source = None;
+        ui.horizontal(|ui| {
+            if ui.button("pywal / wallust").clicked() {
+                source = Some(ImportSource::parse("pywal"));
+            }
+            if ui
+                .button("Hyprland borders")
+                .on_hover_text("general:col.active_border, inactive_border and shadow colour")
+                .clicked()
+            {
+                source = Some(Ok(ImportSource::Hyprland));
+            }
+        });
+        let mut path = ui.data_mut(|d| d.get_temp::<String>(path_id).unwrap_or_default());
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut path)
+                    .hint_text("base16 scheme .yaml")
+                    .desired_width(220.0),
+            );
+            if ui
+                .add_enabled(!path.trim().is_empty(), egui::Button::new("Import"))
+                .clicked()
+            {
+                source = Some(ImportSource::parse(path.trim()));
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(path_id, path));
+
+        if let Some(source) = source {
+            let result = source.and_then(|source| import_theme(&source, None));
+            let message = match result {
+                Ok((name, _)) => {
+                    let message = Ok(format!("Imported '{name}'"));
+                    *css_theme = Some(name);
+                    save_css_theme(css_theme.clone());
+                    message
+                }
+                Err(e) => Err(e),
+            };
+            ui.data_mut(|d| d.insert_temp::<Result<String, String>>(result_id, message));
+        }
+        match ui.data(|d| d.get_temp::<Result<String, String>>(result_id)) {
+            Some(Ok(message)) => {
+                ui.label(egui::RichText::new(format!("\u{f00c} {message}")).weak());
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, format!("⚠ {e}"));
+            }
+            None => {}
+        }
+
+        if ui
+            .checkbox(pywal_sync, "Auto-sync with pywal")
+            .on_hover_text("Re-import the pywal theme whenever colors.json changes")
+            .changed()
+        {
+            save_pywal_sync(*pywal_sync);
+        }
+    });
+}
+
+/// Count of problems found in the CSS file, next to "Reload CSS"
+fn render_css_summary(ctx: &egui::Context, ui: &mut egui::Ui) {
+    let Some(report) = css::css_report(ctx) else {
+        return;
+    };
+    match report.as_ref() {
+        Ok(diagnostics) if diagnostics.is_empty() => {
+            ui.label(egui::RichText::new("\u{f00c} No problems").weak());
+        }
+        Ok(diagnostics) => {
+            let errors = diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .count();
+            let warnings = diagnostics.len() - errors;
+            let color = if errors > 0 {
+                egui::Color32::RED
+            } else {
+                egui::Color32::YELLOW
+            };
+            ui.colored_label(color, format!("⚠ {errors} error(s), {warnings} warning(s)"));
+        }
+        Err(e) => {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {e}"));
+        }
+    }
+}
+
+/// Problems found in the CSS file, one per line with its position
+fn render_css_diagnostics(ctx: &egui::Context, ui: &mut egui::Ui) {
+    let Some(report) = css::css_report(ctx) else {
+        return;
+    };
+    let Ok(diagnostics) = report.as_ref() else {
+        return;
+    };
+    if diagnostics.is_empty() {
+        return;
+    }
+    ui.add_space(4.0);
+    egui::ScrollArea::vertical()
+        .id_salt("css_diagnostics")
+        .max_height(120.0)
+        .show(ui, |ui| {
+            for diagnostic in diagnostics {
+                let color = match diagnostic.severity {
+                    Severity::Error => egui::Color32::RED,
+                    Severity::Warning => egui::Color32::YELLOW,
+                };
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format!("{}:{}", diagnostic.line, diagnostic.column))
+                            .monospace()
+                            .weak(),
+                    );
+                    ui.colored_label(color, &diagnostic.message);
+                });
+            }
+        });
+}
+
 fn render_column_visibility_section(
     ui: &mut egui::Ui,
     theme: Theme,
     column_visibility: &mut ColumnVisibility,
     search_options: &SearchOptions,
     zen_mode: bool,
+    group_by_category: &mut bool,
 ) {
     ui.separator();
     ui.add_space(10.0);
@@ -109,9 +359,17 @@ fn render_column_visibility_section(
     let r1 = ui.checkbox(&mut column_visibility.keybind, "\u{ea65}  Keybind");
     let r2 = ui.checkbox(&mut column_visibility.description, "\u{f29e}  Description");
     let r3 = ui.checkbox(&mut column_visibility.command, "\u{ebc4}  Command");
-    if r1.changed() || r2.changed() || r3.changed() {
+    let r4 = ui.checkbox(&mut column_visibility.category, "\u{f02b}  Category");
+    if r1.changed() || r2.changed() || r3.changed() || r4.changed() {
         save_config(theme, column_visibility, search_options, zen_mode);
     }
+    ui.add_space(5.0);
+    if ui
+        .checkbox(group_by_category, "\u{f0c9}  Group by category")
+        .changed()
+    {
+        save_group_by_category(*group_by_category);
+    }
     ui.add_space(10.0);
 }
 
@@ -131,7 +389,8 @@ fn render_search_options_section(
     let s1 = ui.checkbox(&mut search_options.keybind, "\u{ea65}  Keybind");
     let s2 = ui.checkbox(&mut search_options.description, "\u{f29e}  Description");
     let s3 = ui.checkbox(&mut search_options.command, "\u{ebc4}  Command");
-    if s1.changed() || s2.changed() || s3.changed() {
+    let s4 = ui.checkbox(&mut search_options.annotations, "\u{f02c}  Notes & tags");
+    if s1.changed() || s2.changed() || s3.changed() || s4.changed() {
         save_config(theme, column_visibility, search_options, zen_mode);
     }
     ui.add_space(10.0);
@@ -164,6 +423,146 @@ fn render_zen_mode_section(
     ui.add_space(10.0);
 }
 
+/// Font weights and their names, as in the `OS/2` table
+const FONT_WEIGHTS: [(u16, &str); 9] = [
+    (100, "Thin"),
+    (200, "Extra Light"),
+    (300, "Light"),
+    (400, "Regular"),
+    (500, "Medium"),
+    (600, "Semi Bold"),
+    (700, "Bold"),
+    (800, "Extra Bold"),
+    (900, "Black"),
+];
+
+/// Family picker listing the installed font families after `default_title`
+fn font_family_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    family: &mut Option<String>,
+    default_title: &str,
+) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(family.as_deref().unwrap_or(default_title))
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(family, None, default_title);
+            for name in fontconfig::families(fontconfig::system_faces()) {
+                ui.selectable_value(family, Some(name.to_string()), name);
+            }
+        });
+}
+
+/// Interface and command fonts, weight and UI scale; the families are scanned the first
+/// time a picker opens
+fn render_fonts_section(ui: &mut egui::Ui, fonts: &mut FontSettings) {
+    ui.separator();
+    ui.add_space(10.0);
+
+    ui.heading("\u{f031}  Fonts");
+    ui.add_space(5.0);
+    let previous = fonts.clone();
+    egui::Grid::new("font_settings")
+        .num_columns(2)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            ui.label("Interface");
+            font_family_combo(
+                ui,
+                "font_family",
+                &mut fonts.family,
+                "Firple Bold (bundled)",
+            );
+            ui.end_row();
+
+            ui.label("Weight");
+            ui.add_enabled_ui(fonts.family.is_some(), |ui| {
+                let title = FONT_WEIGHTS
+                    .iter()
+                    .find(|(weight, _)| *weight == fonts.weight)
+                    .map_or_else(|| fonts.weight.to_string(), |(_, name)| (*name).to_string());
+                egui::ComboBox::from_id_salt("font_weight")
+                    .selected_text(title)
+                    .show_ui(ui, |ui| {
+                        for (weight, name) in FONT_WEIGHTS {
+                            ui.selectable_value(&mut fonts.weight, weight, name);
+                        }
+                    });
+            });
+            ui.end_row();
+
+            ui.label("Commands");
+            font_family_combo(
+                ui,
+                "font_monospace",
+                &mut fonts.monospace,
+                "Same as interface",
+            );
+            ui.end_row();
+
+            ui.label("Scale");
+            ui.horizontal(|ui| {
+                // Zooming while the slider is dragged would move it; apply on release
+                let id = ui.id().with("font_scale");
+                let mut percent = ui
+                    .data(|d| d.get_temp::<f32>(id))
+                    .unwrap_or(fonts.scale * 100.0);
+                let range = SCALE_RANGE.start() * 100.0..=SCALE_RANGE.end() * 100.0;
+                let response = ui
+                    .add(
+                        egui::Slider::new(&mut percent, range)
+                            .step_by(10.0)
+                            .suffix("%"),
+                    )
+                    .on_hover_text("Also Ctrl+= and Ctrl+-, Ctrl+0 resets");
+                if response.dragged() {
+                    ui.data_mut(|d| d.insert_temp(id, percent));
+                } else {
+                    ui.data_mut(|d| d.remove::<f32>(id));
+                    fonts.scale = percent / 100.0;
+                }
+                if ui.button("Reset").clicked() {
+                    fonts.scale = 1.0;
+                }
+            });
+            ui.end_row();
+        });
+    if *fonts != previous {
+        save_fonts(fonts);
+    }
+    ui.add_space(10.0);
+}
+
+/// Icon set picker with a sample of its keys
+fn render_icons_section(ui: &mut egui::Ui, icon_set: &mut IconSet) {
+    ui.horizontal(|ui| {
+        ui.label("\u{f030c}  Key icons");
+        for set in IconSet::ALL {
+            if ui.radio_value(icon_set, set, set.label()).changed() {
+                save_icon_set(set);
+                icons::load_icon_map(set);
+            }
+        }
+    });
+    let sample: Vec<String> = [
+        "SUPER",
+        "SHIFT",
+        "Return",
+        "XF86AudioRaiseVolume",
+        "mouse:272",
+    ]
+    .iter()
+    .map(|key| get_icon(key).trim().to_string())
+    .collect();
+    ui.label(egui::RichText::new(sample.join("  ")).weak())
+        .on_hover_text(format!(
+            "Labels of single keys can be replaced in {}",
+            crate::config::icons_path().display()
+        ));
+    ui.add_space(10.0);
+}
+
 fn render_export_section(ui: &mut egui::Ui, export_request: &mut bool) {
     ui.separator();
     ui.add_space(10.0);
@@ -180,19 +579,28 @@ fn render_export_section(ui: &mut egui::Ui, export_request: &mut bool) {
 
 pub fn render_options_contents(ctx: &egui::Context, ui: &mut egui::Ui, state: &mut OptionsState) {
     render_theme_section(
-        ctx,
         ui,
         state.theme,
         state.column_visibility,
         state.search_options,
         *state.zen_mode,
     );
+    render_css_theme_section(
+        ctx,
+        ui,
+        state.css_theme,
+        state.theme_preview,
+        state.pywal_sync,
+    );
+    render_fonts_section(ui, state.fonts);
+    render_icons_section(ui, state.icon_set);
     render_column_visibility_section(
         ui,
         *state.theme,
         state.column_visibility,
         state.search_options,
         *state.zen_mode,
+        state.group_by_category,
     );
     render_search_options_section(
         ui,
@@ -0,0 +1,452 @@
+use eframe::egui::Color32;
+
+/// CSS named colours, lowercase
+const NAMED_COLORS: [(&str, &str); 148] = [
+    ("aliceblue", "f0f8ff"),
+    ("antiquewhite", "faebd7"),
+    ("aqua", "00ffff"),
+    ("aquamarine", "7fffd4"),
+    ("azure", "f0ffff"),
+    ("beige", "f5f5dc"),
+    ("bisque", "ffe4c4"),
+    ("black", "000000"),
+    ("blanchedalmond", "ffebcd"),
+    ("blue", "0000ff"),
+    ("blueviolet", "8a2be2"),
+    ("brown", "a52a2a"),
+    ("burlywood", "deb887"),
+    ("cadetblue", "5f9ea0"),
+    ("chartreuse", "7fff00"),
+    ("chocolate", "d2691e"),
+    ("coral", "ff7f50"),
+    ("cornflowerblue", "6495ed"),
+    ("cornsilk", "fff8dc"),
+    ("crimson", "dc143c"),
+    ("cyan", "00ffff"),
+    ("darkblue", "00008b"),
+    ("darkcyan", "008b8b"),
+    ("darkgoldenrod", "b8860b"),
+    ("darkgray", "a9a9a9"),
+    ("darkgreen", "006400"),
+    ("darkgrey", "a9a9a9"),
+    ("darkkhaki", "bdb76b"),
+    ("darkmagenta", "8b008b"),
+    ("darkolivegreen", "556b2f"),
+    ("darkorange", "ff8c00"),
+    ("darkorchid", "9932cc"),
+    ("darkred", "8b0000"),
+    ("darksalmon", "e9967a"),
+    ("darkseagreen", "8fbc8f"),
+    ("darkslateblue", "483d8b"),
+    ("darkslategray", "2f4f4f"),
+    ("darkslategrey", "2f4f4f"),
+    ("darkturquoise", "00ced1"),
+    ("darkviolet", "9400d3"),
+    ("deeppink", "ff1493"),
+    ("deepskyblue", "00bfff"),
+    ("dimgray", "696969"),
+    ("dimgrey", "696969"),
+    ("dodgerblue", "1e90ff"),
+    ("firebrick", "b22222"),
+    ("floralwhite", "fffaf0"),
+    ("forestgreen", "228b22"),
+    ("fuchsia", "ff00ff"),
+    ("gainsboro", "dcdcdc"),
+    ("ghostwhite", "f8f8ff"),
+    ("gold", "ffd700"),
+    ("goldenrod", "daa520"),
+    ("gray", "808080"),
+    ("green", "008000"),
+    ("greenyellow", "adff2f"),
+    ("grey", "808080"),
+    ("honeydew", "f0fff0"),
+    ("hotpink", "ff69b4"),
+    ("indianred", "cd5c5c"),
+    ("indigo", "4b0082"),
+    ("ivory", "fffff0"),
+    ("khaki", "f0e68c"),
+    ("lavender", "e6e6fa"),
+    ("lavenderblush", "fff0f5"),
+    ("lawngreen", "7cfc00"),
+    ("lemonchiffon", "fffacd"),
+    ("lightblue", "add8e6"),
+    ("lightcoral", "f08080"),
+    ("lightcyan", "e0ffff"),
+    ("lightgoldenrodyellow", "fafad2"),
+    ("lightgray", "d3d3d3"),
+    ("lightgreen", "90ee90"),
+    ("lightgrey", "d3d3d3"),
+    ("lightpink", "ffb6c1"),
+    ("lightsalmon", "ffa07a"),
+    ("lightseagreen", "20b2aa"),
+    ("lightskyblue", "87cefa"),
+    ("lightslategray", "778899"),
+    ("lightslategrey", "778899"),
+    ("lightsteelblue", "b0c4de"),
+    ("lightyellow", "ffffe0"),
+    ("lime", "00ff00"),
+    ("limegreen", "32cd32"),
+    ("linen", "faf0e6"),
+    ("magenta", "ff00ff"),
+    ("maroon", "800000"),
+    ("mediumaquamarine", "66cdaa"),
+    ("mediumblue", "0000cd"),
+    ("mediumorchid", "ba55d3"),
+    ("mediumpurple", "9370db"),
+    ("mediumseagreen", "3cb371"),
+    ("mediumslateblue", "7b68ee"),
+    ("mediumspringgreen", "00fa9a"),
+    ("mediumturquoise", "48d1cc"),
+    ("mediumvioletred", "c71585"),
+    ("midnightblue", "191970"),
+    ("mintcream", "f5fffa"),
+    ("mistyrose", "ffe4e1"),
+    ("moccasin", "ffe4b5"),
+    ("navajowhite", "ffdead"),
+    ("navy", "000080"),
+    ("oldlace", "fdf5e6"),
+    ("olive", "808000"),
+    ("olivedrab", "6b8e23"),
+    ("orange", "ffa500"),
+    ("orangered", "ff4500"),
+    ("orchid", "da70d6"),
+    ("palegoldenrod", "eee8aa"),
+    ("palegreen", "98fb98"),
+    ("paleturquoise", "afeeee"),
+    ("palevioletred", "db7093"),
+    ("papayawhip", "ffefd5"),
+    ("peachpuff", "ffdab9"),
+    ("peru", "cd853f"),
+    ("pink", "ffc0cb"),
+    ("plum", "dda0dd"),
+    ("powderblue", "b0e0e6"),
+    ("purple", "800080"),
+    ("rebeccapurple", "663399"),
+    ("red", "ff0000"),
+    ("rosybrown", "bc8f8f"),
+    ("royalblue", "4169e1"),
+    ("saddlebrown", "8b4513"),
+    ("salmon", "fa8072"),
+    ("sandybrown", "f4a460"),
+    ("seagreen", "2e8b57"),
+    ("seashell", "fff5ee"),
+    ("sienna", "a0522d"),
+    ("silver", "c0c0c0"),
+    ("skyblue", "87ceeb"),
+    ("slateblue", "6a5acd"),
+    ("slategray", "708090"),
+    ("slategrey", "708090"),
+    ("snow", "fffafa"),
+    ("springgreen", "00ff7f"),
+    ("steelblue", "4682b4"),
+    ("tan", "d2b48c"),
+    ("teal", "008080"),
+    ("thistle", "d8bfd8"),
+    ("tomato", "ff6347"),
+    ("turquoise", "40e0d0"),
+    ("violet", "ee82ee"),
+    ("wheat", "f5deb3"),
+    ("white", "ffffff"),
+    ("whitesmoke", "f5f5f5"),
+    ("yellow", "ffff00"),
+    ("yellowgreen", "9acd32"),
+];
+
+/// A colour with `0.0..=1.0` channels, not premultiplied
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rgba {
+    red: f32,
+    green: f32,
+    blue: f32,
+    alpha: f32,
+}
+
+impl Rgba {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn to_color32(self) -> Color32 {
+        let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color32::from_rgba_unmultiplied(
+            byte(self.red),
+            byte(self.green),
+            byte(self.blue),
+            byte(self.alpha),
+        )
+    }
+
+    fn from_bytes([red, green, blue, alpha]: [u8; 4]) -> Self {
+        let channel = |c: u8| f32::from(c) / 255.0;
+        Self {
+            red: channel(red),
+            green: channel(green),
+            blue: channel(blue),
+            alpha: channel(alpha),
+        }
+    }
+
+    fn map_channels(self, f: impl Fn(f32) -> f32) -> Self {
+        Self {
+            red: f(self.red),
+            green: f(self.green),
+            blue: f(self.blue),
+            alpha: self.alpha,
+        }
+    }
+}
+
+/// Parse a CSS Color Level 4 colour: hex (3, 4, 6 or 8 digits), `rgb()`, `rgba()`, `hsl()`,
+/// `hsla()`, named colours, `transparent` and `color-mix()`
+///
+/// `var()` references must be resolved first, see `CssTheme::resolve`.
+pub fn parse_color(value: &str) -> Option<Color32> {
+    parse_rgba(value).map(Rgba::to_color32)
+}
+
+fn parse_rgba(value: &str) -> Option<Rgba> {
+    let value = value.trim().to_ascii_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if value == "transparent" {
+        return Some(Rgba::from_bytes([0; 4]));
+    }
+    if let Some((name, args)) = function(&value) {
+        return match name {
+            "rgb" | "rgba" => parse_rgb_function(args),
+            "hsl" | "hsla" => parse_hsl_function(args),
+            "color-mix" => parse_color_mix(args),
+            _ => None,
+        };
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| *name == value)
+        .and_then(|(_, hex)| parse_hex(hex))
+}
+
+/// `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, without the `#`
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u8> = match hex.len() {
+        3 | 4 => hex
+            .chars()
+            .map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16).ok())
+            .collect::<Option<_>>()?,
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    let alpha = digits.get(3).copied().unwrap_or(255);
+    Some(Rgba::from_bytes([digits[0], digits[1], digits[2], alpha]))
+}
+
+/// Name and arguments of `name(args)`
+fn function(value: &str) -> Option<(&str, &str)> {
+    let open = value.find('(')?;
+    let args = value[open + 1..].strip_suffix(')')?;
+    let name = value[..open].trim();
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic() || c == '-'))
+        .then_some((name, args))
+}
+
+/// Split at `separator` outside of parentheses
+pub(super) fn split_top_level(args: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && separator(c) => {
+                parts.push(&args[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Channels and optional alpha, in either the legacy comma or the modern space syntax
+fn components(args: &str) -> Option<(Vec<&str>, Option<&str>)> {
+    if args.contains(',') {
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        return match parts.len() {
+            3 => Some((parts, None)),
+            4 => {
+                let alpha = parts.pop();
+                Some((parts, alpha))
+            }
+            _ => None,
+        };
+    }
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let channels: Vec<&str> = channels.split_whitespace().collect();
+    (channels.len() == 3).then_some((channels, alpha))
+}
+
+/// A number, `none` (zero), or a percentage scaled so that `100%` is `full`
+fn number_or_percentage(value: &str, full: f32) -> Option<f32> {
+    if value == "none" {
+        return Some(0.0);
+    }
+    let number = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0 * full,
+        None => value.parse::<f32>().ok()?,
+    };
+    number.is_finite().then_some(number)
+}
+
+fn parse_alpha(alpha: Option<&str>) -> Option<f32> {
+    alpha.map_or(Some(1.0), |a| {
+        number_or_percentage(a, 1.0).map(|a| a.clamp(0.0, 1.0))
+    })
+}
+
+fn parse_rgb_function(args: &str) -> Option<Rgba> {
+    let (channels, alpha) = components(args)?;
+    let channel = |v: &str| number_or_percentage(v, 255.0).map(|c| c.clamp(0.0, 255.0) / 255.0);
+    Some(Rgba {
+        red: channel(channels[0])?,
+        green: channel(channels[1])?,
+        blue: channel(channels[2])?,
+        alpha: parse_alpha(alpha)?,
+    })
+}
+
+/// Hue in degrees: a number, or an angle in `deg`, `rad`, `grad` or `turn`
+fn parse_hue(value: &str) -> Option<f32> {
+    if value == "none" {
+        return Some(0.0);
+    }
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f32::consts::PI),
+        ("turn", 360.0),
+    ];
+    let (number, scale) = units
+        .iter()
+        .find_map(|(unit, scale)| value.strip_suffix(unit).map(|n| (n, *scale)))
+        .unwrap_or((value, 1.0));
+    let degrees = number.parse::<f32>().ok()? * scale;
+    degrees.is_finite().then(|| degrees.rem_euclid(360.0))
+}
+
+fn parse_hsl_function(args: &str) -> Option<Rgba> {
+    let (channels, alpha) = components(args)?;
+    let hue = parse_hue(channels[0])?;
+    let percent = |v: &str| number_or_percentage(v, 100.0).map(|p| p.clamp(0.0, 100.0) / 100.0);
+    let saturation = percent(channels[1])?;
+    let lightness = percent(channels[2])?;
+
+    let channel = |n: f32| {
+        let k = (n + hue / 30.0) % 12.0;
+        let a = saturation * lightness.min(1.0 - lightness);
+        (-a).mul_add((k - 3.0).min(9.0 - k).clamp(-1.0, 1.0), lightness)
+    };
+    Some(Rgba {
+        red: channel(0.0),
+        green: channel(8.0),
+        blue: channel(4.0),
+        alpha: parse_alpha(alpha)?,
+    })
+}
+
+fn to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055f32.mul_add(c.powf(1.0 / 2.4), -0.055)
+    }
+}
+
+/// A colour and its optional percentage in `color-mix()`, in either order
+fn mix_component(part: &str) -> Option<(Rgba, Option<f32>)> {
+    let tokens = split_top_level(part, char::is_whitespace);
+    let (percent, color): (Vec<&str>, Vec<&str>) =
+        tokens.into_iter().partition(|t| t.ends_with('%'));
+    let percent = match percent[..] {
+        [] => None,
+        [p] => Some(number_or_percentage(p, 1.0).filter(|p| (0.0..=1.0).contains(p))?),
+        _ => return None,
+    };
+    Some((parse_rgba(&color.join(" "))?, percent))
+}
+
+/// `color-mix(in srgb | srgb-linear, color [p%], color [p%])`
+fn parse_color_mix(args: &str) -> Option<Rgba> {
+    let parts = split_top_level(args, |c| c == ',');
+    let [space, first, second] = parts[..] else {
+        return None;
+    };
+    let linear = match space.split_whitespace().collect::<Vec<_>>()[..] {
+        ["in", "srgb"] => false,
+        ["in", "srgb-linear"] => true,
+        _ => return None,
+    };
+    let (first, p1) = mix_component(first)?;
+    let (second, p2) = mix_component(second)?;
+    let (p1, p2) = match (p1, p2) {
+        (None, None) => (0.5, 0.5),
+        (Some(p1), None) => (p1, 1.0 - p1),
+        (None, Some(p2)) => (1.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+    let total = p1 + p2;
+    if total <= 0.0 {
+        return None;
+    }
+    // Percentages adding up to less than 100% make the result transparent by the rest
+    let alpha_scale = total.min(1.0);
+    let (p1, p2) = (p1 / total, p2 / total);
+
+    let (first, second) = if linear {
+        (
+            first.map_channels(to_linear),
+            second.map_channels(to_linear),
+        )
+    } else {
+        (first, second)
+    };
+    // Mix premultiplied so transparent colours don't darken the result
+    let alpha = first.alpha.mul_add(p1, second.alpha * p2);
+    let channel = |a: f32, b: f32| {
+        if alpha > 0.0 {
+            (a * first.alpha).mul_add(p1, b * second.alpha * p2) / alpha
+        } else {
+            0.0
+        }
+    };
+    let mixed = Rgba {
+        red: channel(first.red, second.red),
+        green: channel(first.green, second.green),
+        blue: channel(first.blue, second.blue),
+        alpha: alpha * alpha_scale,
+    };
+    Some(if linear {
+        mixed.map_channels(from_linear)
+    } else {
+        mixed
+    })
+}
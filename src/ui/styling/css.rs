@@ -1,33 +1,25 @@
+use super::color::parse_color;
+use super::stylesheet::{Diagnostic, parse_stylesheet};
 use eframe::egui;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 const DEFAULT_RADIUS: u8 = 6;
 const DEFAULT_SPACING: i8 = 6;
 
-fn parse_hex_color(hex: &str) -> Option<egui::Color32> {
-    let s = hex.trim();
-    let hex = s.strip_prefix('#').unwrap_or(s);
-    let (red, green, blue, alpha) = match hex.len() {
-        6 => {
-            let red = u8::from_str_radix(&hex[0..2], 16).ok()?;
-            let green = u8::from_str_radix(&hex[2..4], 16).ok()?;
-            let blue = u8::from_str_radix(&hex[4..6], 16).ok()?;
-            (red, green, blue, 255)
-        }
-        8 => {
-            let red = u8::from_str_radix(&hex[0..2], 16).ok()?;
-            let green = u8::from_str_radix(&hex[2..4], 16).ok()?;
-            let blue = u8::from_str_radix(&hex[4..6], 16).ok()?;
-            let alpha = u8::from_str_radix(&hex[6..8], 16).ok()?;
-            (red, green, blue, alpha)
-        }
-        _ => return None,
-    };
-    Some(egui::Color32::from_rgba_unmultiplied(
-        red, green, blue, alpha,
-    ))
-}
+/// `:root` variables styling the whole window, and whether each is a colour or a number
+const THEME_VARS: [(&str, bool); 8] = [
+    ("bg", true),
+    ("fg", true),
+    ("panel", true),
+    ("accent", true),
+    ("stroke", true),
+    ("selection", true),
+    ("radius", false),
+    ("spacing", false),
+];
 
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 fn parse_radius(s: &str) -> Option<u8> {
@@ -45,61 +37,464 @@ fn parse_spacing(s: &str) -> Option<i8> {
     })
 }
 
-fn extract_vars(contents: &str) -> std::collections::HashMap<String, String> {
-    let mut map = std::collections::HashMap::new();
-    for line in contents.lines() {
-        let line = line.trim();
-        if line.starts_with("--")
-            && let Some(colon) = line.find(':')
-        {
-            let key = &line[..colon].trim();
-            let mut value = &line[colon + 1..];
-            if let Some(semi) = value.find(';') {
-                value = &value[..semi];
+/// `HyprBind` elements that CSS rules can style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Element {
+    Header,
+    Search,
+    Row,
+    EvenRow,
+    OddRow,
+    Keycap,
+    /// Key caps of modifiers
+    Modifier,
+    Description,
+    Command,
+    Stats,
+}
+
+impl Element {
+    pub const ALL: [Self; 10] = [
+        Self::Header,
+        Self::Search,
+        Self::Row,
+        Self::EvenRow,
+        Self::OddRow,
+        Self::Keycap,
+        Self::Modifier,
+        Self::Description,
+        Self::Command,
+        Self::Stats,
+    ];
+
+    pub const fn selector(self) -> &'static str {
+        match self {
+            Self::Header => ".header",
+            Self::Search => ".search",
+            Self::Row => ".table row",
+            Self::EvenRow => ".table row:nth-child(even)",
+            Self::OddRow => ".table row:nth-child(odd)",
+            Self::Keycap => ".keycap",
+            Self::Modifier => ".keycap.modifier",
+            Self::Description => ".description",
+            Self::Command => ".command",
+            Self::Stats => ".stats",
+        }
+    }
+
+    /// Element for a selector with whitespace collapsed
+    pub fn from_selector(selector: &str) -> Option<Self> {
+        let selector = match selector {
+            ".table row:nth-child(2n)" => ".table row:nth-child(even)",
+            ".table row:nth-child(2n+1)" => ".table row:nth-child(odd)",
+            ".modifier.keycap" => ".keycap.modifier",
+            other => other,
+        };
+        Self::ALL.into_iter().find(|e| e.selector() == selector)
+    }
+
+    /// Element whose style this one builds on
+    const fn parent(self) -> Option<Self> {
+        match self {
+            Self::EvenRow | Self::OddRow => Some(Self::Row),
+            Self::Modifier => Some(Self::Keycap),
+            _ => None,
+        }
+    }
+}
+
+/// Properties set for an element; unset ones keep the built-in look
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ElementStyle {
+    pub color: Option<egui::Color32>,
+    pub background: Option<egui::Color32>,
+    pub border_color: Option<egui::Color32>,
+    pub border_radius: Option<f32>,
+    pub font_size: Option<f32>,
+    pub padding: Option<egui::Margin>,
+}
+
+impl ElementStyle {
+    /// Set a property from its CSS value
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let set = match name {
+            "color" => set_if_some(&mut self.color, parse_color(value)),
+            "background" | "background-color" => {
+                set_if_some(&mut self.background, parse_color(value))
+            }
+            "border-color" => set_if_some(&mut self.border_color, parse_color(value)),
+            "border-radius" => set_if_some(&mut self.border_radius, parse_length(value)),
+            "font-size" => set_if_some(&mut self.font_size, parse_length(value)),
+            "padding" => set_if_some(&mut self.padding, parse_padding(value)),
+            _ => return Err(format!("unknown property '{name}'")),
+        };
+        if set {
+            Ok(())
+        } else {
+            Err(format!("invalid value '{value}' for '{name}'"))
+        }
+    }
+
+    /// Properties of `over` on top of these
+    #[must_use]
+    pub fn merge(self, over: Self) -> Self {
+        Self {
+            color: over.color.or(self.color),
+            background: over.background.or(self.background),
+            border_color: over.border_color.or(self.border_color),
+            border_radius: over.border_radius.or(self.border_radius),
+            font_size: over.font_size.or(self.font_size),
+            padding: over.padding.or(self.padding),
+        }
+    }
+
+    /// Apply `color`, `background` and `font-size` to text
+    pub fn text(&self, mut text: egui::RichText) -> egui::RichText {
+        if let Some(color) = self.color {
+            text = text.color(color);
+        }
+        if let Some(background) = self.background {
+            text = text.background_color(background);
+        }
+        if let Some(size) = self.font_size {
+            text = text.size(size);
+        }
+        text
+    }
+
+    /// Apply `background`, `border-color`, `border-radius` and `padding` to a frame
+    pub fn frame(&self, mut frame: egui::Frame) -> egui::Frame {
+        if let Some(background) = self.background {
+            frame = frame.fill(background);
+        }
+        if let Some(color) = self.border_color {
+            frame.stroke.color = color;
+        }
+        if let Some(radius) = self.border_radius {
+            frame = frame.corner_radius(radius);
+        }
+        if let Some(padding) = self.padding {
+            frame = frame.inner_margin(padding);
+        }
+        frame
+    }
+}
+
+fn set_if_some<T>(field: &mut Option<T>, value: Option<T>) -> bool {
+    let set = value.is_some();
+    if set {
+        *field = value;
+    }
+    set
+}
+
+/// A length in `px`, or without a unit
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value);
+    number
+        .parse::<f32>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+}
+
+/// `padding` with one to four lengths: all, vertical horizontal, top horizontal bottom,
+/// or top right bottom left
+#[allow(clippy::cast_possible_truncation)]
+fn parse_padding(value: &str) -> Option<egui::Margin> {
+    let lengths: Vec<i8> = value
+        .split_whitespace()
+        .map(|v| parse_length(v).map(|n| n.min(f32::from(i8::MAX)).round() as i8))
+        .collect::<Option<_>>()?;
+    let (top, right, bottom, left) = match lengths[..] {
+        [all] => (all, all, all, all),
+        [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+        [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+        [top, right, bottom, left] => (top, right, bottom, left),
+        _ => return None,
+    };
+    Some(egui::Margin {
+        left,
+        right,
+        top,
+        bottom,
+    })
+}
+
+/// Custom properties and element styles of a CSS file
+#[derive(Debug, Clone, Default)]
+pub struct CssTheme {
+    /// `--name: value` custom properties, without the dashes
+    pub vars: HashMap<String, String>,
+    pub elements: HashMap<Element, ElementStyle>,
+}
+
+impl CssTheme {
+    /// Parse, also returning the problems found, in source order
+    ///
+    /// Later rules override earlier ones; rules for unknown selectors are ignored.
+    /// Custom properties apply everywhere, whichever rule defines them, so `var()` can refer
+    /// to ones defined further down.
+    pub fn check(contents: &str) -> (Self, Vec<Diagnostic>) {
+        let sheet = parse_stylesheet(contents);
+        let mut diagnostics = sheet.diagnostics;
+        let mut theme = Self::default();
+        let mut var_positions = HashMap::new();
+        for declaration in sheet.rules.iter().flat_map(|r| &r.declarations) {
+            if let Some(name) = declaration.name.strip_prefix("--") {
+                theme
+                    .vars
+                    .insert(name.to_string(), declaration.value.clone());
+                var_positions.insert(name, (declaration.line, declaration.column));
+            }
+        }
+        for rule in &sheet.rules {
+            let elements: Vec<Element> = rule
+                .selectors
+                .iter()
+                .filter_map(|s| Element::from_selector(s))
+                .collect();
+            let has_properties = rule.declarations.iter().any(|d| !d.name.starts_with("--"));
+            if has_properties {
+                for selector in &rule.selectors {
+                    if Element::from_selector(selector).is_none() {
+                        diagnostics.push(Diagnostic::warning(
+                            format!("unknown selector '{selector}', its properties are ignored"),
+                            rule.line,
+                            rule.column,
+                        ));
+                    }
+                }
             }
-            map.insert(
-                key.trim().trim_start_matches('-').to_string(),
-                value.trim().to_string(),
-            );
-        }
-        // Also handle lines within :root { ... }
-        if let Some(start) = line.find("--") {
-            let rest = &line[start..];
-            if let Some(colon) = rest.find(':') {
-                let key = &rest[..colon].trim();
-                let mut value = &rest[colon + 1..];
-                if let Some(semi) = value.find(';') {
-                    value = &value[..semi];
+            for declaration in &rule.declarations {
+                if elements.is_empty() || declaration.name.starts_with("--") {
+                    continue;
+                }
+                let error =
+                    |message| Diagnostic::error(message, declaration.line, declaration.column);
+                let value = match theme.resolve(&declaration.value) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        diagnostics.push(error(e));
+                        continue;
+                    }
+                };
+                if let Err(e) = ElementStyle::default().set(&declaration.name, &value) {
+                    diagnostics.push(error(e));
+                    continue;
                 }
-                if key.starts_with("--") {
-                    map.insert(
-                        key.trim().trim_start_matches('-').to_string(),
-                        value.trim().to_string(),
-                    );
+                for element in &elements {
+                    let _ = theme
+                        .elements
+                        .entry(*element)
+                        .or_default()
+                        .set(&declaration.name, &value);
                 }
             }
         }
+
+        // Variables styling the whole window
+        for (name, is_color) in THEME_VARS {
+            let Some(&(line, column)) = var_positions.get(name) else {
+                continue;
+            };
+            let valid = match theme.resolve(&format!("var(--{name})")) {
+                Ok(value) if is_color => parse_color(&value).is_some(),
+                Ok(value) => parse_spacing(&value).is_some(),
+                Err(e) => {
+                    diagnostics.push(Diagnostic::error(e, line, column));
+                    continue;
+                }
+            };
+            if !valid {
+                let kind = if is_color { "colour" } else { "number" };
+                diagnostics.push(Diagnostic::error(
+                    format!("--{name} must be a {kind}, got '{}'", theme.vars[name]),
+                    line,
+                    column,
+                ));
+            }
+        }
+        diagnostics.sort_by_key(|d| (d.line, d.column));
+        (theme, diagnostics)
+    }
+
+    /// Value of a custom property, with its `var()` references resolved
+    pub fn var(&self, name: &str) -> Option<String> {
+        self.resolve(&format!("var(--{name})")).ok()
+    }
+
+    /// Replace `var(--name)` and `var(--name, fallback)` references with their values
+    ///
+    /// The fallback is used when the property is missing or part of a cycle.
+    pub fn resolve(&self, value: &str) -> Result<String, String> {
+        self.resolve_with(value, &mut Vec::new())
+    }
+
+    fn resolve_with(&self, value: &str, stack: &mut Vec<String>) -> Result<String, String> {
+        let mut out = String::new();
+        let mut rest = value;
+        while let Some(start) = rest.find("var(") {
+            out.push_str(&rest[..start]);
+            let args_start = start + "var(".len();
+            let end = closing_paren(&rest[args_start..])
+                .map(|i| args_start + i)
+                .ok_or_else(|| format!("unclosed var() in '{value}'"))?;
+            let args = &rest[args_start..end];
+            let (name, fallback) = match args.split_once(',') {
+                Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+                None => (args.trim(), None),
+            };
+            let key = name
+                .strip_prefix("--")
+                .ok_or_else(|| format!("var() needs a custom property, got '{name}'"))?;
+
+            let resolved = match self.vars.get(key) {
+                _ if stack.iter().any(|s| s == key) => {
+                    stack.push(key.to_string());
+                    let cycle = stack
+                        .iter()
+                        .skip_while(|s| *s != key)
+                        .map(|s| format!("--{s}"))
+                        .collect::<Vec<_>>()
+                        .join(" -> ");
+                    stack.pop();
+                    Err(format!("variable cycle: {cycle}"))
+                }
+                Some(var) => {
+                    stack.push(key.to_string());
+                    let resolved = self.resolve_with(var, stack);
+                    stack.pop();
+                    resolved
+                }
+                None => Err(format!("unknown variable '{name}'")),
+            };
+            let resolved = match (resolved, fallback) {
+                (Ok(resolved), _) => resolved,
+                (Err(_), Some(fallback)) => self.resolve_with(fallback, stack)?,
+                (Err(e), None) => return Err(e),
+            };
+            out.push_str(&resolved);
+            rest = &rest[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out.trim().to_string())
+    }
+
+    /// Style of an element, on top of the element it builds on
+    pub fn style(&self, element: Element) -> ElementStyle {
+        let own = self.elements.get(&element).copied().unwrap_or_default();
+        element
+            .parent()
+            .map_or(own, |parent| self.style(parent).merge(own))
     }
-    map
 }
 
-pub fn apply_from_path(ctx: &egui::Context, path: &str) -> Result<(), String> {
+/// Index of the `)` closing a group whose `(` came just before `text`
+fn closing_paren(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn theme_id() -> egui::Id {
+    egui::Id::new("css_theme")
+}
+
+/// Style of an element from the applied CSS file; empty without one
+pub fn element_style(ctx: &egui::Context, element: Element) -> ElementStyle {
+    ctx.data(|d| d.get_temp::<Arc<CssTheme>>(theme_id()))
+        .map(|theme| theme.style(element))
+        .unwrap_or_default()
+}
+
+/// Forget element styles and the CSS report, e.g. when switching to a built-in theme
+pub fn clear_element_styles(ctx: &egui::Context) {
+    ctx.data_mut(|d| {
+        d.remove::<Arc<CssTheme>>(theme_id());
+        d.remove::<Arc<CssReport>>(report_id());
+    });
+}
+
+/// Whether a colour is closer to white than to black
+fn is_light(color: egui::Color32) -> bool {
+    let luma = 0.2126f32.mul_add(
+        f32::from(color.r()),
+        0.7152f32.mul_add(f32::from(color.g()), 0.0722 * f32::from(color.b())),
+    );
+    luma > 127.5
+}
+
+/// Problems in the CSS file when it was last applied, or why it could not be read
+pub type CssReport = Result<Vec<Diagnostic>, String>;
+
+fn report_id() -> egui::Id {
+    egui::Id::new("css_report")
+}
+
+/// Report of the last `apply_from_path`, if a CSS file was applied
+pub fn css_report(ctx: &egui::Context) -> Option<Arc<CssReport>> {
+    ctx.data(|d| d.get_temp::<Arc<CssReport>>(report_id()))
+}
+
+/// Read and check a CSS file without applying it
+pub fn check_file(path: &Path) -> CssReport {
     let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read CSS: {e}"))?;
-    let vars = extract_vars(&contents);
+    Ok(CssTheme::check(&contents).1)
+}
+
+/// Apply a CSS file, returning the problems found in it
+///
+/// Parts with problems are skipped; the report is kept for the options window.
+pub fn apply_from_path(ctx: &egui::Context, path: &str) -> CssReport {
+    apply_css(
+        ctx,
+        fs::read_to_string(path).map_err(|e| format!("Failed to read CSS: {e}")),
+    )
+}
+
+/// Apply CSS text, or keep the reason it could not be read as the report
+pub fn apply_css(ctx: &egui::Context, contents: Result<String, String>) -> CssReport {
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(e) => {
+            let report: CssReport = Err(e);
+            ctx.data_mut(|d| d.insert_temp(report_id(), Arc::new(report.clone())));
+            return report;
+        }
+    };
+    let (theme, diagnostics) = CssTheme::check(&contents);
 
     // Expected variables (all optional):
     // bg, fg, panel, accent, stroke, selection, radius, spacing
-    let bg = vars.get("bg").and_then(|v| parse_hex_color(v));
-    let fg = vars.get("fg").and_then(|v| parse_hex_color(v));
-    let panel = vars.get("panel").and_then(|v| parse_hex_color(v));
-    let accent = vars.get("accent").and_then(|v| parse_hex_color(v));
-    let stroke = vars.get("stroke").and_then(|v| parse_hex_color(v));
-    let selection = vars.get("selection").and_then(|v| parse_hex_color(v));
-    let radius = vars.get("radius").and_then(|v| parse_radius(v));
-    let spacing = vars.get("spacing").and_then(|v| parse_spacing(v));
+    let bg = theme.var("bg").and_then(|v| parse_color(&v));
+    let fg = theme.var("fg").and_then(|v| parse_color(&v));
+    let panel = theme.var("panel").and_then(|v| parse_color(&v));
+    let accent = theme.var("accent").and_then(|v| parse_color(&v));
+    let stroke = theme.var("stroke").and_then(|v| parse_color(&v));
+    let selection = theme.var("selection").and_then(|v| parse_color(&v));
+    let radius = theme.var("radius").and_then(|v| parse_radius(&v));
+    let spacing = theme.var("spacing").and_then(|v| parse_spacing(&v));
 
+    // Start from the built-in look matching the background, so nothing is left over from
+    // a previously applied theme
     let mut style = (*ctx.style()).clone();
-    let mut visuals = style.visuals.clone();
+    let mut visuals = match bg {
+        Some(bg) if is_light(bg) => egui::Visuals::light(),
+        _ => egui::Visuals::dark(),
+    };
+    let default_spacing = egui::style::Spacing::default();
+    style.spacing.item_spacing = default_spacing.item_spacing;
+    style.spacing.button_padding = default_spacing.button_padding;
+    style.spacing.menu_margin = default_spacing.menu_margin;
+    style.spacing.window_margin = default_spacing.window_margin;
 
     if let Some(fg) = fg {
         visuals.override_text_color = Some(fg);
@@ -153,8 +548,13 @@ pub fn apply_from_path(ctx: &egui::Context, path: &str) -> Result<(), String> {
     }
     style.visuals = visuals;
     ctx.set_style(style);
+    ctx.data_mut(|d| {
+        d.insert_temp(theme_id(), Arc::new(theme));
+        let report: CssReport = Ok(diagnostics.clone());
+        d.insert_temp(report_id(), Arc::new(report));
+    });
 
-    Ok(())
+    Ok(diagnostics)
 }
 
 pub fn default_css_path() -> PathBuf {
@@ -191,15 +591,14 @@ pub fn write_default_css(overwrite: bool) -> Result<PathBuf, String> {
     let path = default_css_path();
     if path.exists() && !overwrite {
         return Err(format!(
-            "CSS already exists at {} (use --force-write-default-css to overwrite)",
+            "CSS already exists at {} (use `hyprbind theme write-default --force` to overwrite)",
             path.to_string_lossy()
         ));
     }
 
     let epoch = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
+        .map_or(0, |d| d.as_secs());
 
     let css = format!(
         "/* HyprBind default CSS generated at epoch {epoch} */\n:root {{\n  --bg: #0f1117;\n  --fg: #d4d7dc;\n  --panel: #151922;\n  --accent: #7aa2f7;\n  --stroke: #3b4261;\n  --selection: #283457;\n  --radius: {DEFAULT_RADIUS};\n  --spacing: {DEFAULT_SPACING};\n}}\n"
@@ -0,0 +1,297 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use ttf_parser::{Language, name, name_id, os2};
+
+/// Maximum `<include>` nesting followed when reading fontconfig files
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Maximum directory depth searched for font files
+const MAX_DIR_DEPTH: usize = 8;
+
+/// Font file extensions, lowercase
+const FONT_EXTENSIONS: [&str; 4] = ["ttf", "otf", "ttc", "otc"];
+
+/// A font in a file on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontFace {
+    pub path: PathBuf,
+    /// Face index inside a font collection (`.ttc`), 0 otherwise
+    pub index: u32,
+    pub family: String,
+    /// 100 (thin) to 900 (black), 400 is regular
+    pub weight: u16,
+    pub italic: bool,
+}
+
+fn home() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".into()))
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> PathBuf {
+    std::env::var(var).map_or_else(|_| home().join(fallback), PathBuf::from)
+}
+
+/// Path of a `<dir>` or `<include>` value, as fontconfig resolves it
+///
+/// `prefix="xdg"` is relative to `xdg_base`, `~/` to the home directory, and other
+/// relative paths to the directory of the file naming them.
+fn resolve(value: &str, prefix: Option<&str>, xdg_base: &Path, base: &Path) -> PathBuf {
+    if prefix == Some("xdg") {
+        return xdg_base.join(value);
+    }
+    if let Some(rest) = value.strip_prefix("~/") {
+        return home().join(rest);
+    }
+    let path = PathBuf::from(value);
+    if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    }
+}
+
+/// Value of `name="..."` in the attributes of a tag
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = attributes[start..].find('"')?;
+    Some(&attributes[start..start + len])
+}
+
+/// Attributes and contents of each `<tag ...>contents</tag>` in `xml`
+fn elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        // `<dirname>` is a different tag than `<dir>`
+        if !rest.starts_with(['>', ' ', '\t', '\n', '\r']) {
+            continue;
+        }
+        let Some(end) = rest.find('>') else { break };
+        let attributes = &rest[..end];
+        rest = &rest[end + 1..];
+        let Some(len) = rest.find(&close) else { break };
+        found.push((attributes, rest[..len].trim()));
+        rest = &rest[len + close.len()..];
+    }
+    found
+}
+
+/// Remove `<!-- ... -->` comments
+fn strip_comments(xml: &str) -> String {
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("-->")
+            .map_or("", |end| &rest[start + end + 3..]);
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Font directories and included files named by a fontconfig file in `base`
+pub fn parse_config(contents: &str, base: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let xml = strip_comments(contents);
+    let data_home = xdg_dir("XDG_DATA_HOME", ".local/share");
+    let config_home = xdg_dir("XDG_CONFIG_HOME", ".config");
+    let dirs = elements(&xml, "dir")
+        .into_iter()
+        .map(|(attrs, value)| resolve(value, attribute(attrs, "prefix"), &data_home, base))
+        .collect();
+    let includes = elements(&xml, "include")
+        .into_iter()
+        .map(|(attrs, value)| resolve(value, attribute(attrs, "prefix"), &config_home, base))
+        .collect();
+    (dirs, includes)
+}
+
+fn read_config(path: &Path, depth: usize, visited: &mut Vec<PathBuf>, dirs: &mut Vec<PathBuf>) {
+    if depth > MAX_INCLUDE_DEPTH || visited.iter().any(|p| p == path) {
+        return;
+    }
+    visited.push(path.to_path_buf());
+    if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "conf"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+        for file in files {
+            read_config(&file, depth + 1, visited, dirs);
+        }
+        return;
+    }
+    let Ok(contents) = fs::read_to_string(path) else {
+        return;
+    };
+    let base = path.parent().unwrap_or_else(|| Path::new("/"));
+    let (found, includes) = parse_config(&contents, base);
+    dirs.extend(found);
+    for include in includes {
+        read_config(&include, depth + 1, visited, dirs);
+    }
+}
+
+/// Font directories of fontconfig (`$FONTCONFIG_FILE` or `/etc/fonts/fonts.conf` and what it
+/// includes), or the usual ones without a config
+pub fn font_dirs() -> Vec<PathBuf> {
+    let config = std::env::var("FONTCONFIG_FILE")
+        .map_or_else(|_| PathBuf::from("/etc/fonts/fonts.conf"), PathBuf::from);
+    let mut dirs = Vec::new();
+    read_config(&config, 0, &mut Vec::new(), &mut dirs);
+    if dirs.is_empty() {
+        dirs = vec![
+            PathBuf::from("/usr/share/fonts"),
+            PathBuf::from("/usr/local/share/fonts"),
+            xdg_dir("XDG_DATA_HOME", ".local/share").join("fonts"),
+            home().join(".fonts"),
+        ];
+    }
+    let mut unique = Vec::new();
+    for dir in dirs {
+        if !unique.contains(&dir) {
+            unique.push(dir);
+        }
+    }
+    unique
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_at(file: &mut File, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0; len];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Family name, preferring the typographic family in US English
+fn family_name(table: &name::Table<'_>) -> Option<String> {
+    [name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY]
+        .into_iter()
+        .find_map(|id| {
+            let names = table.names.into_iter().filter(|n| n.name_id == id);
+            let english = names
+                .clone()
+                .filter(|n| n.language() == Language::English_UnitedStates)
+                .find_map(|n| n.to_string());
+            english.or_else(|| names.into_iter().find_map(|n| n.to_string()))
+        })
+        .filter(|family| !family.is_empty())
+}
+
+/// Read the face at `offset`, loading only its `name` and `OS/2` tables
+fn read_face(file: &mut File, path: &Path, offset: u32, index: u32) -> Option<FontFace> {
+    let header = read_at(file, u64::from(offset), 12).ok()?;
+    let count = usize::from(read_u16(&header, 4)?);
+    let records = read_at(file, u64::from(offset) + 12, count * 16).ok()?;
+    let mut table = |tag: &[u8; 4]| {
+        let record = records
+            .as_chunks::<16>()
+            .0
+            .iter()
+            .find(|r| &r[..4] == tag)?;
+        let len = usize::try_from(read_u32(record, 12)?).ok()?;
+        read_at(file, u64::from(read_u32(record, 8)?), len).ok()
+    };
+    let names = table(b"name")?;
+    let os2 = table(b"OS/2");
+    let os2 = os2.as_deref().and_then(os2::Table::parse);
+    Some(FontFace {
+        path: path.to_path_buf(),
+        index,
+        family: family_name(&name::Table::parse(&names)?)?,
+        weight: os2.map_or(400, |t| t.weight().to_number()),
+        italic: os2.is_some_and(|t| t.style() != ttf_parser::Style::Normal),
+    })
+}
+
+/// Faces in a font file; a collection has several
+pub fn read_faces(path: &Path) -> Vec<FontFace> {
+    let Ok(mut file) = File::open(path) else {
+        return Vec::new();
+    };
+    let Ok(header) = read_at(&mut file, 0, 12) else {
+        return Vec::new();
+    };
+    if &header[..4] != b"ttcf" {
+        return read_face(&mut file, path, 0, 0).into_iter().collect();
+    }
+    let count = read_u32(&header, 8).unwrap_or(0);
+    let Ok(offsets) = read_at(&mut file, 12, count as usize * 4) else {
+        return Vec::new();
+    };
+    (0..count)
+        .filter_map(|index| {
+            let offset = read_u32(&offsets, index as usize * 4)?;
+            read_face(&mut file, path, offset, index)
+        })
+        .collect()
+}
+
+fn scan_dir(dir: &Path, depth: usize, faces: &mut Vec<FontFace>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path.is_dir() {
+            if depth < MAX_DIR_DEPTH {
+                scan_dir(&path, depth + 1, faces);
+            }
+        } else if path.extension().is_some_and(|ext| {
+            FONT_EXTENSIONS.contains(&ext.to_string_lossy().to_ascii_lowercase().as_str())
+        }) {
+            faces.extend(read_faces(&path));
+        }
+    }
+}
+
+/// Faces of the font files in `dirs` and their subdirectories, by family
+pub fn scan_faces(dirs: &[PathBuf]) -> Vec<FontFace> {
+    let mut faces = Vec::new();
+    for dir in dirs {
+        scan_dir(dir, 0, &mut faces);
+    }
+    faces.sort_by(|a, b| {
+        (&a.family, a.weight, a.italic, &a.path).cmp(&(&b.family, b.weight, b.italic, &b.path))
+    });
+    faces
+}
+
+/// Faces installed on the system; scanned once, the first time they are needed
+pub fn system_faces() -> &'static [FontFace] {
+    static FACES: OnceLock<Vec<FontFace>> = OnceLock::new();
+    FACES.get_or_init(|| scan_faces(&font_dirs()))
+}
+
+/// Family names of `faces`, sorted, without duplicates
+pub fn families(faces: &[FontFace]) -> Vec<&str> {
+    let mut families: Vec<&str> = faces.iter().map(|f| f.family.as_str()).collect();
+    families.dedup();
+    families
+}
+
+/// Upright face of `family` (any case) closest to `weight`
+pub fn find_face<'a>(faces: &'a [FontFace], family: &str, weight: u16) -> Option<&'a FontFace> {
+    faces
+        .iter()
+        .filter(|f| f.family.eq_ignore_ascii_case(family))
+        .min_by_key(|f| (f.italic, f.weight.abs_diff(weight)))
+}
@@ -1,26 +1,104 @@
+use super::fontconfig::{FontFace, find_face, system_faces};
 use eframe::egui;
-use egui::epaint::text::{FontInsert, InsertFontFamily};
-
-/// Setup custom fonts including Nerd Font and Japanese font
-pub fn setup_custom_fonts(ctx: &egui::Context) {
-    // Load Firple fonts (Nerd Font & Japanese font)
-    let font_data: egui::FontData =
-        egui::FontData::from_static(include_bytes!("../../../assets/Firple-Bold.ttf"));
-
-    ctx.add_font(FontInsert::new(
-        "Firple Bold",
-        font_data,
-        vec![
-            InsertFontFamily {
-                family: egui::FontFamily::Proportional,
-                priority: egui::epaint::text::FontPriority::Highest,
-            },
-            InsertFontFamily {
-                family: egui::FontFamily::Monospace,
-                priority: egui::epaint::text::FontPriority::Highest,
-            },
-        ],
-    ));
-
-    eprintln!("✓ Loaded Firple Bold");
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Name of the bundled font in the font definitions
+const BUNDLED_FONT: &str = "Firple Bold";
+
+/// Smallest and largest UI scale
+pub const SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+/// Fonts and UI scale from `config.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontSettings {
+    /// System font family for the interface; `None` uses the bundled Firple Bold
+    pub family: Option<String>,
+    /// Weight looked for in `family`, 100 (thin) to 900 (black)
+    pub weight: u16,
+    /// System font family for commands and other monospace text; `None` uses the UI font
+    pub monospace: Option<String>,
+    /// UI scale, also changed with `Ctrl+=` and `Ctrl+-`
+    pub scale: f32,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        Self {
+            family: None,
+            weight: 400,
+            monospace: None,
+            scale: 1.0,
+        }
+    }
+}
+
+/// Read a face's file for the font definitions
+fn load_face(face: &FontFace) -> Option<egui::FontData> {
+    let bytes = std::fs::read(&face.path)
+        .map_err(|e| eprintln!("Failed to read {}: {e}", face.path.display()))
+        .ok()?;
+    let mut data = egui::FontData::from_owned(bytes);
+    data.index = face.index;
+    Some(data)
+}
+
+/// Load the system font `family` closest to `weight` into `defs`, returning its name there
+fn insert_system_font(
+    defs: &mut egui::FontDefinitions,
+    family: &str,
+    weight: u16,
+) -> Option<String> {
+    let Some(face) = find_face(system_faces(), family, weight) else {
+        eprintln!("Font family '{family}' is not installed, using {BUNDLED_FONT}");
+        return None;
+    };
+    let name = format!("{} {}", face.family, face.weight);
+    if !defs.font_data.contains_key(&name) {
+        defs.font_data
+            .insert(name.clone(), Arc::new(load_face(face)?));
+        eprintln!("✓ Loaded {} ({})", name, face.path.display());
+    }
+    Some(name)
+}
+
+/// Setup fonts: the configured system fonts first, then Firple Bold (Nerd Font & Japanese
+/// font) for the glyphs they lack
+pub fn setup_custom_fonts(ctx: &egui::Context, settings: &FontSettings) {
+    let mut defs = egui::FontDefinitions::default();
+    defs.font_data.insert(
+        BUNDLED_FONT.to_string(),
+        Arc::new(egui::FontData::from_static(include_bytes!(
+            "../../../assets/Firple-Bold.ttf"
+        ))),
+    );
+    let ui_font = settings
+        .family
+        .as_deref()
+        .and_then(|family| insert_system_font(&mut defs, family, settings.weight));
+    let mono_font = settings
+        .monospace
+        .as_deref()
+        .and_then(|family| insert_system_font(&mut defs, family, 400));
+
+    let proportional = [ui_font.as_deref(), Some(BUNDLED_FONT)];
+    let monospace = [
+        mono_font.as_deref().or(ui_font.as_deref()),
+        Some(BUNDLED_FONT),
+    ];
+    for (family, fonts) in [
+        (egui::FontFamily::Proportional, proportional),
+        (egui::FontFamily::Monospace, monospace),
+    ] {
+        let list = defs.families.entry(family).or_default();
+        for (i, font) in fonts.into_iter().flatten().enumerate() {
+            list.insert(i, font.to_string());
+        }
+    }
+    ctx.set_fonts(defs);
+
+    if ui_font.is_none() {
+        eprintln!("✓ Loaded {BUNDLED_FONT}");
+    }
 }
@@ -1,50 +1,250 @@
+use crate::hyprland::Key;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::path::Path;
+use std::sync::{OnceLock, PoisonError, RwLock};
 
-/// Get icon for common keys and modifiers using Nerd Font
+/// How keys and modifiers are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// Nerd Font glyphs, from the bundled Firple font
+    #[default]
+    Nerd,
+    /// Unicode symbols such as ⌘ ⇧ ⏎
+    Unicode,
+    /// Plain ASCII names
+    Ascii,
+}
+
+impl IconSet {
+    pub const ALL: [Self; 3] = [Self::Nerd, Self::Unicode, Self::Ascii];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Nerd => "Nerd Font",
+            Self::Unicode => "Unicode",
+            Self::Ascii => "ASCII",
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::Nerd => 0,
+            Self::Unicode => 1,
+            Self::Ascii => 2,
+        }
+    }
+}
+
+/// Lowercase key names with their Nerd Font, Unicode and ASCII labels; an empty label
+/// falls back to the key name
+const ICONS: &[(&str, [&str; 3])] = &[
+    // Modifiers
+    ("super", ["\u{f17a}", "⌘", "Super"]),
+    ("shift", [" \u{f0636} ", "⇧", "Shift"]),
+    ("ctrl", ["", "⌃", "Ctrl"]),
+    ("control", ["", "⌃", "Ctrl"]),
+    ("alt", ["", "⌥", "Alt"]),
+    ("caps", ["\u{f0632}", "⇪", "Caps"]),
+    // Editing and navigation
+    ("return", ["\u{f0311}", "⏎", "Enter"]),
+    ("enter", ["\u{f0311}", "⏎", "Enter"]),
+    ("tab", ["TAB", "⇥", "Tab"]),
+    ("iso_left_tab", ["TAB", "⇤", "Tab"]),
+    ("backspace", ["\u{f030d}", "⌫", "Backspace"]),
+    ("delete", ["DEL", "⌦", "Del"]),
+    ("insert", ["INS", "⎀", "Ins"]),
+    ("escape", ["ESC", "⎋", "Esc"]),
+    ("space", ["\u{f1050}", "␣", "Space"]),
+    ("caps_lock", ["\u{f0632}", "⇪", "CapsLock"]),
+    ("num_lock", ["NUM", "⇭", "NumLock"]),
+    ("scroll_lock", ["SCRL", "⤓", "ScrollLock"]),
+    ("print", ["PRT", "⎙", "Print"]),
+    ("pause", ["PAUSE", "⎉", "Pause"]),
+    ("menu", ["\u{f035c}", "☰", "Menu"]),
+    ("left", ["\u{f0731}", "←", "Left"]),
+    ("right", ["\u{f0734}", "→", "Right"]),
+    ("up", ["\u{f0737}", "↑", "Up"]),
+    ("down", ["\u{f072e}", "↓", "Down"]),
+    ("home", ["HOME", "⇱", "Home"]),
+    ("end", ["END", "⇲", "End"]),
+    ("prior", ["PGUP", "⇞", "PgUp"]),
+    ("page_up", ["PGUP", "⇞", "PgUp"]),
+    ("next", ["PGDN", "⇟", "PgDn"]),
+    ("page_down", ["PGDN", "⇟", "PgDn"]),
+    // Mouse buttons and wheel
+    ("mouse:272", ["\u{eb6f}\u{f037d}", "◧", "LMB"]),
+    ("mouse:273", ["\u{f037d}\u{eb70}", "◨", "RMB"]),
+    ("mouse:274", ["\u{f037d}M", "◫", "MMB"]),
+    ("mouse:275", ["\u{f037d}\u{f004d}", "⇦", "Mouse4"]),
+    ("mouse:276", ["\u{f037d}\u{f0054}", "⇨", "Mouse5"]),
+    ("mouse_down", ["\u{f1550}", "⇣", "WheelDown"]),
+    ("mouse_up", ["\u{f1551}", "⇡", "WheelUp"]),
+    ("mouse_left", ["", "⇠", "WheelLeft"]),
+    ("mouse_right", ["", "⇢", "WheelRight"]),
+    // XF86 keys
+    ("xf86audioraisevolume", ["\u{f028}", "♪+", "Vol+"]),
+    ("xf86audiolowervolume", ["\u{f027}", "♪-", "Vol-"]),
+    ("xf86audiomute", ["\u{eee8}", "♪×", "Mute"]),
+    ("xf86audiomicmute", ["\u{f036d}", "Mic×", "MicMute"]),
+    ("xf86audioplay", ["\u{f04b}", "▶", "Play"]),
+    ("xf86audiopause", ["\u{f04c}", "⏸", "Pause"]),
+    ("xf86audiostop", ["\u{f04db}", "⏹", "Stop"]),
+    ("xf86audionext", ["\u{f0661}", "⏭", "Next"]),
+    ("xf86audioprev", ["\u{f0663}", "⏮", "Prev"]),
+    ("xf86audioforward", ["\u{f0211}", "⏩", "Forward"]),
+    ("xf86audiorewind", ["\u{f045f}", "⏪", "Rewind"]),
+    ("xf86audiorecord", ["\u{f044a}", "⏺", "Record"]),
+    ("xf86audiomedia", ["", "♫", "Media"]),
+    ("xf86monbrightnessup", ["\u{f00e0}", "☀+", "Bright+"]),
+    ("xf86monbrightnessdown", ["\u{f00de}", "☀-", "Bright-"]),
+    ("xf86kbdbrightnessup", ["\u{f030c}+", "⌨+", "KbdBright+"]),
+    ("xf86kbdbrightnessdown", ["\u{f030c}-", "⌨-", "KbdBright-"]),
+    ("xf86kbdlightonoff", ["\u{f030c}", "⌨", "KbdLight"]),
+    ("xf86poweroff", ["\u{f0425}", "⏻", "Power"]),
+    ("xf86powerdown", ["\u{f0425}", "⏻", "Power"]),
+    ("xf86sleep", ["\u{f04b2}", "⏾", "Sleep"]),
+    ("xf86suspend", ["\u{f04b2}", "⏾", "Suspend"]),
+    ("xf86screensaver", ["\u{f033e}", "", "Lock"]),
+    ("xf86eject", ["\u{f01ea}", "⏏", "Eject"]),
+    ("xf86calculator", ["\u{f00ec}", "", "Calc"]),
+    ("xf86mail", ["\u{f01ee}", "✉", "Mail"]),
+    ("xf86www", ["\u{f059f}", "", "WWW"]),
+    ("xf86search", ["\u{f0349}", "", "Search"]),
+    ("xf86homepage", ["\u{f02dc}", "⌂", "HomePage"]),
+    ("xf86favorites", ["\u{f04ce}", "★", "Favorites"]),
+    ("xf86reload", ["\u{f0450}", "⟳", "Reload"]),
+    ("xf86refresh", ["\u{f0450}", "⟳", "Refresh"]),
+    ("xf86back", ["\u{f004d}", "⇦", "Back"]),
+    ("xf86forward", ["\u{f0054}", "⇨", "Forward"]),
+    ("xf86copy", ["\u{f018f}", "", "Copy"]),
+    ("xf86cut", ["\u{f0190}", "", "Cut"]),
+    ("xf86paste", ["\u{f0192}", "", "Paste"]),
+    ("xf86explorer", ["\u{f024b}", "", "Files"]),
+    ("xf86mycomputer", ["\u{f024b}", "", "Files"]),
+    ("xf86display", ["\u{f0379}", "", "Display"]),
+    ("xf86wlan", ["\u{f05a9}", "", "WLAN"]),
+    ("xf86bluetooth", ["\u{f00af}", "", "Bluetooth"]),
+    ("xf86webcam", ["\u{f0100}", "", "Webcam"]),
+];
+
+fn builtin_table() -> &'static HashMap<&'static str, [&'static str; 3]> {
+    static TABLE: OnceLock<HashMap<&'static str, [&'static str; 3]>> = OnceLock::new();
+    TABLE.get_or_init(|| ICONS.iter().copied().collect())
+}
+
+/// Words of an XF86 keysym without a built-in label, e.g. `Launch 1` for `XF86Launch1`
+fn xf86_label(name: &str) -> String {
+    let mut label = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        let boundary = previous.is_some_and(|p| {
+            let word = p.is_ascii_lowercase() && c.is_ascii_uppercase();
+            let number = !p.is_ascii_digit() && c.is_ascii_digit();
+            word || number
+        });
+        if boundary {
+            label.push(' ');
+        }
+        label.push(c);
+        previous = Some(c);
+    }
+    label
+}
+
+/// Built-in icon of `key` in `set`
+fn builtin_icon(set: IconSet, key: &Key) -> Option<String> {
+    let name = key.name();
+    if let Some(icons) = builtin_table().get(name.to_ascii_lowercase().as_str()) {
+        let icon = icons[set.index()];
+        return (!icon.is_empty()).then(|| icon.to_string());
+    }
+    name.strip_prefix("XF86")
+        .filter(|rest| !rest.is_empty())
+        .map(xf86_label)
+}
+
+/// Icon set with the labels of `icons.json` on top
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IconMap {
+    pub set: IconSet,
+    /// Lowercase key names and their labels
+    pub overrides: HashMap<String, String>,
+}
+
+impl IconMap {
+    pub fn new(set: IconSet, overrides: HashMap<String, String>) -> Self {
+        let overrides = overrides
+            .into_iter()
+            .map(|(key, label)| (key.to_ascii_lowercase(), label))
+            .collect();
+        Self { set, overrides }
+    }
+
+    /// `set` with the overrides of the `icons.json` in the config directory, if there is one
+    pub fn load(set: IconSet) -> Self {
+        let path = crate::config::icons_path();
+        match read_overrides(&path) {
+            Ok(overrides) => Self::new(set, overrides),
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                Self::new(set, HashMap::new())
+            }
+        }
+    }
+
+    /// Label of a key or modifier name; see `key_icon`
+    pub fn icon(&self, key: &str) -> String {
+        self.key_icon(&Key::parse(key))
+    }
+
+    /// Label of `key`: its override, its icon in the set, or its label
+    pub fn key_icon(&self, key: &Key) -> String {
+        self.overrides
+            .get(&key.name().to_ascii_lowercase())
+            .cloned()
+            .or_else(|| builtin_icon(self.set, key))
+            .unwrap_or_else(|| key.label())
+    }
+}
+
+/// Key names and labels of an overrides file; a missing file has none
+pub fn read_overrides(path: &Path) -> Result<HashMap<String, String>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Map used by `get_icon`; `None` is the Nerd Font set without overrides
+static CURRENT: RwLock<Option<IconMap>> = RwLock::new(None);
+
+/// Use `map` for the icons drawn from now on
+pub fn set_icon_map(map: IconMap) {
+    *CURRENT.write().unwrap_or_else(PoisonError::into_inner) = Some(map);
+}
+
+/// Load `set` and `icons.json` and use them from now on
+pub fn load_icon_map(set: IconSet) {
+    set_icon_map(IconMap::load(set));
+}
+
+fn with_current<T>(f: impl FnOnce(&IconMap) -> T) -> T {
+    let current = CURRENT.read().unwrap_or_else(PoisonError::into_inner);
+    match current.as_ref() {
+        Some(map) => f(map),
+        None => f(&IconMap::default()),
+    }
+}
+
+/// Get the icon or label of a key or modifier in the current icon set
 pub fn get_icon(key: &str) -> String {
-    // Static table for direct key-icon mapping (case-insensitive)
-    fn icon_table() -> &'static HashMap<&'static str, &'static str> {
-        static TABLE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
-
-        TABLE.get_or_init(|| {
-            let mut m: HashMap<&str, &str> = HashMap::new();
-            m.insert("super", "");
-            m.insert("shift", " 󰘶 ");
-            m.insert("return", "󰌑");
-            m.insert("enter", "󰌑");
-            m.insert("semicolon", ";");
-            m.insert("delete", "DEL");
-            m.insert("tab", "TAB");
-            m.insert("left", "󰜱");
-            m.insert("right", "󰜴");
-            m.insert("up", "󰜷");
-            m.insert("down", "󰜮");
-            m.insert("mouse_down", "󱕐");
-            m.insert("mouse_up", "󱕑");
-            m.insert("mouse:272", "󰍽");
-            m.insert("mouse:273", "󰍽");
-            m.insert("xf86audioraisevolume", "");
-            m.insert("xf86audiolowervolume", "");
-            m.insert("xf86audiomute", "");
-            m.insert("xf86audiomicmute", "󰍭");
-            m.insert("xf86monbrightnessup", "󰃠");
-            m.insert("xf86monbrightnessdown", "󰃞");
-            m.insert("xf86audionext", "󰙡");
-            m.insert("xf86audiopause", "");
-            m.insert("xf86audioplay", "");
-            m.insert("xf86audioprev", "󰙣");
-            m
-        })
-    }
-
-    let key_lower: String = key.to_ascii_lowercase();
-
-    // Table lookup (case-insensitive)
-    if let Some(&icon) = icon_table().get(key_lower.as_str()) {
-        return icon.to_string();
-    }
-
-    // Fallback: return the key itself
-    key.to_string()
+    with_current(|map| map.icon(key))
+}
+
+/// Get the icon or label of a normalised key in the current icon set
+pub fn get_key_icon(key: &Key) -> String {
+    with_current(|map| map.key_icon(key))
 }
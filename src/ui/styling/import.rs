@@ -0,0 +1,298 @@
+use super::color::{parse_color, split_top_level};
+use eframe::egui::Color32;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Hyprland options the imported colours come from
+const ACTIVE_BORDER: &str = "general:col.active_border";
+const INACTIVE_BORDER: &str = "general:col.inactive_border";
+const SHADOW: &str = "decoration:shadow:color";
+
+/// The `:root` colours of a theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub bg: Color32,
+    pub fg: Color32,
+    pub panel: Color32,
+    pub accent: Color32,
+    pub stroke: Color32,
+    pub selection: Color32,
+}
+
+impl Palette {
+    /// A theme file setting the variables, with element rules built on them
+    pub fn to_css(self, title: &str) -> String {
+        let hex = |c: Color32| format!("#{:02x}{:02x}{:02x}", c.r(), c.g(), c.b());
+        format!(
+            "/* {title}, written by `hyprbind theme import` */\n\
+             :root {{\n  \
+               --bg: {};\n  \
+               --fg: {};\n  \
+               --panel: {};\n  \
+               --accent: {};\n  \
+               --stroke: {};\n  \
+               --selection: {};\n\
+             }}\n\
+             .header {{ color: var(--accent); }}\n\
+             .keycap {{ background: var(--selection); color: var(--accent); border-color: var(--stroke); }}\n\
+             .stats {{ color: var(--stroke); }}\n",
+            hex(self.bg),
+            hex(self.fg),
+            hex(self.panel),
+            hex(self.accent),
+            hex(self.stroke),
+            hex(self.selection),
+        )
+    }
+}
+
+/// `a` moved towards `b` by `t` (0 is `a`, 1 is `b`), opaque
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::suboptimal_flops
+)]
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let channel = |x: u8, y: u8| (f32::from(x) + (f32::from(y) - f32::from(x)) * t).round() as u8;
+    Color32::from_rgb(
+        channel(a.r(), b.r()),
+        channel(a.g(), b.g()),
+        channel(a.b(), b.b()),
+    )
+}
+
+fn opaque(color: Color32) -> Color32 {
+    let [r, g, b, _] = color.to_srgba_unmultiplied();
+    Color32::from_rgb(r, g, b)
+}
+
+/// Where imported colours come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportSource {
+    /// `colors.json` written by pywal or wallust
+    Pywal(PathBuf),
+    /// A base16 scheme in YAML
+    Base16(PathBuf),
+    /// Border and shadow colours of the running Hyprland, or of `hyprland.conf`
+    Hyprland,
+}
+
+impl ImportSource {
+    /// `pywal` (or `wal`, `wallust`), `hyprland`, or a `.json` / `.yaml` file (`~/` expanded)
+    pub fn parse(arg: &str) -> Result<Self, String> {
+        match arg {
+            "pywal" | "wal" | "wallust" => return Ok(Self::Pywal(pywal_colors_path())),
+            "hyprland" => return Ok(Self::Hyprland),
+            _ => {}
+        }
+        let path = match (arg.strip_prefix("~/"), std::env::var("HOME")) {
+            (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+            _ => PathBuf::from(arg),
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Self::Pywal(path)),
+            Some("yaml" | "yml") => Ok(Self::Base16(path)),
+            _ => Err(format!(
+                "unknown source '{arg}': use pywal, hyprland, a colors.json or a base16 .yaml file"
+            )),
+        }
+    }
+
+    /// Theme name the import is saved under
+    pub fn default_name(&self) -> String {
+        match self {
+            Self::Pywal(_) => "pywal".to_string(),
+            Self::Hyprland => "hyprland".to_string(),
+            Self::Base16(path) => format!(
+                "base16-{}",
+                path.file_stem().unwrap_or_default().to_string_lossy()
+            ),
+        }
+    }
+
+    pub fn palette(&self) -> Result<Palette, String> {
+        match self {
+            Self::Pywal(path) => palette_from_pywal(&read(path)?),
+            Self::Base16(path) => palette_from_base16(&read(path)?),
+            Self::Hyprland => {
+                let active = crate::hyprland::hyprland_option(ACTIVE_BORDER)
+                    .ok_or_else(|| format!("{ACTIVE_BORDER} is not set"))?;
+                palette_from_hyprland(
+                    &active,
+                    crate::hyprland::hyprland_option(INACTIVE_BORDER).as_deref(),
+                    crate::hyprland::hyprland_option(SHADOW).as_deref(),
+                )
+            }
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
+
+/// `colors.json` of pywal in the cache directory; wallust writes the same file
+pub fn pywal_colors_path() -> PathBuf {
+    let cache = std::env::var("XDG_CACHE_HOME").map_or_else(
+        |_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+            PathBuf::from(home).join(".cache")
+        },
+        PathBuf::from,
+    );
+    cache.join("wal").join("colors.json")
+}
+
+/// Palette of a pywal `colors.json`: its background and foreground, `color4` as the accent
+/// and `color8` for borders
+pub fn palette_from_pywal(json: &str) -> Result<Palette, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("invalid colors.json: {e}"))?;
+    let color = |group: &str, key: &str| {
+        let text = value[group][key]
+            .as_str()
+            .ok_or_else(|| format!("colors.json has no {group}.{key}"))?;
+        parse_color(text).ok_or_else(|| format!("invalid colour '{text}' for {group}.{key}"))
+    };
+    let background = color("special", "background")?;
+    let foreground = color("special", "foreground")?;
+    let accent = color("colors", "color4")?;
+    let bg = mix(background, foreground, 0.06);
+    Ok(Palette {
+        bg,
+        fg: foreground,
+        panel: background,
+        accent,
+        stroke: color("colors", "color8")?,
+        selection: mix(bg, accent, 0.25),
+    })
+}
+
+/// Palette of a base16 scheme, in the classic (`base00: "1d1f21"`) or the tinted-theming
+/// layout (`palette:` with `base00: "#1d1f21"`)
+pub fn palette_from_base16(yaml: &str) -> Result<Palette, String> {
+    let mut bases: [Option<Color32>; 16] = [None; 16];
+    for line in yaml.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(index) = key
+            .trim()
+            .strip_prefix("base0")
+            .and_then(|digit| usize::from_str_radix(digit, 16).ok())
+        else {
+            continue;
+        };
+        let value = yaml_scalar(value);
+        let hex = value.strip_prefix('#').unwrap_or(value);
+        let color = (hex.len() == 6)
+            .then(|| parse_color(&format!("#{hex}")))
+            .flatten()
+            .ok_or_else(|| format!("invalid colour '{value}' for {}", key.trim()))?;
+        bases[index] = Some(color);
+    }
+    let base = |index: usize| bases[index].ok_or_else(|| format!("scheme has no base0{index:X}"));
+    Ok(Palette {
+        bg: base(0x0)?,
+        fg: base(0x5)?,
+        panel: base(0x1)?,
+        accent: base(0xD)?,
+        stroke: base(0x3)?,
+        selection: base(0x2)?,
+    })
+}
+
+/// A YAML value without quotes or a trailing comment
+fn yaml_scalar(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            return rest.split(quote).next().unwrap_or_default();
+        }
+    }
+    value.split(" #").next().unwrap_or_default().trim()
+}
+
+/// First colour of a Hyprland colour or gradient value
+///
+/// Accepts `rgba(33ccffee)`, `rgb(33ccff)`, `rgba(51, 204, 255, 0.9)`, `0xee33ccff` and the
+/// bare `ee33ccff` printed by `hyprctl getoption`.
+pub fn parse_hyprland_color(value: &str) -> Option<Color32> {
+    let first = split_top_level(value, char::is_whitespace)
+        .into_iter()
+        .next()?;
+    let lower = first.to_ascii_lowercase();
+    if let Some(hex) = lower
+        .strip_prefix("rgba(")
+        .or_else(|| lower.strip_prefix("rgb("))
+        .and_then(|rest| rest.strip_suffix(')'))
+        .filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return parse_color(&format!("#{hex}"));
+    }
+    let argb = lower.strip_prefix("0x").unwrap_or(&lower);
+    if argb.len() == 8 && argb.chars().all(|c| c.is_ascii_hexdigit()) {
+        return parse_color(&format!("#{}{}", &argb[2..], &argb[..2]));
+    }
+    parse_color(&lower)
+}
+
+/// Palette around Hyprland's window borders: the active border is the accent, the inactive
+/// one the stroke, and the shadow colour the panel
+pub fn palette_from_hyprland(
+    active_border: &str,
+    inactive_border: Option<&str>,
+    shadow: Option<&str>,
+) -> Result<Palette, String> {
+    let accent = parse_hyprland_color(active_border)
+        .map(opaque)
+        .ok_or_else(|| format!("invalid colour '{active_border}' for {ACTIVE_BORDER}"))?;
+    let stroke = inactive_border
+        .and_then(parse_hyprland_color)
+        .map_or_else(|| mix(accent, Color32::BLACK, 0.5), opaque);
+    let panel = shadow
+        .and_then(parse_hyprland_color)
+        .map_or_else(|| Color32::from_gray(24), opaque);
+    let bg = mix(mix(panel, Color32::WHITE, 0.04), accent, 0.05);
+    Ok(Palette {
+        bg,
+        fg: mix(Color32::from_gray(230), accent, 0.1),
+        panel,
+        accent,
+        stroke,
+        selection: mix(bg, accent, 0.3),
+    })
+}
+
+/// File name of a theme saved as `name`
+fn theme_file_name(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(format!("invalid theme name '{name}'"));
+    }
+    Ok(format!("{name}.css"))
+}
+
+/// Import `source` into the themes directory as `name` (the source's default without one)
+///
+/// Returns the theme name and the written file.
+pub fn import_theme(
+    source: &ImportSource,
+    name: Option<&str>,
+) -> Result<(String, PathBuf), String> {
+    import_theme_into(&crate::config::themes_dir(), source, name)
+}
+
+/// Import `source` into `dir`, see `import_theme`
+pub fn import_theme_into(
+    dir: &Path,
+    source: &ImportSource,
+    name: Option<&str>,
+) -> Result<(String, PathBuf), String> {
+    let name = name.map_or_else(|| source.default_name(), str::to_string);
+    let path = dir.join(theme_file_name(&name)?);
+    let css = source.palette()?.to_css(&name);
+    fs::create_dir_all(dir)
+        .and_then(|()| fs::write(&path, css))
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok((name, path))
+}
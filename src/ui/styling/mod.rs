@@ -1,3 +1,8 @@
+pub mod color;
 pub mod css;
+pub mod fontconfig;
 pub mod fonts;
 pub mod icons;
+pub mod import;
+pub mod stylesheet;
+pub mod themes;
@@ -0,0 +1,302 @@
+use std::fmt;
+
+/// How bad a `Diagnostic` is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The declaration or rule is ignored
+    Error,
+    /// Valid CSS that `HyprBind` has no use for
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// A problem in a style sheet, at a 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(message, line, column)
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, self.severity, self.message
+        )
+    }
+}
+
+/// A `name: value` declaration, with the position of its name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    pub name: String,
+    pub value: String,
+    /// 1-based line and column
+    pub line: usize,
+    pub column: usize,
+}
+
+/// `selector, selector { declarations }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// Selectors with whitespace collapsed, e.g. `.table row:nth-child(even)`
+    pub selectors: Vec<String>,
+    pub declarations: Vec<Declaration>,
+    /// 1-based position of the first selector
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Rules of a style sheet, in source order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stylesheet {
+    pub rules: Vec<Rule>,
+    /// Syntax problems; the parts they cover are skipped
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Characters of the source with comments blanked out, and their positions
+struct Source {
+    chars: Vec<(char, usize, usize)>,
+    /// Start of a comment that never ends
+    unclosed_comment: Option<(usize, usize)>,
+}
+
+impl Source {
+    /// Drop `/* ... */` comments, which may span lines, keeping positions of the rest
+    fn new(text: &str) -> Self {
+        let mut chars = Vec::new();
+        let (mut line, mut column) = (1, 1);
+        let mut in_comment = None;
+        let mut iter = text.chars().peekable();
+        while let Some(c) = iter.next() {
+            let position = (line, column);
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+            if in_comment.is_some() {
+                if c == '*' && iter.peek() == Some(&'/') {
+                    iter.next();
+                    column += 1;
+                    in_comment = None;
+                }
+                continue;
+            }
+            if c == '/' && iter.peek() == Some(&'*') {
+                iter.next();
+                column += 1;
+                in_comment = Some(position);
+                continue;
+            }
+            chars.push((c, position.0, position.1));
+        }
+        Self {
+            chars,
+            unclosed_comment: in_comment,
+        }
+    }
+}
+
+/// Trimmed text of `chars` and the position of its first non-space character
+fn trimmed(chars: &[(char, usize, usize)]) -> Option<(String, usize, usize)> {
+    let start = chars.iter().position(|(c, _, _)| !c.is_whitespace())?;
+    let text: String = chars[start..].iter().map(|(c, _, _)| c).collect();
+    let (_, line, column) = chars[start];
+    Some((text.trim_end().to_string(), line, column))
+}
+
+fn parse_declarations(
+    chars: &[(char, usize, usize)],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, &(c, _, _)) in chars.iter().enumerate() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                declarations.extend(parse_declaration(&chars[start..i], diagnostics));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.extend(parse_declaration(&chars[start..], diagnostics));
+    declarations
+}
+
+fn parse_declaration(
+    chars: &[(char, usize, usize)],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<Declaration> {
+    let (text, line, column) = trimmed(chars)?;
+    let Some((name, value)) = text.split_once(':') else {
+        diagnostics.push(Diagnostic::error(
+            format!("expected 'name: value', found '{text}'"),
+            line,
+            column,
+        ));
+        return None;
+    };
+    let (name, value) = (name.trim(), value.trim());
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        diagnostics.push(Diagnostic::error(
+            format!("invalid property name '{name}'"),
+            line,
+            column,
+        ));
+        return None;
+    }
+    if value.is_empty() {
+        diagnostics.push(Diagnostic::error(
+            format!("missing value for '{name}'"),
+            line,
+            column,
+        ));
+        return None;
+    }
+    Some(Declaration {
+        name: name.to_string(),
+        value: value.to_string(),
+        line,
+        column,
+    })
+}
+
+/// Warning for an unsupported `@` rule, or an error for other text outside of rules
+fn at_rule_or_stray(text: &str, line: usize, column: usize) -> Diagnostic {
+    if text.starts_with('@') {
+        let name = text.split_whitespace().next().unwrap_or_default();
+        Diagnostic::warning(format!("{name} is not supported"), line, column)
+    } else {
+        Diagnostic::error(format!("expected '{{' after '{text}'"), line, column)
+    }
+}
+
+/// Parse a style sheet
+///
+/// Comments may span lines and a line may hold several declarations.
+/// `@` rules and nested blocks such as `@media` are skipped with a warning, and syntax
+/// errors skip the declaration or rule they are in.
+pub fn parse_stylesheet(text: &str) -> Stylesheet {
+    let source = Source::new(text);
+    let chars = &source.chars;
+    let mut rules = Vec::new();
+    let mut diagnostics = Vec::new();
+    if let Some((line, column)) = source.unclosed_comment {
+        diagnostics.push(Diagnostic::error("unclosed comment", line, column));
+    }
+    let mut i = 0;
+    while i < chars.len() {
+        let Some(open) = chars[i..].iter().position(|(c, _, _)| *c == '{') else {
+            for statement in chars[i..].split(|(c, _, _)| *c == ';') {
+                if let Some((text, line, column)) = trimmed(statement) {
+                    diagnostics.push(at_rule_or_stray(&text, line, column));
+                }
+            }
+            break;
+        };
+        let mut prelude = &chars[i..i + open];
+        // Statements such as `@import url(a.css);` end at `;` and have no block
+        while let Some(semicolon) = prelude.iter().position(|(c, _, _)| *c == ';') {
+            if let Some((text, line, column)) = trimmed(&prelude[..semicolon]) {
+                diagnostics.push(at_rule_or_stray(&text, line, column));
+            }
+            prelude = &prelude[semicolon + 1..];
+        }
+        let body_start = i + open + 1;
+
+        // Find the matching `}`
+        let mut depth = 1;
+        let mut end = body_start;
+        while end < chars.len() {
+            match chars[end].0 {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            end += 1;
+        }
+        let body = &chars[body_start..end.min(chars.len())];
+        i = end + 1;
+
+        let Some((selectors, line, column)) = trimmed(prelude) else {
+            let (_, line, column) = chars[body_start - 1];
+            diagnostics.push(Diagnostic::error("missing selector", line, column));
+            continue;
+        };
+        if let Some(stray) = prelude.iter().find(|(c, _, _)| *c == '}') {
+            diagnostics.push(Diagnostic::error("unexpected '}'", stray.1, stray.2));
+            continue;
+        }
+        if end >= chars.len() {
+            diagnostics.push(Diagnostic::error(
+                format!("'{selectors}' is missing its closing '}}'"),
+                line,
+                column,
+            ));
+        }
+        if selectors.starts_with('@') {
+            diagnostics.push(at_rule_or_stray(&selectors, line, column));
+            continue;
+        }
+        if body.iter().any(|(c, _, _)| *c == '{') {
+            diagnostics.push(Diagnostic::warning(
+                "nested rules are not supported",
+                line,
+                column,
+            ));
+            continue;
+        }
+        rules.push(Rule {
+            selectors: selectors
+                .split(',')
+                .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|s| !s.is_empty())
+                .collect(),
+            declarations: parse_declarations(body, &mut diagnostics),
+            line,
+            column,
+        });
+    }
+    Stylesheet { rules, diagnostics }
+}
@@ -0,0 +1,132 @@
+use super::css::{CssReport, apply_css};
+use eframe::egui;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A CSS theme built into the binary
+pub struct Preset {
+    /// Name saved in the config; a file with this name in the themes directory replaces it
+    pub name: &'static str,
+    pub title: &'static str,
+    pub css: &'static str,
+}
+
+pub const PRESETS: [Preset; 5] = [
+    Preset {
+        name: "catppuccin-mocha",
+        title: "Catppuccin Mocha",
+        css: include_str!("../../../assets/themes/catppuccin-mocha.css"),
+    },
+    Preset {
+        name: "tokyo-night",
+        title: "Tokyo Night",
+        css: include_str!("../../../assets/themes/tokyo-night.css"),
+    },
+    Preset {
+        name: "gruvbox-dark",
+        title: "Gruvbox Dark",
+        css: include_str!("../../../assets/themes/gruvbox-dark.css"),
+    },
+    Preset {
+        name: "nord",
+        title: "Nord",
+        css: include_str!("../../../assets/themes/nord.css"),
+    },
+    Preset {
+        name: "rose-pine",
+        title: "Rosé Pine",
+        css: include_str!("../../../assets/themes/rose-pine.css"),
+    },
+];
+
+/// Where the CSS of a named theme comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeSource {
+    Preset(&'static str),
+    File(PathBuf),
+}
+
+/// A theme that can be picked by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedTheme {
+    pub name: String,
+    pub title: String,
+    pub source: ThemeSource,
+}
+
+impl NamedTheme {
+    fn from_file(path: PathBuf) -> Option<Self> {
+        let name = path.file_stem()?.to_str()?.to_string();
+        Some(Self {
+            title: name.clone(),
+            name,
+            source: ThemeSource::File(path),
+        })
+    }
+
+    pub fn css(&self) -> Result<String, String> {
+        match &self.source {
+            ThemeSource::Preset(css) => Ok((*css).to_string()),
+            ThemeSource::File(path) => fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) -> CssReport {
+        apply_css(ctx, self.css())
+    }
+}
+
+fn preset(preset: &Preset) -> NamedTheme {
+    NamedTheme {
+        name: preset.name.to_string(),
+        title: preset.title.to_string(),
+        source: ThemeSource::Preset(preset.css),
+    }
+}
+
+/// Presets, then the `.css` files of the themes directory by name
+pub fn list_themes() -> Vec<NamedTheme> {
+    list_themes_in(&crate::config::themes_dir())
+}
+
+/// Presets, then the `.css` files of `dir` by name; a file named like a preset takes its place
+pub fn list_themes_in(dir: &Path) -> Vec<NamedTheme> {
+    let mut files: Vec<NamedTheme> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "css"))
+                .filter_map(NamedTheme::from_file)
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut themes: Vec<NamedTheme> = PRESETS
+        .iter()
+        .map(|p| {
+            files
+                .iter()
+                .position(|f| f.name == p.name)
+                .map_or_else(|| preset(p), |i| files.remove(i))
+        })
+        .collect();
+    themes.extend(files);
+    themes
+}
+
+/// The theme saved under `name`: a file in the themes directory, or a preset
+pub fn find_theme(name: &str) -> Option<NamedTheme> {
+    find_theme_in(&crate::config::themes_dir(), name)
+}
+
+/// The theme saved under `name`: a file in `dir`, or a preset
+pub fn find_theme_in(dir: &Path, name: &str) -> Option<NamedTheme> {
+    let path = dir.join(format!("{name}.css"));
+    if path.is_file() {
+        return NamedTheme::from_file(path);
+    }
+    PRESETS.iter().find(|p| p.name == name).map(preset)
+}
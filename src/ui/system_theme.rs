@@ -0,0 +1,162 @@
+use eframe::egui;
+use std::sync::{Arc, Mutex, PoisonError};
+use zbus::zvariant::{OwnedValue, Value};
+
+const APPEARANCE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME: &str = "color-scheme";
+const GNOME_INTERFACE: &str = "org.gnome.desktop.interface";
+const GTK_THEME: &str = "gtk-theme";
+
+/// Light or dark, as the desktop prefers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Dark,
+    Light,
+}
+
+/// A desktop setting value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingValue {
+    U32(u32),
+    Str(String),
+}
+
+/// Reads desktop settings: the settings portal on the session bus, or a mock in tests
+pub trait SettingsSource {
+    fn read(&self, namespace: &str, key: &str) -> Option<SettingValue>;
+}
+
+/// Scheme a GTK theme name suggests, e.g. `Adwaita-dark` or `Adwaita:dark`
+pub fn scheme_from_gtk_theme(name: &str) -> ColorScheme {
+    if name.to_ascii_lowercase().contains("dark") {
+        ColorScheme::Dark
+    } else {
+        ColorScheme::Light
+    }
+}
+
+/// Scheme preferred by the desktop, `None` if nothing says
+///
+/// The portal's `color-scheme` wins (1 is dark, 2 light). Without a preference, the GTK
+/// theme from the portal decides, then the `GTK_THEME` variable.
+pub fn detect(
+    source: Option<&dyn SettingsSource>,
+    gtk_theme_env: Option<&str>,
+) -> Option<ColorScheme> {
+    if let Some(source) = source {
+        match source.read(APPEARANCE, COLOR_SCHEME) {
+            Some(SettingValue::U32(1)) => return Some(ColorScheme::Dark),
+            Some(SettingValue::U32(2)) => return Some(ColorScheme::Light),
+            _ => {}
+        }
+        if let Some(SettingValue::Str(theme)) = source.read(GNOME_INTERFACE, GTK_THEME)
+            && !theme.is_empty()
+        {
+            return Some(scheme_from_gtk_theme(&theme));
+        }
+    }
+    gtk_theme_env
+        .filter(|theme| !theme.is_empty())
+        .map(scheme_from_gtk_theme)
+}
+
+/// Whether a changed setting can change the detected scheme
+pub fn affects_scheme(namespace: &str, key: &str) -> bool {
+    matches!(
+        (namespace, key),
+        (APPEARANCE, COLOR_SCHEME) | (GNOME_INTERFACE, GTK_THEME)
+    )
+}
+
+fn setting_value(value: &Value<'_>) -> Option<SettingValue> {
+    match value {
+        Value::U32(n) => Some(SettingValue::U32(*n)),
+        Value::Str(s) => Some(SettingValue::Str(s.to_string())),
+        // The deprecated `Read` method wraps the value in another variant
+        Value::Value(inner) => setting_value(inner),
+        _ => None,
+    }
+}
+
+/// `org.freedesktop.portal.Settings` on the session bus
+#[derive(Debug, Clone)]
+pub struct Portal {
+    proxy: zbus::blocking::Proxy<'static>,
+}
+
+impl Portal {
+    pub fn connect() -> zbus::Result<Self> {
+        let connection = zbus::blocking::Connection::session()?;
+        let proxy = zbus::blocking::Proxy::new(
+            &connection,
+            "org.freedesktop.portal.Desktop",
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Settings",
+        )?;
+        Ok(Self { proxy })
+    }
+
+    /// Call `on_change` after each change of a setting the scheme depends on; runs until the
+    /// bus connection closes
+    fn watch(&self, mut on_change: impl FnMut()) -> zbus::Result<()> {
+        for message in self.proxy.receive_signal("SettingChanged")? {
+            let Ok((namespace, key, _)) =
+                message.body().deserialize::<(String, String, OwnedValue)>()
+            else {
+                continue;
+            };
+            if affects_scheme(&namespace, &key) {
+                on_change();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SettingsSource for Portal {
+    fn read(&self, namespace: &str, key: &str) -> Option<SettingValue> {
+        let value: OwnedValue = self
+            .proxy
+            .call("ReadOne", &(namespace, key))
+            .or_else(|_| self.proxy.call("Read", &(namespace, key)))
+            .ok()?;
+        setting_value(&value)
+    }
+}
+
+/// The desktop colour scheme, kept current by a thread listening to the portal
+#[derive(Debug, Clone)]
+pub struct SystemTheme {
+    scheme: Arc<Mutex<Option<ColorScheme>>>,
+}
+
+impl SystemTheme {
+    /// Detect the scheme and follow changes in a background thread, repainting `ctx`
+    ///
+    /// The scheme is unknown until the portal has answered, so a slow or missing portal
+    /// doesn't hold up the window.
+    pub fn start(ctx: &egui::Context) -> Self {
+        let scheme = Arc::new(Mutex::new(None));
+        let shared = Arc::clone(&scheme);
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let gtk_theme_env = std::env::var("GTK_THEME").ok();
+            let portal = Portal::connect().ok();
+            let update = || {
+                let source = portal.as_ref().map(|p| p as &dyn SettingsSource);
+                let detected = detect(source, gtk_theme_env.as_deref());
+                *shared.lock().unwrap_or_else(PoisonError::into_inner) = detected;
+                ctx.request_repaint();
+            };
+            update();
+            if let Some(portal) = &portal {
+                let _ = portal.watch(update);
+            }
+        });
+        Self { scheme }
+    }
+
+    pub fn scheme(&self) -> Option<ColorScheme> {
+        *self.scheme.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -1,6 +1,7 @@
 use super::types::{ColumnVisibility, SortColumn, SortState};
 use crate::hyprland::KeyBindEntry;
-use crate::ui::styling::icons::get_icon;
+use crate::ui::styling::css::{Element, ElementStyle, element_style};
+use crate::ui::styling::icons::{get_icon, get_key_icon};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 
@@ -30,7 +31,7 @@ pub fn render_sort_button(
 
     let button = egui::Button::new(egui::RichText::new(button_text).strong().size(14.0))
         .fill(egui::Color32::TRANSPARENT)
-        .stroke(egui::Stroke::new(1.0, ui.visuals().hyperlink_color));
+        .stroke(egui::Stroke::new(1.0_f32, ui.visuals().hyperlink_color));
 
     ui.add(button).clicked()
 }
@@ -73,51 +74,47 @@ pub(crate) fn is_nerd_font_icon(text: &str) -> bool {
     })
 }
 
-fn render_keybind_cell(ui: &mut egui::Ui, entry: &KeyBindEntry) {
-    ui.add_space(8.0);
+/// Element styles from the CSS file used by the table
+struct TableStyles {
+    row: ElementStyle,
+    even_row: ElementStyle,
+    odd_row: ElementStyle,
+    keycap: ElementStyle,
+    modifier: ElementStyle,
+    description: ElementStyle,
+    command: ElementStyle,
+}
 
-    if !entry.modifiers.is_empty() {
-        let modifiers: Vec<&str> = entry.modifiers.split('+').collect();
-        for (i, modifier_str) in modifiers.iter().enumerate() {
-            let icon_text = get_icon(modifier_str);
-            let is_icon = is_nerd_font_icon(&icon_text);
-
-            let key_frame = egui::Frame::new()
-                .inner_margin(egui::Margin {
-                    left: KEY_FRAME_MARGIN_LEFT,
-                    right: if is_icon {
-                        KEY_FRAME_MARGIN_RIGHT_ICON
-                    } else {
-                        KEY_FRAME_MARGIN_RIGHT_TEXT
-                    },
-                    top: KEY_FRAME_MARGIN_VERTICAL,
-                    bottom: KEY_FRAME_MARGIN_VERTICAL,
-                })
-                .corner_radius(6.0)
-                .fill(ui.visuals().widgets.inactive.bg_fill)
-                .stroke(egui::Stroke::new(1.5, ui.visuals().hyperlink_color));
-
-            key_frame.show(ui, |ui: &mut egui::Ui| {
-                ui.label(
-                    egui::RichText::new(&icon_text)
-                        .size(13.0)
-                        .family(egui::FontFamily::Proportional),
-                );
-            });
-            if i < modifiers.len() - 1 {
-                ui.label(egui::RichText::new("+").size(12.0).weak());
-            }
+impl TableStyles {
+    fn from_ctx(ctx: &egui::Context) -> Self {
+        let style = |element| element_style(ctx, element);
+        Self {
+            row: style(Element::Row),
+            even_row: style(Element::EvenRow),
+            odd_row: style(Element::OddRow),
+            keycap: style(Element::Keycap),
+            modifier: style(Element::Modifier),
+            description: style(Element::Description),
+            command: style(Element::Command),
         }
-        ui.label(egui::RichText::new("+").size(12.0).weak());
     }
 
-    let key_icon_text = get_icon(&entry.key);
-    let is_key_icon = is_nerd_font_icon(&key_icon_text);
+    /// Style of the row at `idx`; CSS counts rows from 1, so index 1 is even
+    const fn row(&self, idx: usize) -> &ElementStyle {
+        if idx % 2 == 1 {
+            &self.even_row
+        } else {
+            &self.odd_row
+        }
+    }
+}
 
+fn render_keycap(ui: &mut egui::Ui, text: &str, style: &ElementStyle) {
+    let is_icon = is_nerd_font_icon(text);
     let key_frame = egui::Frame::new()
         .inner_margin(egui::Margin {
             left: KEY_FRAME_MARGIN_LEFT,
-            right: if is_key_icon {
+            right: if is_icon {
                 KEY_FRAME_MARGIN_RIGHT_ICON
             } else {
                 KEY_FRAME_MARGIN_RIGHT_TEXT
@@ -127,37 +124,147 @@ fn render_keybind_cell(ui: &mut egui::Ui, entry: &KeyBindEntry) {
         })
         .corner_radius(6.0)
         .fill(ui.visuals().widgets.inactive.bg_fill)
-        .stroke(egui::Stroke::new(1.5, ui.visuals().hyperlink_color));
+        .stroke(egui::Stroke::new(1.5_f32, ui.visuals().hyperlink_color));
 
-    key_frame.show(ui, |ui| {
+    style.frame(key_frame).show(ui, |ui: &mut egui::Ui| {
+        let text = egui::RichText::new(text)
+            .size(13.0)
+            .family(egui::FontFamily::Proportional);
+        // The frame draws the background
         ui.label(
-            egui::RichText::new(&key_icon_text)
-                .size(13.0)
-                .family(egui::FontFamily::Proportional),
+            ElementStyle {
+                background: None,
+                ..*style
+            }
+            .text(text),
         );
     });
+}
+
+fn render_keybind_cell(ui: &mut egui::Ui, entry: &KeyBindEntry, styles: &TableStyles) {
+    ui.add_space(8.0);
+
+    if !entry.modifiers.is_empty() {
+        let modifiers: Vec<&str> = entry.modifiers.split('+').collect();
+        for (i, modifier_str) in modifiers.iter().enumerate() {
+            render_keycap(ui, &get_icon(modifier_str), &styles.modifier);
+            if i < modifiers.len() - 1 {
+                ui.label(egui::RichText::new("+").size(12.0).weak());
+            }
+        }
+        ui.label(egui::RichText::new("+").size(12.0).weak());
+    }
+
+    render_keycap(ui, &get_key_icon(entry.normalized_key()), &styles.keycap);
 
     ui.label(egui::RichText::new(" ").size(12.0));
 }
 
-fn render_description_cell(ui: &mut egui::Ui, entry: &KeyBindEntry) {
+/// Outcome of an inline description edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditResult {
+    Save,
+    Cancel,
+}
+
+/// User interactions with the table during a frame
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TableResponse {
+    pub clicked_column: Option<SortColumn>,
+    pub clicked_row: Option<usize>,
+    /// Row whose description cell was double-clicked
+    pub edit_row: Option<usize>,
+    pub edit_result: Option<EditResult>,
+}
+
+fn render_description_editor(ui: &mut egui::Ui, text: &mut String) -> Option<EditResult> {
+    ui.add_space(8.0);
+    let response = ui.add(
+        egui::TextEdit::singleline(text)
+            .id(egui::Id::new("description_editor"))
+            .hint_text("Description (Enter to save, Esc to cancel)")
+            .desired_width(f32::INFINITY),
+    );
+    if response.lost_focus() {
+        if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            return Some(EditResult::Save);
+        }
+        return Some(EditResult::Cancel);
+    }
+    None
+}
+
+fn render_description_cell(ui: &mut egui::Ui, entry: &KeyBindEntry, style: &ElementStyle) {
     ui.add_space(8.0);
     let description = if entry.description.is_empty() {
         egui::RichText::new("-")
             .weak()
             .family(egui::FontFamily::Proportional)
     } else {
-        egui::RichText::new(&entry.description)
-            .family(egui::FontFamily::Proportional)
-            .extra_letter_spacing(1.0)
+        style.text(
+            egui::RichText::new(&entry.description)
+                .family(egui::FontFamily::Proportional)
+                .extra_letter_spacing(1.0),
+        )
     };
     ui.label(description);
 }
 
-fn render_command_cell(ui: &mut egui::Ui, entry: &KeyBindEntry) {
+fn render_command_cell(ui: &mut egui::Ui, entry: &KeyBindEntry, style: &ElementStyle) {
     ui.add_space(8.0);
-    ui.label(egui::RichText::new(&entry.command).size(12.0))
-        .on_hover_text(&entry.command);
+    ui.label(
+        style.text(
+            egui::RichText::new(&entry.command)
+                .size(12.0)
+                .family(egui::FontFamily::Monospace),
+        ),
+    )
+    .on_hover_text(&entry.command);
+}
+
+fn render_category_cell(ui: &mut egui::Ui, entry: &KeyBindEntry) {
+    ui.add_space(8.0);
+    ui.label(egui::RichText::new(&entry.category).size(12.0).weak());
+}
+
+fn render_group_header(
+    row: &mut egui_extras::TableRow<'_, '_>,
+    category: &str,
+    column_visibility: &ColumnVisibility,
+) {
+    for i in 0..visible_column_count(column_visibility) {
+        row.col(|ui| {
+            let rect = ui.max_rect();
+            ui.painter()
+                .rect_filled(rect, 0.0, ui.visuals().faint_bg_color);
+            if i == 0 {
+                ui.add_space(8.0);
+                ui.label(
+                    egui::RichText::new(category)
+                        .strong()
+                        .size(13.0)
+                        .color(ui.visuals().hyperlink_color),
+                );
+            }
+        });
+    }
+}
+
+/// Set the row height, paint the row's CSS background and highlight the cell if its row is selected
+fn paint_cell_background(ui: &mut egui::Ui, selected: bool, style: &ElementStyle) {
+    ui.set_min_height(32.0);
+    if let Some(background) = style.background {
+        let radius = style.border_radius.unwrap_or_default();
+        ui.painter().rect_filled(ui.max_rect(), radius, background);
+    }
+    if let Some(color) = style.color {
+        ui.visuals_mut().override_text_color = Some(color);
+    }
+    if selected {
+        let rect = ui.max_rect();
+        let hl = ui.visuals().selection.bg_fill;
+        ui.painter().rect_filled(rect, 0.0, hl);
+    }
 }
 
 fn add_table_column(
@@ -183,18 +290,23 @@ fn add_table_column(
     }
 }
 
-fn build_table_columns<'a>(
-    mut table: TableBuilder<'a>,
-    column_visibility: &ColumnVisibility,
-) -> TableBuilder<'a> {
-    let visible_count = [
+fn visible_column_count(column_visibility: &ColumnVisibility) -> usize {
+    [
         column_visibility.keybind,
         column_visibility.description,
         column_visibility.command,
+        column_visibility.category,
     ]
     .iter()
     .filter(|&&v| v)
-    .count();
+    .count()
+}
+
+fn build_table_columns<'a>(
+    mut table: TableBuilder<'a>,
+    column_visibility: &ColumnVisibility,
+) -> TableBuilder<'a> {
+    let visible_count = visible_column_count(column_visibility);
 
     let mut col_index = 0;
 
@@ -210,119 +322,179 @@ fn build_table_columns<'a>(
         col_index += 1;
         table = add_table_column(table, col_index == visible_count, 300.0, 200.0);
     }
+    if column_visibility.category {
+        col_index += 1;
+        table = add_table_column(table, col_index == visible_count, 150.0, 80.0);
+    }
 
     table
 }
 
-pub fn render_table(
-    ui: &mut egui::Ui,
-    filtered: &[KeyBindEntry],
+fn render_table_header(
+    header: &mut egui_extras::TableRow<'_, '_>,
     column_visibility: &ColumnVisibility,
     sort_column: SortColumn,
     sort_state: SortState,
-    selected_row: Option<usize>,
 ) -> Option<SortColumn> {
+    let mut clicked_column = None;
+    if column_visibility.keybind {
+        header.col(|ui| {
+            if render_header_cell(ui, "Keybind", SortColumn::Keybind, sort_column, sort_state) {
+                clicked_column = Some(SortColumn::Keybind);
+            }
+        });
+    }
+    if column_visibility.description {
+        header.col(|ui| {
+            if render_header_cell(
+                ui,
+                "Description",
+                SortColumn::Description,
+                sort_column,
+                sort_state,
+            ) {
+                clicked_column = Some(SortColumn::Description);
+            }
+        });
+    }
+    if column_visibility.command {
+        header.col(|ui| {
+            if render_header_cell(ui, "Command", SortColumn::Command, sort_column, sort_state) {
+                clicked_column = Some(SortColumn::Command);
+            }
+        });
+    }
+    if column_visibility.category {
+        header.col(|ui| {
+            if render_header_cell(
+                ui,
+                "Category",
+                SortColumn::Category,
+                sort_column,
+                sort_state,
+            ) {
+                clicked_column = Some(SortColumn::Category);
+            }
+        });
+    }
+    clicked_column
+}
+
+/// How the table is laid out
+pub struct TableView<'a> {
+    pub column_visibility: &'a ColumnVisibility,
+    pub sort_column: SortColumn,
+    pub sort_state: SortState,
+    /// Insert a header row before each category group
+    pub group_by_category: bool,
+    /// Descriptions can be edited by double-clicking them
+    pub editable: bool,
+}
+
+pub fn render_table(
+    ui: &mut egui::Ui,
+    filtered: &[KeyBindEntry],
+    view: &TableView<'_>,
+    selected_row: Option<usize>,
+    mut editing: Option<(usize, &mut String)>,
+) -> TableResponse {
+    let column_visibility = view.column_visibility;
+
     // Remove vertical lines by making separator invisible
     ui.style_mut().visuals.widgets.noninteractive.bg_stroke = egui::Stroke::NONE;
     ui.style_mut().visuals.widgets.inactive.bg_stroke = egui::Stroke::NONE;
 
+    let styles = TableStyles::from_ctx(ui.ctx());
+
+    // Row backgrounds from CSS replace the stripes
+    let striped = [styles.row, styles.even_row, styles.odd_row]
+        .iter()
+        .all(|style| style.background.is_none());
     let table = TableBuilder::new(ui)
-        .striped(true)
+        .striped(striped)
         .resizable(true)
+        .sense(egui::Sense::click())
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center));
 
     let table = build_table_columns(table, column_visibility);
 
-    let mut clicked_column = None;
+    let mut response = TableResponse::default();
 
     table
         .header(35.0, |mut header| {
-            if column_visibility.keybind {
-                header.col(|ui| {
-                    if render_header_cell(
-                        ui,
-                        "Keybind",
-                        SortColumn::Keybind,
-                        sort_column,
-                        sort_state,
-                    ) {
-                        clicked_column = Some(SortColumn::Keybind);
-                    }
-                });
-            }
-            if column_visibility.description {
-                header.col(|ui| {
-                    if render_header_cell(
-                        ui,
-                        "Description",
-                        SortColumn::Description,
-                        sort_column,
-                        sort_state,
-                    ) {
-                        clicked_column = Some(SortColumn::Description);
-                    }
-                });
-            }
-            if column_visibility.command {
-                header.col(|ui| {
-                    if render_header_cell(
-                        ui,
-                        "Command",
-                        SortColumn::Command,
-                        sort_column,
-                        sort_state,
-                    ) {
-                        clicked_column = Some(SortColumn::Command);
-                    }
-                });
-            }
+            response.clicked_column = render_table_header(
+                &mut header,
+                column_visibility,
+                view.sort_column,
+                view.sort_state,
+            );
         })
         .body(|mut body| {
+            let mut current_category = None;
             for (idx, entry) in filtered.iter().enumerate() {
+                if view.group_by_category && current_category != Some(&entry.category) {
+                    current_category = Some(&entry.category);
+                    body.row(30.0, |mut row| {
+                        render_group_header(&mut row, &entry.category, column_visibility);
+                    });
+                }
+                let selected = selected_row == Some(idx);
+                let row_style = styles.row.merge(*styles.row(idx));
                 body.row(32.0, |mut row| {
                     if column_visibility.keybind {
-                        row.col(|ui| {
-                            ui.set_min_height(32.0);
-                            if let Some(sel) = selected_row
-                                && sel == idx
-                            {
-                                let rect = ui.max_rect();
-                                let hl = ui.visuals().selection.bg_fill;
-                                ui.painter().rect_filled(rect, 0.0, hl);
-                                ui.scroll_to_rect(rect, None);
+                        let (_, cell) = row.col(|ui| {
+                            paint_cell_background(ui, selected, &row_style);
+                            if selected {
+                                ui.scroll_to_rect(ui.max_rect(), None);
                             }
-                            render_keybind_cell(ui, entry);
+                            render_keybind_cell(ui, entry, &styles);
                         });
+                        if cell.clicked() {
+                            response.clicked_row = Some(idx);
+                        }
                     }
                     if column_visibility.description {
-                        row.col(|ui| {
-                            ui.set_min_height(32.0);
-                            if let Some(sel) = selected_row
-                                && sel == idx
-                            {
-                                let rect = ui.max_rect();
-                                let hl = ui.visuals().selection.bg_fill;
-                                ui.painter().rect_filled(rect, 0.0, hl);
+                        let (_, cell) = row.col(|ui| {
+                            paint_cell_background(ui, selected, &row_style);
+                            match editing.as_mut() {
+                                Some((row_idx, text)) if *row_idx == idx => {
+                                    if let Some(result) = render_description_editor(ui, text) {
+                                        response.edit_result = Some(result);
+                                    }
+                                }
+                                _ => render_description_cell(ui, entry, &styles.description),
                             }
-                            render_description_cell(ui, entry);
                         });
+                        if view.editable && cell.double_clicked() {
+                            response.edit_row = Some(idx);
+                        } else if cell.clicked() {
+                            response.clicked_row = Some(idx);
+                        }
+                        if view.editable {
+                            cell.on_hover_text("Double-click to edit the description");
+                        }
                     }
                     if column_visibility.command {
-                        row.col(|ui| {
-                            ui.set_min_height(32.0);
-                            if let Some(sel) = selected_row
-                                && sel == idx
-                            {
-                                let rect = ui.max_rect();
-                                let hl = ui.visuals().selection.bg_fill;
-                                ui.painter().rect_filled(rect, 0.0, hl);
-                            }
-                            render_command_cell(ui, entry);
+                        let (_, cell) = row.col(|ui| {
+                            paint_cell_background(ui, selected, &row_style);
+                            render_command_cell(ui, entry, &styles.command);
                         });
+                        if cell.clicked() {
+                            response.clicked_row = Some(idx);
+                        }
+                    }
+                    if column_visibility.category {
+                        let (_, cell) = row.col(|ui| {
+                            paint_cell_background(ui, selected, &row_style);
+                            render_category_cell(ui, entry);
+                        });
+                        if cell.clicked() {
+                            response.clicked_row = Some(idx);
+                        }
                     }
                 });
             }
         });
 
-    clicked_column
+    response
 }
@@ -5,6 +5,7 @@ pub enum SortColumn {
     Keybind,
     Description,
     Command,
+    Category,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -14,17 +15,22 @@ pub enum SortState {
     None,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum Theme {
     Dark,
     Light,
+    /// Follow the desktop's colour scheme
+    System,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ColumnVisibility {
     pub keybind: bool,
     pub description: bool,
     pub command: bool,
+    #[serde(default)]
+    pub category: bool,
 }
 
 impl Default for ColumnVisibility {
@@ -33,6 +39,7 @@ impl Default for ColumnVisibility {
             keybind: true,
             description: true,
             command: false,
+            category: false,
         }
     }
 }